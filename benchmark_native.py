@@ -105,3 +105,69 @@ bench("clean_business_name", py_clean_business_name, rust_clean_business_name, N
 bench("extract_emails (HTML)", py_extract_emails, rust_extract_emails, [SAMPLE_HTML] * 100)
 bench("detect_cms (HTML)", lambda h: None, rust_detect_cms, [SAMPLE_HTML] * 100)
 bench("detect_tracking (HTML)", lambda h: {}, rust_detect_tracking, [SAMPLE_HTML] * 100)
+
+
+# analyze_tech_stack_batch scaling: same pages, growing rayon pool
+from _leadswarm_native import (
+    analyze_tech_stack as rust_analyze_tech_stack,
+    analyze_tech_stack_batch as rust_analyze_tech_stack_batch,
+)
+
+PAGES = [(f"page-{i}", SAMPLE_HTML) for i in range(400)]
+
+start = time.perf_counter()
+for _, html in PAGES:
+    rust_analyze_tech_stack(html)
+loop_time = time.perf_counter() - start
+print(f"\nanalyze_tech_stack x{len(PAGES)} (Python loop): {loop_time*1000:8.2f}ms")
+
+for threads in (1, 2, 4, 8):
+    start = time.perf_counter()
+    rust_analyze_tech_stack_batch(PAGES, num_threads=threads)
+    batch_time = time.perf_counter() - start
+    print(f"analyze_tech_stack_batch threads={threads}:      {batch_time*1000:8.2f}ms  Scaling: {loop_time / batch_time:.1f}x")
+
+# The SEO and accessibility audits reuse each page's single parse, so adding
+# them should cost the audits themselves, not another parse per page
+start = time.perf_counter()
+rust_analyze_tech_stack_batch(PAGES)
+plain_time = time.perf_counter() - start
+start = time.perf_counter()
+rust_analyze_tech_stack_batch(PAGES, include_seo=True, include_accessibility=True)
+audit_time = time.perf_counter() - start
+print(f"analyze_tech_stack_batch +seo +a11y:    {audit_time*1000:8.2f}ms  Overhead: {audit_time / plain_time:.2f}x")
+
+
+# score_prospects_batch vs a per-prospect loop (GIL released during the batch)
+from _leadswarm_native import (
+    calculate_fit_score as rust_calculate_fit_score,
+    calculate_opportunity_score as rust_calculate_opportunity_score,
+    score_prospects_batch as rust_score_prospects_batch,
+)
+
+PROSPECTS = [
+    {
+        "website": "https://example.com" if i % 3 else None,
+        "phone": "0412345678",
+        "emails": ["info@example.com"] if i % 2 else [],
+        "found_in_maps": True,
+        "maps_position": i % 7 + 1,
+        "rating": 3.5 + (i % 15) / 10,
+        "review_count": i % 120,
+        "found_in_ads": i % 4 == 0,
+        "found_in_organic": i % 5 != 0,
+        "organic_position": i % 20 + 1,
+        "signals": {"has_google_analytics": i % 2 == 0, "has_booking_system": False, "cms": "Wix"},
+    }
+    for i in range(100_000)
+]
+
+start = time.perf_counter()
+for p in PROSPECTS:
+    rust_calculate_fit_score(p)
+    rust_calculate_opportunity_score(p)
+loop_time = time.perf_counter() - start
+start = time.perf_counter()
+rust_score_prospects_batch(PROSPECTS)
+batch_time = time.perf_counter() - start
+print(f"\nscore_prospects_batch x{len(PROSPECTS)}:  Loop {loop_time*1000:8.2f}ms  Batch {batch_time*1000:8.2f}ms  Speedup: {loop_time / batch_time:.1f}x")
@@ -28,14 +28,86 @@ extract_phones = None
 detect_cms = None
 detect_tracking = None
 detect_booking_system = None
+detect_booking_vendor = None
+detect_online_ordering = None
+detect_payment_providers = None
+detect_review_widgets = None
+detect_video_embeds = None
+detect_hiring_signals = None
+detect_parked_domain = None
+detect_placeholder_page = None
+detect_error_page = None
 detect_frameworks = None
+detect_framework_versions = None
+detect_jquery = None
 detect_responsive = None
+analyze_responsive = None
+detect_wordpress_details = None
+detect_site_builder = None
+detect_page_builder = None
+detect_chat_widget = None
+extract_tracking_ids = None
+detect_consent_platform = None
+detect_captcha = None
+detect_forms = None
+detect_mixed_content = None
+detect_copyright_year = None
+detect_page_language = None
+seo_audit = None
+accessibility_audit = None
+extract_headings = None
+content_metrics = None
+audit_images = None
+performance_hints = None
+detect_hosting_hints = None
+modern_practices = None
+classify_links = None
+discover_feeds = None
+detect_amp = None
+extract_opening_hours = None
+extract_addresses = None
+detect_locations = None
+estimate_team_size = None
+detect_trust_badges = None
+detect_popups = None
+detect_legacy_markup = None
+detect_map_embed = None
+detect_ab_testing = None
+extract_business_ids = None
 analyze_tech_stack = None
+analyze_tech_stack_batch = None
 
 # Scoring (scoring/fit.py / scoring/opportunity.py)
 calculate_fit_score = None
 calculate_opportunity_score = None
 score_prospects_batch = None
+get_default_fit_weights = None
+explain_fit_score = None
+explain_opportunity_score = None
+rank_prospects = None
+grade_prospects = None
+register_scoring_profile = None
+get_default_opportunity_weights = None
+set_priority_formula = None
+get_priority_formula = None
+calculate_priority = None
+generate_opportunity_notes = None
+top_prospects = None
+score_deltas = None
+compare_weightings = None
+reputation_signal = None
+ScoringConfig = None
+load_scoring_config = None
+dump_scoring_config = None
+RuleSet = None
+compile_rules = None
+segment_prospects = None
+merge_duplicate_prospects = None
+prospect_content_hash = None
+score_prospects_incremental = None
+calculate_confidence = None
+score_statistics = None
+flag_outliers = None
 
 # Geo / cache (orchestrator.py / locations.py)
 fast_cache_key = None
@@ -48,6 +120,11 @@ serialize_prospects_json = None
 
 # HTML metadata extraction (crawler.py)
 extract_html_metadata = None
+extract_html_metadata_bytes = None
+extract_html_metadata_batch = None
+extract_social_profiles = None
+add_social_domains = None
+get_social_domains = None
 
 AVAILABLE = False
 
@@ -68,13 +145,85 @@ try:
     detect_cms = _n.detect_cms
     detect_tracking = _n.detect_tracking
     detect_booking_system = _n.detect_booking_system
+    detect_booking_vendor = _n.detect_booking_vendor
+    detect_online_ordering = _n.detect_online_ordering
+    detect_payment_providers = _n.detect_payment_providers
+    detect_review_widgets = _n.detect_review_widgets
+    detect_video_embeds = _n.detect_video_embeds
+    detect_hiring_signals = _n.detect_hiring_signals
+    detect_parked_domain = _n.detect_parked_domain
+    detect_placeholder_page = _n.detect_placeholder_page
+    detect_error_page = _n.detect_error_page
     detect_frameworks = _n.detect_frameworks
+    detect_framework_versions = _n.detect_framework_versions
+    detect_jquery = _n.detect_jquery
     detect_responsive = _n.detect_responsive
+    analyze_responsive = _n.analyze_responsive
+    detect_wordpress_details = _n.detect_wordpress_details
+    detect_site_builder = _n.detect_site_builder
+    detect_page_builder = _n.detect_page_builder
+    detect_chat_widget = _n.detect_chat_widget
+    extract_tracking_ids = _n.extract_tracking_ids
+    detect_consent_platform = _n.detect_consent_platform
+    detect_captcha = _n.detect_captcha
+    detect_forms = _n.detect_forms
+    detect_mixed_content = _n.detect_mixed_content
+    detect_copyright_year = _n.detect_copyright_year
+    detect_page_language = _n.detect_page_language
+    seo_audit = _n.seo_audit
+    accessibility_audit = _n.accessibility_audit
+    extract_headings = _n.extract_headings
+    content_metrics = _n.content_metrics
+    audit_images = _n.audit_images
+    performance_hints = _n.performance_hints
+    detect_hosting_hints = _n.detect_hosting_hints
+    modern_practices = _n.modern_practices
+    classify_links = _n.classify_links
+    discover_feeds = _n.discover_feeds
+    detect_amp = _n.detect_amp
+    extract_opening_hours = _n.extract_opening_hours
+    extract_addresses = _n.extract_addresses
+    detect_locations = _n.detect_locations
+    estimate_team_size = _n.estimate_team_size
+    detect_trust_badges = _n.detect_trust_badges
+    detect_popups = _n.detect_popups
+    detect_legacy_markup = _n.detect_legacy_markup
+    detect_map_embed = _n.detect_map_embed
+    detect_ab_testing = _n.detect_ab_testing
+    extract_business_ids = _n.extract_business_ids
     analyze_tech_stack = _n.analyze_tech_stack
+    analyze_tech_stack_batch = _n.analyze_tech_stack_batch
 
     calculate_fit_score = _n.calculate_fit_score
     calculate_opportunity_score = _n.calculate_opportunity_score
     score_prospects_batch = _n.score_prospects_batch
+    get_default_fit_weights = _n.get_default_fit_weights
+    explain_fit_score = _n.explain_fit_score
+    explain_opportunity_score = _n.explain_opportunity_score
+    rank_prospects = _n.rank_prospects
+    grade_prospects = _n.grade_prospects
+    register_scoring_profile = _n.register_scoring_profile
+    get_default_opportunity_weights = _n.get_default_opportunity_weights
+    set_priority_formula = _n.set_priority_formula
+    get_priority_formula = _n.get_priority_formula
+    calculate_priority = _n.calculate_priority
+    generate_opportunity_notes = _n.generate_opportunity_notes
+    top_prospects = _n.top_prospects
+    score_deltas = _n.score_deltas
+    compare_weightings = _n.compare_weightings
+    reputation_signal = _n.reputation_signal
+    ScoringConfig = _n.ScoringConfig
+    load_scoring_config = _n.load_scoring_config
+    dump_scoring_config = _n.dump_scoring_config
+    RuleSet = _n.RuleSet
+    compile_rules = _n.compile_rules
+    segment_prospects = _n.segment_prospects
+    merge_duplicate_prospects = _n.merge_duplicate_prospects
+    prospect_content_hash = _n.prospect_content_hash
+    score_prospects_incremental = _n.score_prospects_incremental
+    calculate_confidence = _n.calculate_confidence
+    score_statistics = _n.score_statistics
+    flag_outliers = _n.flag_outliers
 
     fast_cache_key = _n.fast_cache_key
     haversine_distance = _n.haversine_distance
@@ -84,6 +233,11 @@ try:
     serialize_prospects_json = _n.serialize_prospects_json
 
     extract_html_metadata = _n.extract_html_metadata
+    extract_html_metadata_bytes = _n.extract_html_metadata_bytes
+    extract_html_metadata_batch = _n.extract_html_metadata_batch
+    extract_social_profiles = _n.extract_social_profiles
+    add_social_domains = _n.add_social_domains
+    get_social_domains = _n.get_social_domains
 
     AVAILABLE = True
     _logger.info("Rust native acceleration loaded successfully")
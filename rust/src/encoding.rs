@@ -0,0 +1,181 @@
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes, PyString};
+use regex::bytes::Regex;
+use std::sync::LazyLock;
+
+// ---------------------------------------------------------------------------
+// Static data
+// ---------------------------------------------------------------------------
+
+/// How far into the document a `<meta charset>` declaration is looked for.
+/// HTML requires it within the first 1024 bytes; real pages are sloppier.
+const SNIFF_WINDOW: usize = 4096;
+
+static META_CHARSET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<meta[^>]+charset\s*=\s*["']?\s*([a-z0-9_:.\-]+)"#).unwrap()
+});
+
+// Labels that browsers decode as windows-1252 regardless of what they claim.
+static WINDOWS_1252_ALIASES: &[&str] = &[
+    "ascii",
+    "us-ascii",
+    "iso-8859-1",
+    "iso8859-1",
+    "latin1",
+    "latin-1",
+    "l1",
+    "cp1252",
+    "windows-1252",
+];
+
+// ---------------------------------------------------------------------------
+// Detection
+// ---------------------------------------------------------------------------
+
+/// HTML received from Python, decoded to UTF-8.
+pub(crate) struct HtmlInput {
+    pub text: String,
+    /// The encoding the bytes were decoded with; `None` when a `str` was passed.
+    pub encoding: Option<String>,
+}
+
+/// Accept either `str` or `bytes`/`bytearray` HTML. Strings are used as-is;
+/// bytes are sniffed and decoded lossily so undecodable bytes never raise.
+pub(crate) fn read_html(html: &Bound<'_, PyAny>) -> PyResult<HtmlInput> {
+    if let Ok(s) = html.downcast::<PyString>() {
+        return Ok(HtmlInput { text: s.to_str()?.to_string(), encoding: None });
+    }
+
+    let bytes = if let Ok(b) = html.downcast::<PyBytes>() {
+        b.as_bytes().to_vec()
+    } else if let Ok(b) = html.downcast::<PyByteArray>() {
+        b.to_vec()
+    } else {
+        return Err(PyTypeError::new_err(format!(
+            "html must be str or bytes, not {}",
+            html.get_type().name()?
+        )));
+    };
+
+    decode_html_bytes(html.py(), &bytes, None)
+}
+
+/// Decode a raw response body. The charset comes from, in order: a byte order
+/// mark, the `charset=` param of `content_type` (the Content-Type header), a
+/// `<meta charset>` declaration, then the byte-level guess.
+pub(crate) fn decode_html_bytes(
+    py: Python<'_>,
+    bytes: &[u8],
+    content_type: Option<&str>,
+) -> PyResult<HtmlInput> {
+    let (encoding, bom_len) = sniff_encoding(py, bytes, content_type);
+    let text = decode(py, &bytes[bom_len..], &encoding)?;
+    Ok(HtmlInput { text, encoding: Some(encoding) })
+}
+
+/// Return the encoding label for `bytes` and the length of any byte order mark.
+fn sniff_encoding(py: Python<'_>, bytes: &[u8], content_type: Option<&str>) -> (String, usize) {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return ("utf-8".to_string(), 3);
+    }
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return ("utf-16-le".to_string(), 2);
+    }
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        return ("utf-16-be".to_string(), 2);
+    }
+
+    if let Some(encoding) = content_type
+        .and_then(header_charset)
+        .and_then(|label| normalize_label(py, &label))
+    {
+        return (encoding, 0);
+    }
+
+    // `<meta charset="...">` and `<meta http-equiv="content-type"
+    // content="text/html; charset=...">` both end in `charset=<label>`.
+    let head = &bytes[..bytes.len().min(SNIFF_WINDOW)];
+    if let Some(label) = META_CHARSET_RE
+        .captures(head)
+        .and_then(|c| c.get(1))
+        .map(|m| String::from_utf8_lossy(m.as_bytes()).to_lowercase())
+    {
+        if let Some(encoding) = normalize_label(py, &label) {
+            return (encoding, 0);
+        }
+    }
+
+    (guess_encoding(bytes).to_string(), 0)
+}
+
+/// The `charset` parameter of a Content-Type value, e.g.
+/// `text/html; charset="ISO-8859-1"` → `iso-8859-1`.
+fn header_charset(content_type: &str) -> Option<String> {
+    let label = content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim().eq_ignore_ascii_case("charset").then(|| {
+            value.trim().trim_matches(|c| c == '"' || c == '\'').to_lowercase()
+        })
+    })?;
+    (!label.is_empty()).then_some(label)
+}
+
+fn normalize_label(py: Python<'_>, label: &str) -> Option<String> {
+    if WINDOWS_1252_ALIASES.contains(&label) {
+        return Some("windows-1252".to_string());
+    }
+    if label == "utf8" || label == "utf-8" {
+        return Some("utf-8".to_string());
+    }
+    // Only trust labels Python can actually decode.
+    let codecs = py.import("codecs").ok()?;
+    codecs.call_method1("lookup", (label,)).ok()?;
+    Some(label.to_string())
+}
+
+/// Heuristic fallback when nothing declares an encoding: valid UTF-8 is
+/// UTF-8, byte pairs that all fit Shift_JIS are Shift_JIS, anything else is
+/// treated as windows-1252, which can decode every byte.
+fn guess_encoding(bytes: &[u8]) -> &'static str {
+    if std::str::from_utf8(bytes).is_ok() {
+        return "utf-8";
+    }
+    if looks_like_shift_jis(bytes) {
+        return "shift_jis";
+    }
+    "windows-1252"
+}
+
+fn looks_like_shift_jis(bytes: &[u8]) -> bool {
+    let mut pairs = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b < 0x80 || (0xA1..=0xDF).contains(&b) {
+            // ASCII or half-width katakana
+            i += 1;
+            continue;
+        }
+        let lead = (0x81..=0x9F).contains(&b) || (0xE0..=0xFC).contains(&b);
+        let trail = bytes.get(i + 1).copied().unwrap_or(0);
+        if !lead || !((0x40..=0x7E).contains(&trail) || (0x80..=0xFC).contains(&trail)) {
+            return false;
+        }
+        pairs += 1;
+        i += 2;
+    }
+    pairs > 0
+}
+
+fn decode(py: Python<'_>, bytes: &[u8], encoding: &str) -> PyResult<String> {
+    if encoding == "utf-8" {
+        return Ok(String::from_utf8_lossy(bytes).into_owned());
+    }
+    // Python's cp1252 leaves five bytes undefined; browsers map them to C1
+    // controls, which `replace` turns into U+FFFD — harmless for matching.
+    let codec = if encoding == "windows-1252" { "cp1252" } else { encoding };
+    PyBytes::new(py, bytes)
+        .call_method1("decode", (codec, "replace"))?
+        .extract()
+}
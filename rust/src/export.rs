@@ -1,7 +1,8 @@
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
 use std::collections::HashMap;
 
+use crate::scoring::{prospect_fields, Fields};
+
 // ---------------------------------------------------------------------------
 // Helpers – reuse the same extract pattern from scoring.rs
 // ---------------------------------------------------------------------------
@@ -36,7 +37,7 @@ fn extract_opt_bool(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str)
         .flatten()
 }
 
-fn extract_string_list(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> Vec<String> {
+pub(crate) fn extract_string_list(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> Vec<String> {
     map.get(key)
         .and_then(|obj| obj.extract::<Option<Vec<String>>>(py).ok())
         .flatten()
@@ -52,7 +53,11 @@ fn yes_no(val: bool) -> &'static str {
 }
 
 fn extract_signals(py: Python<'_>, map: &HashMap<String, PyObject>) -> Option<HashMap<String, PyObject>> {
-    map.get("signals")
+    extract_nested(py, map, "signals")
+}
+
+fn extract_nested(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> Option<HashMap<String, PyObject>> {
+    map.get(key)
         .and_then(|obj| obj.extract::<Option<HashMap<String, PyObject>>>(py).ok())
         .flatten()
 }
@@ -69,13 +74,65 @@ const CSV_FIELDS: &[&str] = &[
     "has_google_analytics", "has_booking_system",
 ];
 
+// Appended after CSV_FIELDS when any prospect carries a grade
+const CSV_GRADE_FIELD: &str = "grade";
+// Then when any prospect carries a reputation_signal classification
+const CSV_REPUTATION_FIELD: &str = "reputation";
+// Optional columns added later go after those, so the columns before them
+// keep their positions: a segment_prospects tier, then a
+// calculate_confidence score
+const CSV_TIER_FIELD: &str = "tier";
+const CSV_CONFIDENCE_FIELD: &str = "confidence";
+
+// Attributes read from non-dict prospects
+const CSV_OBJECT_FIELDS: &[&str] = &[
+    "name", "website", "phone", "address", "emails", "rating", "review_count", "fit_score",
+    "opportunity_score", "priority_score", "opportunity_notes", "found_in_ads", "found_in_maps",
+    "found_in_organic", "grade", "reputation", "tier", "confidence",
+];
+const CSV_SIGNAL_FIELDS: &[&str] = &["cms", "has_google_analytics", "has_booking_system"];
+
+/// Prospects as CSV text. A trailing `grade` column is added only when some
+/// prospect has a non-empty `"grade"`, so ungraded exports are unchanged;
+/// likewise a `reputation` column for `"reputation"` (the
+/// `reputation_signal` classification), a `tier` column for `"tier"` (from
+/// `segment_prospects`) and a last `confidence` column for `"confidence"`
+/// (from `calculate_confidence`).
+///
+/// Prospects may be dicts or objects carrying the same fields as attributes.
 #[pyfunction]
-pub fn serialize_prospects_csv(prospects: Vec<HashMap<String, PyObject>>) -> PyResult<String> {
+pub fn serialize_prospects_csv(prospects: Vec<Bound<'_, PyAny>>) -> PyResult<String> {
+    let nested: &[(&str, &[&str])] = &[("signals", CSV_SIGNAL_FIELDS)];
+    let prospects = prospects
+        .iter()
+        .map(|p| prospect_fields(p, CSV_OBJECT_FIELDS, nested))
+        .collect::<PyResult<Vec<Fields>>>()?;
     Python::with_gil(|py| {
         let mut wtr = csv::Writer::from_writer(Vec::new());
+        let with_grade = prospects.iter()
+            .any(|p| extract_opt_string(py, p, "grade").is_some_and(|g| !g.is_empty()));
+        let with_tier = prospects.iter()
+            .any(|p| extract_opt_string(py, p, "tier").is_some_and(|t| !t.is_empty()));
+        let with_confidence = prospects.iter()
+            .any(|p| extract_opt_i64(py, p, "confidence").is_some());
+        let with_reputation = prospects.iter()
+            .any(|p| extract_opt_string(py, p, "reputation").is_some_and(|r| !r.is_empty()));
 
         // Header
-        wtr.write_record(CSV_FIELDS)
+        let mut header = CSV_FIELDS.to_vec();
+        if with_grade {
+            header.push(CSV_GRADE_FIELD);
+        }
+        if with_reputation {
+            header.push(CSV_REPUTATION_FIELD);
+        }
+        if with_tier {
+            header.push(CSV_TIER_FIELD);
+        }
+        if with_confidence {
+            header.push(CSV_CONFIDENCE_FIELD);
+        }
+        wtr.write_record(&header)
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
 
         for p in &prospects {
@@ -108,7 +165,7 @@ pub fn serialize_prospects_csv(prospects: Vec<HashMap<String, PyObject>>) -> PyR
                 .map(|v| format!("{:.1}", v))
                 .unwrap_or_else(|| "0.0".to_string());
 
-            let record: Vec<String> = vec![
+            let mut record: Vec<String> = vec![
                 str_or_empty(extract_opt_string(py, p, "name")),
                 str_or_empty(extract_opt_string(py, p, "website")),
                 str_or_empty(extract_opt_string(py, p, "phone")),
@@ -127,6 +184,19 @@ pub fn serialize_prospects_csv(prospects: Vec<HashMap<String, PyObject>>) -> PyR
                 yes_no(has_analytics).to_string(),
                 yes_no(has_booking).to_string(),
             ];
+            if with_grade {
+                record.push(str_or_empty(extract_opt_string(py, p, "grade")));
+            }
+            if with_reputation {
+                record.push(str_or_empty(extract_opt_string(py, p, "reputation")));
+            }
+            if with_tier {
+                record.push(str_or_empty(extract_opt_string(py, p, "tier")));
+            }
+            if with_confidence {
+                let confidence = extract_opt_i64(py, p, "confidence");
+                record.push(confidence.map(|v| v.to_string()).unwrap_or_default());
+            }
 
             wtr.write_record(&record)
                 .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
@@ -143,6 +213,18 @@ pub fn serialize_prospects_csv(prospects: Vec<HashMap<String, PyObject>>) -> PyR
 // JSON serialization – matches prospect_to_dict() nested structure
 // ---------------------------------------------------------------------------
 
+// Attributes read from non-dict prospects
+const JSON_OBJECT_FIELDS: &[&str] = &[
+    "name", "website", "domain", "phone", "address", "emails", "found_in_ads", "ad_position",
+    "found_in_maps", "maps_position", "found_in_organic", "organic_position", "rating",
+    "review_count", "category", "fit_score", "opportunity_score", "priority_score",
+    "opportunity_notes", "source", "scraped_at", "reputation", "tier", "confidence",
+];
+const JSON_SIGNAL_FIELDS: &[&str] = &[
+    "reachable", "cms", "has_google_analytics", "has_facebook_pixel", "has_google_ads",
+    "has_booking_system", "load_time_ms", "title", "meta_description", "social_links",
+];
+
 fn prospect_to_json_value(py: Python<'_>, p: &HashMap<String, PyObject>) -> serde_json::Value {
     let emails = extract_string_list(py, p, "emails");
     let signals = extract_signals(py, p);
@@ -175,22 +257,34 @@ fn prospect_to_json_value(py: Python<'_>, p: &HashMap<String, PyObject>) -> serd
     data.insert("serp_presence".into(), serp);
 
     // google_business
-    let gb = serde_json::json!({
+    let mut gb = serde_json::json!({
         "rating": json_opt_f64(extract_opt_f64(py, p, "rating")),
         "review_count": json_opt_i64(extract_opt_i64(py, p, "review_count")),
         "category": json_opt_str(extract_opt_string(py, p, "category")),
     });
+    // Only when set (reputation_signal), so other exports are unchanged
+    if let Some(reputation) = extract_opt_string(py, p, "reputation") {
+        gb["reputation"] = serde_json::Value::String(reputation);
+    }
     data.insert("google_business".into(), gb);
 
     // scores
     let priority = extract_opt_f64(py, p, "priority_score")
         .map(|v| (v * 100.0).round() / 100.0)
         .unwrap_or(0.0);
-    let scores = serde_json::json!({
+    let mut scores = serde_json::json!({
         "fit": extract_opt_i64(py, p, "fit_score").unwrap_or(0),
         "opportunity": extract_opt_i64(py, p, "opportunity_score").unwrap_or(0),
         "priority": priority,
     });
+    // Only when set (segment_prospects), so other exports are unchanged
+    if let Some(tier) = extract_opt_string(py, p, "tier") {
+        scores["tier"] = serde_json::Value::String(tier);
+    }
+    // Likewise only when set (calculate_confidence)
+    if let Some(confidence) = extract_opt_i64(py, p, "confidence") {
+        scores["confidence"] = confidence.into();
+    }
     data.insert("scores".into(), scores);
 
     data.insert("opportunity_notes".into(), json_opt_str(extract_opt_string(py, p, "opportunity_notes")));
@@ -221,6 +315,27 @@ fn prospect_to_json_value(py: Python<'_>, p: &HashMap<String, PyObject>) -> serd
         data.insert("signals".into(), sig_val);
     }
 
+    // delta (entries from score_deltas)
+    if let Some(delta) = extract_nested(py, p, "delta") {
+        let previous = extract_nested(py, &delta, "previous").map(|prev| {
+            serde_json::json!({
+                "fit": json_opt_i64(extract_opt_i64(py, &prev, "fit")),
+                "opportunity": json_opt_i64(extract_opt_i64(py, &prev, "opportunity")),
+                "priority": json_opt_f64(extract_opt_f64(py, &prev, "priority")),
+            })
+        });
+        let delta_val = serde_json::json!({
+            "status": json_opt_str(extract_opt_string(py, &delta, "status")),
+            "key": json_opt_str(extract_opt_string(py, &delta, "key")),
+            "fit": json_opt_i64(extract_opt_i64(py, &delta, "fit")),
+            "opportunity": json_opt_i64(extract_opt_i64(py, &delta, "opportunity")),
+            "priority": json_opt_f64(extract_opt_f64(py, &delta, "priority")),
+            "changed_fields": extract_string_list(py, &delta, "changed_fields"),
+            "previous": previous,
+        });
+        data.insert("delta".into(), delta_val);
+    }
+
     serde_json::Value::Object(data)
 }
 
@@ -247,8 +362,14 @@ fn json_opt_f64(opt: Option<f64>) -> serde_json::Value {
     }
 }
 
+/// Prospects as a JSON array; dicts or objects, as for `serialize_prospects_csv`.
 #[pyfunction]
-pub fn serialize_prospects_json(prospects: Vec<HashMap<String, PyObject>>, pretty: bool) -> PyResult<String> {
+pub fn serialize_prospects_json(prospects: Vec<Bound<'_, PyAny>>, pretty: bool) -> PyResult<String> {
+    let nested: &[(&str, &[&str])] = &[("signals", JSON_SIGNAL_FIELDS)];
+    let prospects = prospects
+        .iter()
+        .map(|p| prospect_fields(p, JSON_OBJECT_FIELDS, nested))
+        .collect::<PyResult<Vec<Fields>>>()?;
     Python::with_gil(|py| {
         let items: Vec<serde_json::Value> = prospects
             .iter()
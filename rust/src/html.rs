@@ -1,8 +1,11 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use rayon::prelude::*;
 use regex::Regex;
+use scraper::{node::Node, ElementRef, Html, Selector};
 use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
+use url::Url;
 
 // ---------------------------------------------------------------------------
 // Compiled regexes
@@ -14,13 +17,26 @@ static EMAIL_RE: LazyLock<Regex> = LazyLock::new(|| {
 
 static PHONE_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
     vec![
-        Regex::new(r"(?:\+61|0)[2-478](?:[ \-]?\d){8}").unwrap(),
-        Regex::new(r"\(\d{2}\)[ \-]?\d{4}[ \-]?\d{4}").unwrap(),
-        Regex::new(r"1[38]00[ \-]?\d{3}[ \-]?\d{3}").unwrap(),
+        // +61 may be followed by a separator and a bracketed trunk zero: +61 (0)2 ...
+        Regex::new(r"(?:\+61[ \-.]?(?:\(0\)[ \-.]?)?|0)[2-478](?:[ \-.]?\d){8}").unwrap(),
+        Regex::new(r"\(\d{2}\)[ \-.]?\d{4}[ \-.]?\d{4}").unwrap(),
+        Regex::new(r"1[38]00[ \-.]?\d{3}[ \-.]?\d{3}").unwrap(),
         Regex::new(r"13[ \-]?\d{2}[ \-]?\d{2}").unwrap(),
     ]
 });
 
+// Normalised AU numbers: (prefix, total digit count)
+static AU_PHONE_RULES: &[(&str, usize)] = &[
+    ("02", 10), ("03", 10), ("04", 10), ("07", 10), ("08", 10),
+    ("1300", 10), ("1800", 10), ("13", 6),
+];
+
+// Words that introduce a phone number in the text just before it
+static PHONE_CTA_WORDS: &[&str] = &["call", "phone", "ph:", "ph.", "tel:", "mob:", "mobile"];
+
+// Class/id fragments of elements that wrap a phone number
+static PHONE_CLASS_MARKERS: &[&str] = &["phone", "tel", "call", "mobile"];
+
 static PHONE_NORMALIZE_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"[^\d+]").unwrap()
 });
@@ -75,6 +91,26 @@ static EXCLUDE_EMAIL_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
     ]
 });
 
+// File extensions that make an "email" domain an asset name (logo@2x.png)
+static EMAIL_ASSET_EXTENSIONS: &[&str] = &[
+    ".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp", ".avif", ".ico", ".bmp", ".css", ".js",
+    ".mjs", ".map", ".json", ".woff", ".woff2", ".ttf", ".eot", ".otf", ".mp4", ".webm", ".mov",
+    ".pdf", ".zip", ".min",
+];
+
+// Package registries and CDNs: `name@version` specifiers, not mailboxes
+static PACKAGE_EMAIL_DOMAINS: &[&str] = &[
+    "npmjs.com", "npmjs.org", "unpkg.com", "jsdelivr.net", "cdnjs.com", "skypack.dev",
+    "esm.sh", "yarnpkg.com", "jspm.io", "deno.land", "pypi.org", "rubygems.org",
+];
+
+static SCRIPT_STYLE_BLOCK_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        Regex::new(r"(?is)<script\b[^>]*>.*?</script\s*>").unwrap(),
+        Regex::new(r"(?is)<style\b[^>]*>.*?</style\s*>").unwrap(),
+    ]
+});
+
 // Spam email domains
 static SPAM_EMAIL_DOMAINS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
     HashSet::from([
@@ -103,51 +139,102 @@ static SPAM_EMAIL_DOMAINS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
 // CMS / Tracking / Booking / Framework signatures
 // ---------------------------------------------------------------------------
 
-static CMS_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+/// A detection name and its markers, each tagged with how strongly it implies
+/// the detection.
+type MarkerTable = Vec<(&'static str, Vec<(&'static str, Confidence)>)>;
+
+static CMS_SIGNATURES: LazyLock<MarkerTable> = LazyLock::new(|| {
+    use Confidence::*;
     vec![
-        ("WordPress", vec!["/wp-content/", "/wp-includes/", "wp-json", "wordpress"]),
-        ("Wix", vec!["wix.com", "wixsite.com", "_wix_browser_sess", "wix-code"]),
-        ("Squarespace", vec!["squarespace.com", "static.squarespace", "sqsp.net"]),
-        ("Shopify", vec!["cdn.shopify.com", "myshopify.com", "shopify"]),
-        ("Webflow", vec!["webflow.com", "assets-global.website-files", "webflow.io"]),
-        ("Weebly", vec!["weebly.com", "weeblycloud.com"]),
-        ("GoDaddy Website Builder", vec!["godaddy.com", "secureserver.net", "godaddysites"]),
-        ("Joomla", vec!["joomla", "/components/com_"]),
-        ("Drupal", vec!["drupal", "/sites/default/"]),
+        ("WordPress", vec![
+            ("/wp-content/", Definite), ("/wp-includes/", Definite), ("wp-json", Definite),
+            ("wordpress", Possible),
+        ]),
+        ("Wix", vec![
+            ("wix.com", Probable), ("wixsite.com", Definite), ("_wix_browser_sess", Definite),
+            ("wix-code", Probable),
+        ]),
+        ("Squarespace", vec![
+            ("squarespace.com", Probable), ("static.squarespace", Definite), ("sqsp.net", Definite),
+        ]),
+        ("Shopify", vec![
+            ("cdn.shopify.com", Definite), ("myshopify.com", Definite), ("shopify", Possible),
+        ]),
+        ("Webflow", vec![
+            ("webflow.com", Probable), ("assets-global.website-files", Definite),
+            ("webflow.io", Definite),
+        ]),
+        ("Weebly", vec![("weebly.com", Probable), ("weeblycloud.com", Definite)]),
+        ("GoDaddy Website Builder", vec![
+            ("godaddy.com", Possible), ("secureserver.net", Probable), ("godaddysites", Definite),
+        ]),
+        ("Joomla", vec![("joomla", Possible), ("/components/com_", Definite)]),
+        ("Drupal", vec![("drupal", Possible), ("/sites/default/", Probable)]),
     ]
 });
 
-static TRACKING_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+static TRACKING_SIGNATURES: LazyLock<MarkerTable> = LazyLock::new(|| {
+    use Confidence::*;
     vec![
         ("google_analytics", vec![
-            "google-analytics.com", "gtag(", "ga(", "g-", "ua-", "googletagmanager.com",
+            ("google-analytics.com", Definite), ("gtag(", Definite), ("ga(", Possible),
+            ("g-", Possible), ("ua-", Possible), ("googletagmanager.com", Definite),
         ]),
         ("facebook_pixel", vec![
-            "facebook.com/tr", "fbq(", "connect.facebook.net",
+            ("facebook.com/tr", Definite), ("fbq(", Definite),
+            // Also loaded by like buttons and comment plugins
+            ("connect.facebook.net", Probable),
         ]),
         ("google_ads", vec![
-            "googleadservices.com", "googlesyndication.com", "aw-", "google_conversion",
+            ("googleadservices.com", Definite), ("googlesyndication.com", Probable),
+            ("aw-", Possible), ("google_conversion", Definite),
+        ]),
+        ("has_hotjar", vec![
+            ("static.hotjar.com", Definite), ("_hjsettings", Definite),
+            ("hotjar.com/c/hotjar-", Definite),
+        ]),
+        ("has_clarity", vec![
+            ("clarity.ms/tag", Definite), ("www.clarity.ms", Definite),
+            ("\"clarity\", \"script\"", Probable),
+        ]),
+        ("has_linkedin_insight", vec![
+            ("snap.licdn.com/li.lms-analytics", Definite), ("_linkedin_partner_id", Definite),
+            ("px.ads.linkedin.com", Definite),
+        ]),
+        ("has_tiktok_pixel", vec![
+            ("analytics.tiktok.com", Definite), ("ttq.load(", Definite), ("ttq.page(", Definite),
+        ]),
+        ("has_pinterest_tag", vec![
+            ("s.pinimg.com/ct/core.js", Definite), ("pintrk(", Definite),
+            ("ct.pinterest.com", Definite),
+        ]),
+        ("has_snap_pixel", vec![
+            ("sc-static.net/scevent.min.js", Definite), ("snaptr(", Definite),
+            ("tr.snapchat.com", Definite),
         ]),
     ]
 });
 
-static BOOKING_SIGNATURES: LazyLock<Vec<&str>> = LazyLock::new(|| {
+static BOOKING_SIGNATURES: LazyLock<Vec<(&str, Confidence)>> = LazyLock::new(|| {
+    use Confidence::*;
     vec![
-        "calendly.com", "acuityscheduling", "youcanbook.me", "setmore.com",
-        "square.site/book", "fresha.com", "book-online", "book-now",
-        "schedule-appointment", "hubspot.com/meetings", "bookings.google.com",
-        "appointlet.com", "simplybook.me", "timify.com",
+        ("calendly.com", Definite), ("acuityscheduling", Definite), ("youcanbook.me", Definite),
+        ("setmore.com", Definite), ("square.site/book", Definite), ("fresha.com", Definite),
+        ("book-online", Possible), ("book-now", Possible), ("schedule-appointment", Possible),
+        ("hubspot.com/meetings", Definite), ("bookings.google.com", Definite),
+        ("appointlet.com", Definite), ("simplybook.me", Definite), ("timify.com", Definite),
     ]
 });
 
-static FRAMEWORK_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+static FRAMEWORK_SIGNATURES: LazyLock<MarkerTable> = LazyLock::new(|| {
+    use Confidence::*;
     vec![
-        ("React", vec!["react", "reactdom", "__react"]),
-        ("Vue.js", vec!["vue.js", "vuejs", "__vue__"]),
-        ("Angular", vec!["ng-app", "ng-controller", "angular"]),
-        ("jQuery", vec!["jquery", "$(document)", "$.ajax"]),
-        ("Bootstrap", vec!["bootstrap.min", "bootstrap.css"]),
-        ("Tailwind", vec!["tailwindcss", "tailwind.css"]),
+        ("React", vec![("react", Possible), ("reactdom", Probable), ("__react", Probable)]),
+        ("Vue.js", vec![("vue.js", Probable), ("vuejs", Probable), ("__vue__", Probable)]),
+        ("Angular", vec![("ng-app", Definite), ("ng-controller", Definite), ("angular", Possible)]),
+        ("jQuery", vec![("jquery", Probable), ("$(document)", Probable), ("$.ajax", Probable)]),
+        ("Bootstrap", vec![("bootstrap.min", Definite), ("bootstrap.css", Definite)]),
+        ("Tailwind", vec![("tailwindcss", Probable), ("tailwind.css", Definite)]),
     ]
 });
 
@@ -156,263 +243,5350 @@ static RESPONSIVE_INDICATORS: LazyLock<Vec<&str>> = LazyLock::new(|| {
 });
 
 // ---------------------------------------------------------------------------
-// Helpers
+// WordPress theme / plugin enumeration
 // ---------------------------------------------------------------------------
 
-fn is_spam_email(email: &str) -> bool {
-    // Check domain blocklist
-    if let Some(pos) = email.rfind('@') {
-        let domain = &email[pos + 1..];
-        if SPAM_EMAIL_DOMAINS.contains(domain) {
-            return true;
-        }
-    }
+static WP_MARKERS: &[&str] = &["/wp-content/", "/wp-includes/", "wp-json"];
 
-    // Check spam patterns
-    for re in SPAM_EMAIL_RES.iter() {
-        if re.is_match(email) {
-            return true;
-        }
-    }
+static WP_THEME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)wp-content/themes/([a-z0-9_\-.]+)/").unwrap()
+});
 
-    false
-}
+static WP_PLUGIN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)wp-content/plugins/([a-z0-9_\-.]+)/").unwrap()
+});
 
-fn format_au_number(digits: &str) -> String {
-    if digits.len() == 9 {
-        if digits.starts_with('4') {
-            // Mobile: 0XXX XXX XXX
-            format!("0{} {} {}", &digits[0..3], &digits[3..6], &digits[6..])
-        } else {
-            // Landline: 0X XXXX XXXX
-            format!("0{} {} {}", &digits[0..1], &digits[1..5], &digits[5..])
-        }
-    } else {
-        digits.to_string()
-    }
-}
+// Stock themes shipped with WordPress core (a strong "no developer" tell)
+static WP_DEFAULT_THEMES: &[&str] = &[
+    "twentyten", "twentyeleven", "twentytwelve", "twentythirteen",
+    "twentyfourteen", "twentyfifteen", "twentysixteen", "twentyseventeen",
+    "twentynineteen", "twentytwenty", "twentytwentyone", "twentytwentytwo",
+    "twentytwentythree", "twentytwentyfour", "twentytwentyfive",
+];
 
-fn normalize_phone(phone: &str) -> String {
-    if phone.is_empty() {
-        return String::new();
-    }
+// Plugin and theme slugs that ship a drag-and-drop page builder
+static WP_PAGE_BUILDER_SLUGS: &[&str] = &[
+    "elementor", "elementor-pro", "divi-builder", "divi", "extra",
+    "beaver-builder-lite-version", "bb-plugin", "bb-theme", "js_composer",
+    "siteorigin-panels", "oxygen", "brizy", "thrive-visual-editor",
+    "fusion-builder", "avada", "visualcomposer", "kingcomposer",
+];
 
-    let digits = PHONE_NORMALIZE_RE.replace_all(phone, "").to_string();
+// ---------------------------------------------------------------------------
+// Site builder / page builder signatures
+//   (name, CDN/asset markers → "definite", class-name markers → "probable")
+// ---------------------------------------------------------------------------
 
-    // Count actual digits (excluding +)
-    let digit_count = digits.chars().filter(|c| c.is_ascii_digit()).count();
-    if digit_count < 8 {
-        return String::new();
-    }
+type TieredSignatures = Vec<(&'static str, Vec<&'static str>, Vec<&'static str>)>;
 
-    if digits.starts_with("+61") {
-        let mut rest = &digits[3..];
-        if rest.starts_with('0') {
-            rest = &rest[1..];
-        }
-        format_au_number(rest)
-    } else if digits.starts_with('0') {
-        format_au_number(&digits[1..])
-    } else if digits.starts_with("1300") || digits.starts_with("1800") {
-        format!("{} {} {}", &digits[..4], &digits[4..7], &digits[7..])
-    } else if digits.starts_with("13") && digits.len() == 6 {
-        format!("{} {} {}", &digits[..2], &digits[2..4], &digits[4..])
-    } else {
-        phone.trim().to_string()
-    }
-}
+static SITE_BUILDER_SIGNATURES: LazyLock<TieredSignatures> = LazyLock::new(|| {
+    vec![
+        ("Wix",
+            vec!["wixstatic.com", "static.parastorage.com", "x-wix-", "content=\"wix.com"],
+            vec!["_wix_browser_sess", "wix-image", "data-mesh-id"]),
+        ("Squarespace",
+            vec!["static1.squarespace.com", "squarespace-cdn.com", "static.squarespace_context"],
+            vec!["sqs-block", "sqs-layout"]),
+        ("Weebly",
+            vec!["editmysite.com", "weeblycloud.com", "cdn2.editmysite.com"],
+            vec!["wsite-", "weebly-"]),
+        ("GoDaddy Website Builder",
+            vec!["img1.wsimg.com", "wsimg.com"],
+            vec!["godaddysites", "x-theme-", "widget-header-header"]),
+        ("Duda",
+            vec!["dudamobile.com", "multiscreensite.com", "irp.cdn-website.com"],
+            vec!["dmbody", "dmlayoutwrapper", "dmnewparagraph"]),
+    ]
+});
+
+static PAGE_BUILDER_SIGNATURES: LazyLock<TieredSignatures> = LazyLock::new(|| {
+    vec![
+        ("Elementor",
+            vec!["/wp-content/plugins/elementor/", "/wp-content/plugins/elementor-pro/"],
+            vec!["elementor-section", "elementor-element", "elementor-widget"]),
+        ("Divi",
+            vec!["/wp-content/themes/divi/", "/wp-content/plugins/divi-builder/"],
+            vec!["et_pb_section", "et_pb_row", "et-db"]),
+        ("Beaver Builder",
+            vec!["/wp-content/plugins/bb-plugin/", "/wp-content/plugins/beaver-builder-lite-version/"],
+            vec!["fl-builder", "fl-row-content"]),
+        ("WPBakery",
+            vec!["/wp-content/plugins/js_composer/"],
+            vec!["vc_row", "wpb_wrapper", "vc_column"]),
+    ]
+});
 
 // ---------------------------------------------------------------------------
-// PyO3 functions
+// Live chat / chatbot widget signatures (loader script hosts + global objects)
 // ---------------------------------------------------------------------------
 
-#[pyfunction]
-pub fn extract_emails(html: &str) -> Vec<String> {
-    if html.is_empty() {
-        return Vec::new();
-    }
+static CHAT_WIDGET_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+    vec![
+        ("Intercom", vec!["widget.intercom.io", "js.intercomcdn.com", "intercomsettings", "window.intercom("]),
+        ("Drift", vec!["js.driftt.com", "drift.load(", "window.drift"]),
+        ("Tawk.to", vec!["embed.tawk.to", "tawk_api"]),
+        ("Crisp", vec!["client.crisp.chat", "$crisp", "crisp_website_id"]),
+        ("Zendesk", vec!["static.zdassets.com", "ze-snippet", "zopim"]),
+        ("LiveChat", vec!["cdn.livechatinc.com", "__lc.license", "livechatwidget"]),
+        ("HubSpot", vec!["js.usemessages.com", "hubspotconversations", "hubspot-messages-iframe"]),
+        ("Facebook Customer Chat", vec!["fb-customerchat", "xfbml.customerchat"]),
+        ("Tidio", vec!["code.tidio.co", "tidiochatapi"]),
+    ]
+});
 
-    let mut valid_emails = Vec::new();
-    let mut seen: HashSet<String> = HashSet::new();
+// ---------------------------------------------------------------------------
+// Booking vendor signatures
+//   (vendor, embedded widget markers → "widget", hosted page links → "link")
+// ---------------------------------------------------------------------------
 
-    for m in EMAIL_RE.find_iter(html) {
-        let email_lower = m.as_str().to_lowercase();
+static BOOKING_VENDOR_SIGNATURES: LazyLock<TieredSignatures> = LazyLock::new(|| {
+    vec![
+        ("Calendly",
+            vec!["assets.calendly.com", "calendly-inline-widget", "calendly.initpopupwidget", "calendly.initbadgewidget"],
+            vec!["calendly.com/"]),
+        ("Acuity Scheduling",
+            vec!["embed.acuityscheduling.com", "acuity-embed-button", "squarespacescheduling.com/embed"],
+            vec!["acuityscheduling.com/schedule", "app.squarespacescheduling.com", ".as.me/"]),
+        ("Fresha",
+            vec!["widget.fresha.com", "fresha-widget"],
+            vec!["fresha.com/book-now", "fresha.com/a/", "fresha.com/providers"]),
+        ("Timely",
+            vec!["book.gettimely.com/booking-widget", "timelybutton", "gettimely.com/js"],
+            vec!["gettimely.com/book", "book.gettimely.com"]),
+        ("Mindbody",
+            vec!["widgets.mindbodyonline.com", "brandedweb.mindbodyonline.com", "healcode-widget"],
+            vec!["clients.mindbodyonline.com", "mindbodyonline.com/explore"]),
+        ("Setmore",
+            vec!["setmore_iframe", "setmore.com/webapp/bookingpage"],
+            vec!["setmore.com"]),
+        ("SimplyBook.me",
+            vec!["widget.simplybook.me", "simplybook.me/v2/widget"],
+            vec!["simplybook.me"]),
+        ("Square Appointments",
+            vec!["app.squareup.com/appointments/buyer/widget", "square-appointments-widget"],
+            vec!["squareup.com/appointments/book", "square.site/book"]),
+        ("ServiceM8",
+            vec!["servicem8.com/book/widget", "servicem8-booking-widget"],
+            vec!["book.servicem8.com"]),
+        ("Housecall Pro",
+            vec!["online-booking.housecallpro.com/script.js", "hcp-button"],
+            vec!["book.housecallpro.com"]),
+        ("HubSpot Meetings",
+            vec!["meetings-iframe-container", "static.hsappstatic.net/meetingsembed"],
+            vec!["hubspot.com/meetings", "meetings.hubspot.com"]),
+        ("Google Calendar",
+            vec!["calendar.google.com/calendar/appointments/schedules"],
+            vec!["bookings.google.com", "calendar.app.google/"]),
+    ]
+});
 
-        if email_lower.len() > 100 {
-            continue;
-        }
+// ---------------------------------------------------------------------------
+// Analytics / pixel ID patterns
+// ---------------------------------------------------------------------------
 
-        if seen.contains(&email_lower) {
-            continue;
-        }
+static GA4_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:id=|['"])(G-[A-Z0-9]{6,12})\b"#).unwrap()
+});
 
-        if is_spam_email(&email_lower) {
-            continue;
-        }
+static UA_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(UA-\d{4,10}-\d{1,4})\b").unwrap()
+});
 
-        if EXCLUDE_EMAIL_RES.iter().any(|re| re.is_match(&email_lower)) {
-            continue;
-        }
+static GTM_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(GTM-[A-Z0-9]{4,9})\b").unwrap()
+});
 
-        // Skip hash-like local parts
-        if let Some(pos) = email_lower.find('@') {
-            let local_part = &email_lower[..pos];
-            if local_part.len() > 15 {
-                let hex_count = local_part
-                    .chars()
-                    .filter(|c| matches!(c, '0'..='9' | 'a'..='f'))
-                    .count();
-                if (hex_count as f64 / local_part.len() as f64) > 0.7 {
-                    continue;
-                }
-            }
-        }
+static FB_PIXEL_ID_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        Regex::new(r#"fbq\(\s*['"]init['"]\s*,\s*['"]?(\d{10,20})"#).unwrap(),
+        Regex::new(r"facebook\.com/tr/?\?id=(\d{10,20})").unwrap(),
+    ]
+});
 
-        seen.insert(email_lower.clone());
-        valid_emails.push(email_lower);
+static GOOGLE_ADS_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(AW-\d{6,12})\b").unwrap()
+});
 
-        if valid_emails.len() >= 5 {
-            break;
-        }
-    }
+// ---------------------------------------------------------------------------
+// Consent management platforms
+// ---------------------------------------------------------------------------
 
-    valid_emails
-}
+static CONSENT_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+    vec![
+        ("CookieYes", vec!["cdn-cookieyes.com", "cky-consent-container", "cookie-law-info-bar"]),
+        ("OneTrust", vec!["cdn.cookielaw.org", "onetrust-banner-sdk", "optanon-alert-box"]),
+        ("Cookiebot", vec!["consent.cookiebot.com", "cybotcookiebotdialog"]),
+        ("Complianz", vec!["/wp-content/plugins/complianz-gdpr", "cmplz-cookiebanner"]),
+        ("Termly", vec!["app.termly.io", "termly-code-snippet-support"]),
+        ("Osano", vec!["cmp.osano.com", "osano-cm-window"]),
+        ("iubenda", vec!["cdn.iubenda.com/cs", "iubenda-cs-banner"]),
+        ("Usercentrics", vec!["app.usercentrics.eu", "usercentrics-root"]),
+    ]
+});
 
-#[pyfunction]
-pub fn extract_phones(html: &str) -> Vec<String> {
-    if html.is_empty() {
-        return Vec::new();
-    }
+static COOKIE_CONTAINER_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    // body/html excluded: themes often hang "cookies-accepted" style classes there
+    Selector::parse(
+        ":not(html):not(body):is([id*='cookie' i], [class*='cookie' i], [id*='consent' i], [class*='consent' i])",
+    )
+    .unwrap()
+});
 
-    let mut phones = Vec::new();
-    let mut seen: HashSet<String> = HashSet::new();
+static INTERACTIVE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("button, a, input[type='button'], input[type='submit']").unwrap()
+});
 
-    for pattern in PHONE_PATTERNS.iter() {
-        for m in pattern.find_iter(html) {
-            let normalized = normalize_phone(m.as_str());
-            if !normalized.is_empty() && !seen.contains(&normalized) {
-                seen.insert(normalized.clone());
-                phones.push(normalized);
-            }
-        }
-    }
+static ACCEPT_LABEL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(accept|agree|allow|got it|ok|okay|i understand)\b").unwrap()
+});
 
-    phones
-}
+// ---------------------------------------------------------------------------
+// CAPTCHA / bot protection
+//   (provider, script markers, widget container class, widget-in-form selector)
+// ---------------------------------------------------------------------------
 
-#[pyfunction]
-pub fn detect_cms(html: &str) -> Option<String> {
-    if html.is_empty() {
-        return None;
-    }
+static RECAPTCHA_WIDGET_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("form .g-recaptcha").unwrap());
+static HCAPTCHA_WIDGET_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("form .h-captcha").unwrap());
+static TURNSTILE_WIDGET_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("form .cf-turnstile").unwrap());
+static FRIENDLY_CAPTCHA_WIDGET_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("form .frc-captcha").unwrap());
 
-    let html_lower = html.to_lowercase();
+type CaptchaSignature =
+    (&'static str, Vec<&'static str>, &'static str, &'static LazyLock<Selector>);
 
-    for (cms_name, signatures) in CMS_SIGNATURES.iter() {
-        for sig in signatures {
-            if html_lower.contains(&sig.to_lowercase()) {
-                return Some(cms_name.to_string());
-            }
-        }
-    }
+static CAPTCHA_SIGNATURES: LazyLock<Vec<CaptchaSignature>> = LazyLock::new(|| {
+    vec![
+        ("reCAPTCHA", vec!["google.com/recaptcha/", "gstatic.com/recaptcha/", "recaptcha.net/recaptcha/", "grecaptcha."], "g-recaptcha", &RECAPTCHA_WIDGET_SEL),
+        ("hCaptcha", vec!["js.hcaptcha.com", "hcaptcha.com/1/api.js"], "h-captcha", &HCAPTCHA_WIDGET_SEL),
+        ("Turnstile", vec!["challenges.cloudflare.com/turnstile"], "cf-turnstile", &TURNSTILE_WIDGET_SEL),
+        ("Friendly Captcha", vec!["friendlycaptcha", "friendly-challenge"], "frc-captcha", &FRIENDLY_CAPTCHA_WIDGET_SEL),
+    ]
+});
 
-    None
-}
+static RECAPTCHA_V3_RENDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"recaptcha/(?:api|enterprise)\.js\?[^\x22']*render=(?:[a-z0-9_\-]{20,})").unwrap()
+});
 
-#[pyfunction]
-pub fn detect_tracking(html: &str) -> HashMap<String, bool> {
-    let mut result = HashMap::new();
-    result.insert("google_analytics".to_string(), false);
-    result.insert("facebook_pixel".to_string(), false);
-    result.insert("google_ads".to_string(), false);
+static FORM_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("form").unwrap());
 
-    if html.is_empty() {
-        return result;
-    }
+// ---------------------------------------------------------------------------
+// Form providers
+//   (provider, markers, hosted elsewhere and embedded via iframe/script)
+// ---------------------------------------------------------------------------
 
-    let html_lower = html.to_lowercase();
+static FORM_PROVIDER_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>, bool)>> = LazyLock::new(|| {
+    vec![
+        ("Typeform", vec!["embed.typeform.com", "typeform.com/to/", "data-tf-widget"], true),
+        ("Jotform", vec!["jotform.com/", "jotfor.ms/", "form.jotform"], true),
+        ("Google Forms", vec!["docs.google.com/forms", "forms.gle/"], true),
+        ("HubSpot", vec!["js.hsforms.net", "hbspt.forms.create", "share.hsforms.com"], true),
+        ("Wufoo", vec!["wufoo.com/forms", "wufoo.com/scripts"], true),
+        ("Gravity Forms", vec!["gform_wrapper", "/wp-content/plugins/gravityforms/"], false),
+        ("Contact Form 7", vec!["wpcf7", "/wp-content/plugins/contact-form-7/"], false),
+        ("WPForms", vec!["wpforms-form", "/wp-content/plugins/wpforms"], false),
+        ("Ninja Forms", vec!["nf-form-cont", "/wp-content/plugins/ninja-forms/"], false),
+    ]
+});
 
-    for (tracker, signatures) in TRACKING_SIGNATURES.iter() {
-        for sig in signatures {
-            if html_lower.contains(sig) {
-                result.insert(tracker.to_string(), true);
-                break;
-            }
-        }
-    }
+static TEXTAREA_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("textarea").unwrap());
 
-    result
-}
+static FIELD_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("input:not([type='hidden']):not([type='submit']):not([type='button']):not([type='checkbox']):not([type='radio']), select, textarea").unwrap()
+});
 
-#[pyfunction]
-pub fn detect_booking_system(html: &str) -> bool {
-    if html.is_empty() {
-        return false;
-    }
+static EMAIL_INPUT_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("input[type='email'], input[name*='email' i]").unwrap()
+});
 
-    let html_lower = html.to_lowercase();
+static SEARCH_INPUT_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("input[type='search'], input[name='s'], input[name='q'], input[name='search']").unwrap()
+});
 
-    BOOKING_SIGNATURES.iter().any(|sig| html_lower.contains(&sig.to_lowercase()))
-}
+static NEWSLETTER_MARKERS: &[&str] = &["newsletter", "subscribe", "mailchimp", "list-manage.com", "klaviyo", "mc-embedded"];
 
-#[pyfunction]
-pub fn detect_frameworks(html: &str) -> Vec<String> {
-    if html.is_empty() {
-        return Vec::new();
-    }
+// ---------------------------------------------------------------------------
+// Mixed content
+// ---------------------------------------------------------------------------
 
-    let html_lower = html.to_lowercase();
-    let mut frameworks = Vec::new();
+static MIXED_BLOCKABLE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("script[src], iframe[src], link[rel~='stylesheet'][href], link[rel~='preload'][href], link[rel~='modulepreload'][href], object[data], embed[src]").unwrap()
+});
 
-    for (name, signatures) in FRAMEWORK_SIGNATURES.iter() {
-        for sig in signatures {
-            if html_lower.contains(sig) {
-                frameworks.push(name.to_string());
-                break;
-            }
-        }
-    }
+static MIXED_UPGRADEABLE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("img[src], source[src], audio[src], video[src], link[rel~='icon'][href], link[rel~='apple-touch-icon'][href]").unwrap()
+});
 
-    frameworks
-}
+static STYLE_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("style").unwrap());
 
-#[pyfunction]
-pub fn detect_responsive(html: &str) -> bool {
-    if html.is_empty() {
-        return false;
-    }
+static STYLE_ATTR_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("[style]").unwrap());
 
-    let html_lower = html.to_lowercase();
+static CSS_HTTP_URL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)url\(\s*['"]?(http://[^'")\s]+)"#).unwrap()
+});
 
-    RESPONSIVE_INDICATORS.iter().any(|ind| html_lower.contains(ind))
-}
+const MIXED_CONTENT_EXAMPLES: usize = 10;
 
-#[pyfunction]
-pub fn analyze_tech_stack(py: Python<'_>, html: &str) -> PyResult<PyObject> {
-    let dict = PyDict::new(py);
+// ---------------------------------------------------------------------------
+// Copyright footer
+// ---------------------------------------------------------------------------
 
-    let cms = detect_cms(html);
-    let tracking = detect_tracking(html);
-    let has_booking = detect_booking_system(html);
-    let frameworks = detect_frameworks(html);
-    let has_responsive = detect_responsive(html);
+static FOOTER_REGION_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("footer, [class*='footer' i], [id*='footer' i], [class*='copyright' i], [id*='copyright' i]").unwrap()
+});
 
-    match cms {
-        Some(ref v) => dict.set_item("cms", v)?,
-        None => dict.set_item("cms", py.None())?,
-    }
+static COPYRIGHT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:©|\(c\)|copyright)\s*(?:©\s*)?(?:\(c\)\s*)?((?:19|20)\d{2})(?:\s*[-–—]\s*((?:19|20)\d{2}))?").unwrap()
+});
 
-    let tracking_dict = PyDict::new(py);
-    for (k, v) in &tracking {
-        tracking_dict.set_item(k, *v)?;
-    }
-    dict.set_item("tracking", tracking_dict)?;
+// ---------------------------------------------------------------------------
+// Page language
+// ---------------------------------------------------------------------------
 
-    dict.set_item("has_booking", has_booking)?;
-    dict.set_item("frameworks", &frameworks)?;
-    dict.set_item("has_ssl", false)?;
-    dict.set_item("has_responsive", has_responsive)?;
+static OG_LOCALE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("meta[property='og:locale'], meta[name='og:locale']").unwrap()
+});
 
-    Ok(dict.into())
+static CONTENT_LANGUAGE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("meta[http-equiv='content-language' i]").unwrap()
+});
+
+static HREFLANG_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='alternate'][hreflang]").unwrap()
+});
+
+// Elements whose text never renders
+static NON_VISIBLE_TAGS: &[&str] = &["script", "style", "noscript", "template", "svg", "head"];
+
+// ---------------------------------------------------------------------------
+// On-page SEO
+// ---------------------------------------------------------------------------
+
+static HEAD_TITLE_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("head title").unwrap());
+
+static META_DESCRIPTION_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("meta[name='description' i]").unwrap()
+});
+
+static META_ROBOTS_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("meta[name='robots' i], meta[name='googlebot' i]").unwrap()
+});
+
+static CANONICAL_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='canonical'][href]").unwrap()
+});
+
+static H1_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("h1").unwrap());
+
+static IMG_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("img").unwrap());
+
+static MAIN_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("main, [role='main']").unwrap()
+});
+
+static BODY_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("body").unwrap());
+
+// Page chrome excluded from "main content" word counts
+static CHROME_TAGS: &[&str] = &["nav", "header", "footer", "aside"];
+
+// Titles / descriptions left at a CMS or builder default
+static DEFAULT_TITLES: &[&str] = &[
+    "home", "homepage", "index", "untitled", "untitled document", "new page", "my site",
+    "my website", "welcome", "document", "just another wordpress site", "my wordpress blog",
+];
+
+static DEFAULT_DESCRIPTIONS: &[&str] = &[
+    "just another wordpress site", "my wordpress blog", "this is my site", "my site",
+    "site description", "powered by wix",
+];
+
+const TITLE_MIN_CHARS: usize = 10;
+const TITLE_MAX_CHARS: usize = 60;
+const DESCRIPTION_MIN_CHARS: usize = 50;
+const DESCRIPTION_MAX_CHARS: usize = 160;
+pub(crate) const THIN_CONTENT_WORDS: usize = 200;
+
+// ---------------------------------------------------------------------------
+// Heading outline
+// ---------------------------------------------------------------------------
+
+static HEADING_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("h1, h2, h3, h4, h5, h6").unwrap()
+});
+
+// Headings in these regions are navigation/boilerplate, not page outline
+static OUTLINE_EXCLUDED_TAGS: &[&str] = &["nav", "footer", "aside"];
+
+// ---------------------------------------------------------------------------
+// Content metrics
+// ---------------------------------------------------------------------------
+
+static CONTENT_EXCLUDED_TAGS: &[&str] = &["nav", "footer"];
+
+// class/id fragments of cookie banners and skip-links
+static BOILERPLATE_MARKERS: &[&str] = &[
+    "cookie", "consent", "gdpr", "skip-link", "skiplink", "skip-to", "skip-nav",
+];
+
+static PARAGRAPH_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("p").unwrap());
+
+// ---------------------------------------------------------------------------
+// Image audit
+// ---------------------------------------------------------------------------
+
+static BACKGROUND_IMAGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)background(?:-image)?\s*:[^;]*url\(\s*['"]?([^'")\s]+)"#).unwrap()
+});
+
+// ---------------------------------------------------------------------------
+// Performance hints
+// ---------------------------------------------------------------------------
+
+static SCRIPT_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("script").unwrap());
+
+static HEAD_SCRIPT_SRC_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("head script[src]").unwrap()
+});
+
+static HEAD_STYLESHEET_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("head link[rel~='stylesheet' i][href]").unwrap()
+});
+
+static RESOURCE_HINT_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='preload' i], link[rel~='preconnect' i]").unwrap()
+});
+
+static SITE_URL_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel='canonical' i][href], meta[property='og:url'][content]").unwrap()
+});
+
+// Script types the browser executes; JSON-LD and templates are inert data
+pub(crate) static EXECUTABLE_SCRIPT_TYPES: &[&str] = &[
+    "", "text/javascript", "application/javascript", "module", "text/ecmascript",
+];
+
+// ---------------------------------------------------------------------------
+// Modern build practices
+// ---------------------------------------------------------------------------
+
+static LAZY_LOADED_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("img[loading='lazy' i], iframe[loading='lazy' i]").unwrap()
+});
+
+static PRECONNECT_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='preconnect' i]").unwrap()
+});
+
+static DNS_PREFETCH_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='dns-prefetch' i]").unwrap()
+});
+
+static PRELOAD_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='preload' i], link[rel~='modulepreload' i]").unwrap()
+});
+
+static SRCSET_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("img[srcset], source[srcset]").unwrap()
+});
+
+static PICTURE_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("picture").unwrap());
+
+static IMAGE_SOURCE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("img, picture source").unwrap()
+});
+
+static MODERN_IMAGE_EXTENSIONS: &[&str] = &["webp", "avif"];
+
+// ---------------------------------------------------------------------------
+// Link classification
+// ---------------------------------------------------------------------------
+
+static LINK_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("a[href]").unwrap());
+
+const LINK_SAMPLE_LIMIT: usize = 20;
+
+// ---------------------------------------------------------------------------
+// Feed / sitemap discovery
+// ---------------------------------------------------------------------------
+
+pub(crate) static FEED_LINK_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        "link[rel~='alternate' i][type='application/rss+xml' i][href], \
+         link[rel~='alternate' i][type='application/atom+xml' i][href]",
+    )
+    .unwrap()
+});
+
+static SITEMAP_LINK_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='sitemap' i][href]").unwrap()
+});
+
+static FEED_PATH_SUFFIXES: &[&str] = &[
+    "/feed", "/rss", "/atom", ".rss", "/feed.xml", "/rss.xml", "/atom.xml",
+];
+
+static SITEMAP_PATH_SUFFIXES: &[&str] = &["sitemap.xml", "sitemap_index.xml"];
+
+// ---------------------------------------------------------------------------
+// Accessibility audit
+// ---------------------------------------------------------------------------
+
+static LABELLABLE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        "input:not([type='hidden' i]):not([type='submit' i]):not([type='button' i])\
+         :not([type='image' i]):not([type='reset' i]), select, textarea",
+    )
+    .unwrap()
+});
+
+static LABEL_FOR_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("label[for]").unwrap());
+
+static HTML_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("html").unwrap());
+
+static LEGACY_PRESENTATION_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("font, center, marquee").unwrap()
+});
+
+// ---------------------------------------------------------------------------
+// Framework versions
+// ---------------------------------------------------------------------------
+
+/// Version of an npm/CDN package from its asset URL: unpkg/jsdelivr
+/// `pkg@1.2.3`, cdnjs/googleapis `/pkg/1.2.3/`, or a `pkg-1.2.3.min.js` file.
+fn package_version_regex(package: &str) -> Regex {
+    let p = regex::escape(package);
+    let ver = r"(\d+\.\d+(?:\.\d+)?)";
+    Regex::new(&format!(
+        r"(?:unpkg\.com/|/npm/){p}@v?{ver}|/{p}/v?{ver}/|/{p}[.-]v?{ver}(?:\.slim)?(?:\.min)?\.js"
+    ))
+    .unwrap()
+}
+
+fn capture_package_version(re: &Regex, html_lower: &str) -> Option<String> {
+    re.captures(html_lower)
+        .and_then(|caps| caps.iter().skip(1).flatten().next().map(|m| m.as_str().to_string()))
+}
+
+static REACT_VERSION_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![package_version_regex("react"), package_version_regex("react-dom")]
+});
+
+static VUE_VERSION_RE: LazyLock<Regex> = LazyLock::new(|| package_version_regex("vue"));
+
+static ANGULARJS_VERSION_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        package_version_regex("angular.js"),
+        package_version_regex("angularjs"),
+        package_version_regex("angular"),
+    ]
+});
+
+static NG_VERSION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"ng-version=["']?(\d+(?:\.\d+)*)"#).unwrap()
+});
+
+static ANGULARJS_MARKERS: &[&str] = &["ng-app", "ng-controller", "angular.module("];
+
+static VUE_MARKERS: &[&str] = &["__vue_devtools_global_hook__", "__vue__", "data-v-app", "vue.js"];
+
+// ---------------------------------------------------------------------------
+// jQuery
+// ---------------------------------------------------------------------------
+
+static JQUERY_VERSION_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        package_version_regex("jquery"),
+        // WordPress core enqueues jquery.min.js?ver=3.7.1
+        Regex::new(r"/jquery(?:\.min)?\.js\?ver=(\d+\.\d+(?:\.\d+)?)").unwrap(),
+    ]
+});
+
+static SCRIPT_SRC_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("script[src]").unwrap());
+
+// Slider/lightbox plugins from the jQuery 1.x era, matched in script srcs
+static LEGACY_JQUERY_PLUGINS: &[(&str, &[&str])] = &[
+    ("flexslider", &["flexslider"]),
+    ("nivo-slider", &["nivo.slider", "nivo-slider"]),
+    (
+        "fancybox",
+        &["jquery.fancybox-1.", "jquery.fancybox-2.", "fancybox.pack.js", "fancybox/1.", "fancybox/2."],
+    ),
+    ("bxslider", &["bxslider"]),
+    ("prettyPhoto", &["prettyphoto"]),
+];
+
+// ---------------------------------------------------------------------------
+// Hosting / CDN hints
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq)]
+enum HostingKind {
+    Cdn,
+    Host,
+}
+
+// (provider, asset host suffix, kind) — one row per provider, each with a
+// matching row in test_v2_native.py's hosting_rows
+static HOSTING_SIGNATURES: &[(&str, &str, HostingKind)] = &[
+    ("WP Engine", "wpengine.com", HostingKind::Host),
+    ("WP Engine", "wpenginepowered.com", HostingKind::Host),
+    ("Kinsta", "kinsta.cloud", HostingKind::Host),
+    ("Shopify", "cdn.shopify.com", HostingKind::Host),
+    ("Netlify", "netlify.app", HostingKind::Host),
+    ("Vercel", "vercel.app", HostingKind::Host),
+    ("Squarespace", "squarespace-cdn.com", HostingKind::Host),
+    ("Wix", "wixstatic.com", HostingKind::Host),
+    ("Amazon CloudFront", "cloudfront.net", HostingKind::Cdn),
+    ("BunnyCDN", "b-cdn.net", HostingKind::Cdn),
+];
+
+static ASSET_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        "script[src], link[href]:not([rel~='canonical' i]):not([rel~='alternate' i]):not([rel~='shortlink' i]), \
+         img[src], source[src], iframe[src], video[src]",
+    )
+    .unwrap()
+});
+
+// ---------------------------------------------------------------------------
+// Responsive layout
+// ---------------------------------------------------------------------------
+
+static VIEWPORT_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("meta[name='viewport' i]").unwrap()
+});
+
+static MEDIA_LINK_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("link[media]").unwrap());
+
+static LAYOUT_WRAPPER_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        "body[style], main[style], #wrapper[style], #container[style], #page[style], \
+         .wrapper[style], .container[style]",
+    )
+    .unwrap()
+});
+
+static LAYOUT_TABLE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("body > table[width], body > center > table[width]").unwrap()
+});
+
+static MEDIA_QUERY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)@media[^{]*(?:max|min)-(?:device-)?width").unwrap()
+});
+
+// `width: 960px` but not `max-width`/`min-width`
+static FIXED_WIDTH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:^|[;\s{])width\s*:\s*(\d{3,4})px").unwrap()
+});
+
+static FIXED_WRAPPER_RULE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:^|[\s,}])(?:body|main|#wrapper|#container|#page|\.wrapper|\.container)\s*\{([^}]*)\}",
+    )
+    .unwrap()
+});
+
+static MOBILE_SUBDOMAIN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:https?:)?//m\.").unwrap()
+});
+
+// Narrowest desktop layout width worth calling "fixed" (phones are ~400px)
+const FIXED_LAYOUT_MIN_PX: u32 = 760;
+
+// ---------------------------------------------------------------------------
+// AMP
+// ---------------------------------------------------------------------------
+
+static AMPHTML_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("link[rel~='amphtml' i][href]").unwrap()
+});
+
+static AMP_RUNTIME_MARKER: &str = "cdn.ampproject.org/v0";
+
+// ---------------------------------------------------------------------------
+// Opening hours
+// ---------------------------------------------------------------------------
+
+static JSON_LD_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("script[type='application/ld+json' i]").unwrap()
+});
+
+static WEEKDAYS: [&str; 7] = [
+    "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+];
+
+const DAY_PATTERN: &str = r"\b(?:mon(?:day)?|tue(?:s(?:day)?)?|wed(?:nesday)?|thu(?:r(?:s(?:day)?)?)?|fri(?:day)?|sat(?:urday)?|sun(?:day)?)\b\.?";
+
+const TIME_PATTERN: &str = r"(\d{1,2})(?:[:.](\d{2}))?\s*(a\.?m\.?|p\.?m\.?)?";
+
+static DAY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&format!("(?i){DAY_PATTERN}")).unwrap());
+
+static DAY_RANGE_SEP_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(?:-|–|—|to|thru|through)\s*$").unwrap()
+});
+
+static TIME_RANGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"(?i){TIME_PATTERN}\s*(?:-|–|—|to|until|till)\s*{TIME_PATTERN}")).unwrap()
+});
+
+// "Mon–Fri 7am–5pm", "Monday to Friday: 7:00 - 17:00", "Sat & Sun closed"
+static HOURS_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let time = r"\d{1,2}(?:[:.]\d{2})?\s*(?:a\.?m\.?|p\.?m\.?)?";
+    let time_range = format!(r"{time}\s*(?:-|–|—|to|until|till)\s*{time}");
+    let days = format!(
+        r"{DAY_PATTERN}(?:\s*(?:-|–|—|to|thru|through|,|&|and|/)\s*{DAY_PATTERN})*|7\s+days(?:\s+a\s+week)?|every\s*day|daily"
+    );
+    Regex::new(&format!(
+        r"(?i)(?P<days>{days})\s*[:\-–—]?\s*(?P<value>closed|by\s+appointment(?:\s+only)?|{time_range}(?:\s*(?:,|&|and)\s*{time_range})*)"
+    ))
+    .unwrap()
+});
+
+static OPEN_24_7_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b24\s*/\s*7\b|\b24\s*hours?,?\s*(?:a\s+day,?\s*)?7\s*days\b").unwrap()
+});
+
+// ---------------------------------------------------------------------------
+// Postal addresses
+// ---------------------------------------------------------------------------
+
+static ADDRESS_REGION_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        "footer, address, [itemtype*='PostalAddress' i], [id*='contact' i], [class*='contact' i]",
+    )
+    .unwrap()
+});
+
+const STREET_PATTERN: &str = r"(?:(?i:unit|shop|suite|level|lot)\s+\d+[A-Za-z]?,?\s+)?\d+[A-Za-z]?(?:[-/]\d+[A-Za-z]?)?\s+(?:[A-Z][A-Za-z']+\s+){1,3}(?i:street|st|road|rd|avenue|ave|drive|dr|court|ct|place|pl|parade|pde|highway|hwy|lane|ln|terrace|tce|boulevard|blvd|crescent|cres|close|cl|way|circuit|cct|esplanade|esp)\b\.?";
+
+// street, suburb, state and/or 4-digit postcode
+static AU_ADDRESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?P<street>{STREET_PATTERN}),?\s+(?P<locality>(?:[A-Z][A-Za-z']+\s?){{1,3}}?),?\s+(?P<state>NSW|VIC|QLD|SA|WA|TAS|NT|ACT)?\s*,?\s*(?P<postcode>\d{{4}})?\b"
+    ))
+    .unwrap()
+});
+
+static US_ADDRESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?P<street>{STREET_PATTERN}),?\s+(?P<locality>(?:[A-Z][A-Za-z']+\s?){{1,3}}?),\s*(?P<state>A[KLRZ]|C[AOT]|D[CE]|FL|GA|HI|I[ADLN]|K[SY]|LA|M[ADEINOST]|N[CDEHJMVY]|O[HKR]|PA|RI|S[CD]|T[NX]|UT|V[AT]|W[AIVY])?\s*(?P<postcode>\d{{5}}(?:-\d{{4}})?)?\b"
+    ))
+    .unwrap()
+});
+
+static AU_STATE_NAMES: &[(&str, &str)] = &[
+    ("new south wales", "NSW"),
+    ("victoria", "VIC"),
+    ("queensland", "QLD"),
+    ("south australia", "SA"),
+    ("western australia", "WA"),
+    ("tasmania", "TAS"),
+    ("northern territory", "NT"),
+    ("australian capital territory", "ACT"),
+];
+
+// ---------------------------------------------------------------------------
+// Business registration numbers
+// ---------------------------------------------------------------------------
+
+// Unlabelled ABNs are only accepted in the printed 2-3-3-3 grouping
+static ABN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:\b(?P<label>A\.?B\.?N\.?|australian\s+business\s+number)\s*[:#.]?\s*)?\b(?P<num>\d{2}[\s\x{a0}]?\d{3}[\s\x{a0}]?\d{3}[\s\x{a0}]?\d{3})\b").unwrap()
+});
+
+static ACN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?P<label>A\.?C\.?N\.?|australian\s+company\s+number)\s*[:#.]?\s*(?P<num>\d{3}[\s\x{a0}]?\d{3}[\s\x{a0}]?\d{3})\b").unwrap()
+});
+
+static NZBN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?P<label>NZBN)\s*[:#.]?\s*(?P<num>94\d{11})\b").unwrap()
+});
+
+static UK_COMPANY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?P<label>company\s+(?:registration\s+)?(?:no\.?|number)|registered\s+in\s+england(?:\s+(?:and|&)\s+wales)?(?:\s+no\.?)?|CRN)\s*[:#.]?\s*(?P<num>[A-Z]{2}\d{6}|\d{8})\b").unwrap()
+});
+
+static VAT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?P<label>VAT(?:\s+reg(?:istration)?\.?)?(?:\s+(?:no\.?|number))?)\s*[:#.]?\s*(?P<num>(?:GB)?\s?\d{3}\s?\d{4}\s?\d{2}(?:\s?\d{3})?|(?:AT|BE|BG|CY|CZ|DE|DK|EE|EL|ES|FI|FR|HR|HU|IE|IT|LT|LU|LV|MT|NL|PL|PT|RO|SE|SI|SK)\s?[0-9A-Z]{8,12})\b").unwrap()
+});
+
+const ID_CONTEXT_CHARS: usize = 40;
+
+// ---------------------------------------------------------------------------
+// Online ordering
+// ---------------------------------------------------------------------------
+
+// First-party ordering: the venue takes orders itself (vendor, markers)
+static ORDERING_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+    vec![
+        ("Mr Yum", vec!["mryum.com", "mryum.app"]),
+        ("me&u", vec!["meandu.com", "meandu.app"]),
+        ("Order Up", vec!["orderup.com.au", "orderup.net.au"]),
+        ("Square Online", vec![".square.site", "squareup.com/online", "square-online"]),
+        ("Shopify Buy Button", vec!["buy-button-storefront", "shopifybuy.buildclient", "shopify-buy__"]),
+        ("Bopple", vec!["bopple.app", "bopple.com"]),
+        ("HungryHungry", vec!["hungryhungry.com"]),
+        ("Toast", vec!["order.toasttab.com", "toasttab.com/"]),
+        ("ChowNow", vec!["chownow.com"]),
+    ]
+});
+
+// Aggregator marketplaces — the venue is listed, not ordering direct
+static DELIVERY_PLATFORM_DOMAINS: &[&str] = &[
+    "ubereats.com",
+    "doordash.com",
+    "menulog.com.au",
+    "deliveroo.com",
+    "deliveroo.co.uk",
+    "grubhub.com",
+];
+
+// ---------------------------------------------------------------------------
+// Payment providers
+// ---------------------------------------------------------------------------
+
+static PAYMENT_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+    vec![
+        ("Stripe", vec!["js.stripe.com", "checkout.stripe.com", "buy.stripe.com"]),
+        ("Square", vec!["web.squarecdn.com", "js.squareup.com", "sandbox.web.squarecdn.com"]),
+        ("PayPal", vec!["paypal.com/sdk/js", "paypalobjects.com/api/checkout", "paypal.com/cgi-bin/webscr"]),
+        ("Afterpay", vec!["js.afterpay.com", "portal.afterpay.com", "static.afterpay.com", "<afterpay-placement"]),
+        ("Zip", vec!["static.zipmoney.com.au", "zip.co/widget", "zipmoney-widgets"]),
+        ("Klarna", vec!["x.klarnacdn.net", "js.klarna.com", "<klarna-placement"]),
+    ]
+});
+
+// "We accept" logo strips: tokens in img alt/src/class (low confidence)
+static PAYMENT_LOGO_TOKENS: &[(&str, &[&str])] = &[
+    ("PayPal", &["paypal"]),
+    ("Afterpay", &["afterpay"]),
+    ("Zip", &["zippay", "zipmoney"]),
+    ("Klarna", &["klarna"]),
+    ("Card payments", &["visa", "mastercard", "amex"]),
+];
+
+static PAYMENT_LOGO_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("img, [class]").unwrap());
+
+// ---------------------------------------------------------------------------
+// Review widgets
+// ---------------------------------------------------------------------------
+
+static REVIEW_WIDGET_SIGNATURES: LazyLock<Vec<(&str, Vec<&str>)>> = LazyLock::new(|| {
+    vec![
+        ("Trustpilot", vec!["widget.trustpilot.com", "trustpilot-widget"]),
+        ("Elfsight", vec!["elfsight.com", "elfsightcdn.com", "elfsight-app-"]),
+        ("EmbedSocial", vec!["embedsocial.com"]),
+        ("Trustindex", vec!["cdn.trustindex.io", "trustindex.io/loader"]),
+        ("Yotpo", vec!["staticw2.yotpo.com", "cdn-widgetsrepository.yotpo.com", "yotpo-widget"]),
+        ("ProductReview", vec!["productreview.com.au"]),
+        ("Reviews.io", vec!["widget.reviews.io", "widget.reviews.co.uk"]),
+        ("Judge.me", vec!["judge.me", "jdgm-widget"]),
+        ("Feefo", vec!["api.feefo.com", "feefo-widget"]),
+        ("Birdeye", vec!["birdeye.com/embed"]),
+    ]
+});
+
+static RATING_VALUE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("[itemprop='ratingValue']").unwrap()
+});
+
+static RATING_COUNT_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("[itemprop='reviewCount'], [itemprop='ratingCount']").unwrap()
+});
+
+static TESTIMONIAL_HEADING_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("h1, h2, h3, h4").unwrap()
+});
+
+static TESTIMONIAL_CONTAINER_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("[class*='testimonial' i], [id*='testimonial' i]").unwrap()
+});
+
+static TESTIMONIAL_HEADINGS: &[&str] = &[
+    "testimonial",
+    "what our customers say",
+    "what our clients say",
+    "what people say",
+    "customer reviews",
+    "our reviews",
+    "reviews",
+];
+
+// ---------------------------------------------------------------------------
+// Video embeds
+// ---------------------------------------------------------------------------
+
+static YOUTUBE_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:youtube(?:-nocookie)?\.com/(?:embed/|watch\?v=|v/)|youtu\.be/)([A-Za-z0-9_-]{11})").unwrap()
+});
+
+static WISTIA_ASYNC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"wistia_async_([A-Za-z0-9]+)").unwrap()
+});
+
+// Lazy-load plugins park the real URL in one of these
+static EMBED_SRC_ATTRS: &[&str] = &["src", "data-src", "data-lazy-src"];
+
+// ---------------------------------------------------------------------------
+// Hiring signals
+// ---------------------------------------------------------------------------
+
+// Site chrome where a careers link or hiring banner lives (not blog content)
+static HIRING_REGION_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("nav, header, footer, [class*='hero' i], [id*='hero' i], [class*='banner' i]").unwrap()
+});
+
+static CAREERS_TEXT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:careers?|jobs|join (?:our|the) team|work with us|we(?:'|’)?re hiring|we are hiring|employment|vacancies)\b").unwrap()
+});
+
+static CAREERS_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)/(?:careers?|jobs|join-(?:our-|the-)?team|work-with-us|employment|vacancies)(?:[/.?#]|$)").unwrap()
+});
+
+static HIRING_PHRASE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:now hiring|we(?:'|’)?re hiring|we are hiring|join our team|apprentices? wanted|staff wanted|positions? available|vacanc(?:y|ies))\b").unwrap()
+});
+
+// (host suffix, required path fragment) of job boards and applicant-tracking systems
+static JOB_BOARD_SIGNATURES: &[(&str, &str)] = &[
+    ("seek.com.au", ""),
+    ("seek.co.nz", ""),
+    ("indeed.com", ""),
+    ("jora.com", ""),
+    ("linkedin.com", "/jobs"),
+    ("workable.com", ""),
+    ("lever.co", ""),
+    ("greenhouse.io", ""),
+    ("bamboohr.com", "/careers"),
+    ("employmenthero.com", "/jobs"),
+];
+
+// ---------------------------------------------------------------------------
+// Parked domains
+// ---------------------------------------------------------------------------
+
+// Registrar / marketplace parking templates
+static PARKING_SIGNATURES: &[(&str, &[&str])] = &[
+    ("GoDaddy", &["img1.wsimg.com/parking-lander", "parking-lander", "godaddy.com/domainsearch/find"]),
+    ("Sedo", &["sedoparking.com", "sedo.com/search/details", "sedo domain parking"]),
+    ("Afternic", &["afternic.com/forsale", "afternic.com/domain"]),
+    ("Dan", &["dan.com/buy-domain", "dan.com/lander", "undeveloped.com"]),
+    ("Namecheap", &["parkingpage.namecheap.com", "namecheap.com/domains/registration/results"]),
+    ("Porkbun", &["porkbun.com/checkout/search", "parked by porkbun", "porkbun.com/parked"]),
+    ("ParkingCrew", &["parkingcrew.net"]),
+    ("Bodis", &["bodis.com"]),
+];
+
+static DOMAIN_FOR_SALE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:(?:this|the) domain(?: name)?(?: [a-z0-9.\-]+)? (?:is|may be) for sale|buy this domain|make an offer on this domain|domain (?:is )?available for (?:purchase|sale))\b").unwrap()
+});
+
+// Google AdSense-for-domains: the ad feed parking lots are built on
+static PARKED_AD_FEED_MARKERS: &[&str] = &["google.com/adsense/domains/caf.js", "syndicatedsearch.goog"];
+
+/// Below this many visible words a page with a single frame is a parking shell.
+const PARKED_MAX_WORDS: usize = 40;
+
+static IFRAME_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("iframe, frame").unwrap());
+
+// ---------------------------------------------------------------------------
+// Placeholder pages
+// ---------------------------------------------------------------------------
+
+// WordPress coming-soon / under-construction plugins
+static PLACEHOLDER_PLUGIN_SIGNATURES: &[(&str, &[&str])] = &[
+    // SeedProd also builds ordinary landing pages; only its coming-soon mode counts
+    ("SeedProd", &["seedprod-coming-soon", "seedprod_coming_soon", "seed_csp4", "seed-csp4"]),
+    ("Under Construction Page", &["under-construction-page", "underconstructionpage.com"]),
+    ("CMP Coming Soon", &["cmp-coming-soon", "niteothemes"]),
+    ("Coming Soon Page", &["coming-soon-page", "csmm-"]),
+];
+
+// Default pages web servers and hosting control panels ship with
+static SERVER_DEFAULT_SIGNATURES: &[(&str, &[&str])] = &[
+    ("Apache", &["apache2 ubuntu default page", "apache2 debian default page",
+        "test page for the apache http server", "<title>it works!</title>"]),
+    ("nginx", &["welcome to nginx!", "test page for the nginx http server"]),
+    ("IIS", &["iisstart.png", "iis windows server", "internet information services"]),
+    ("cPanel", &["cgi-sys/defaultwebpage.cgi", "there is no website configured at this address"]),
+    ("Plesk", &["plesk-default-page", "this page was generated by plesk"]),
+];
+
+static COMING_SOON_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:coming soon|under construction|launching soon|site is being built|website is on (?:its|the) way|new website (?:is )?coming)\b").unwrap()
+});
+
+/// Word count below which a coming-soon phrase marks the whole page.
+const PLACEHOLDER_MAX_WORDS: usize = 150;
+
+// ---------------------------------------------------------------------------
+// Error / maintenance pages
+// ---------------------------------------------------------------------------
+
+// (kind, markers) of stand-in pages that get cached with a 200 status
+static ERROR_PAGE_SIGNATURES: &[(&str, &[&str])] = &[
+    ("maintenance", &[
+        "briefly unavailable for scheduled maintenance", "wp-maintenance-mode",
+        "down for maintenance", "currently undergoing scheduled maintenance",
+        "<title>maintenance mode",
+    ]),
+    ("database_error", &[
+        "error establishing a database connection", "mysqli_connect(", "sqlstate[hy000]",
+        "too many connections",
+    ]),
+    ("challenge", &[
+        "cf_chl_opt", "cf-browser-verification", "cf-challenge-running",
+        "<title>just a moment...</title>", "checking your browser before accessing",
+        "captcha-delivery.com", "_incapsula_resource", "px-captcha", "ddos-guard",
+    ]),
+    ("cloudflare_error", &["cf-error-details", "cf-error-code", "<title>attention required! | cloudflare"]),
+    ("access_denied", &[
+        "<title>access denied</title>", "you don't have permission to access",
+        "errors.edgesuite.net", "sucuri website firewall - access denied",
+    ]),
+];
+
+/// Real pages that merely mention "maintenance" have more text than this.
+const ERROR_PAGE_MAX_WORDS: usize = 300;
+
+// ---------------------------------------------------------------------------
+// Multiple locations
+// ---------------------------------------------------------------------------
+
+static LOCATIONS_LINK_TEXT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:our locations|all locations|find (?:a|your nearest) (?:store|location|clinic|branch)|store locator|our (?:stores|clinics|branches|showrooms))\b").unwrap()
+});
+
+static LOCATIONS_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)/(?:locations|our-locations|stores|store-locator|find-a-store|branches|our-clinics)(?:[/.?#]|$)").unwrap()
+});
+
+static STORE_LOCATOR_MARKERS: &[&str] = &[
+    "wp-store-locator", "wpsl-search", "storelocatorplus", "agile-store-locator",
+    "storepoint.co", "stockist.co", "storerocket.io", "metizsoft-store-locator", "bh-storelocator",
+];
+
+static POSTAL_ADDRESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:g?po box|locked bag|private bag|p\.o\. box)\b").unwrap()
+});
+
+static STREET_NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
+
+static POSTCODE_TOKEN_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\d{4,5}\b").unwrap());
+
+// ---------------------------------------------------------------------------
+// Team size
+// ---------------------------------------------------------------------------
+
+static TEAM_SECTION_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("[class*='team' i], [id*='team' i], [class*='staff' i], [id*='staff' i], [class*='our-people' i]").unwrap()
+});
+
+static TEAM_NAME_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("h2, h3, h4, h5, h6, strong, b, figcaption, [class*='name' i]").unwrap()
+});
+
+// Quote cards that also carry a person's name
+static TESTIMONIAL_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("[class*='testimonial' i], [class*='review' i], blockquote").unwrap()
+});
+
+static PERSON_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:Dr\.? )?[A-Z][a-z'’\-]+(?: [A-Z][a-z'’\-]+){1,2}$").unwrap()
+});
+
+static TEAM_ROLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:owner|founder|co-founder|director|manager|ceo|principal|partner|technician|plumber|electrician|carpenter|builder|apprentice|mechanic|engineer|consultant|specialist|coordinator|administrator|receptionist|assistant|officer|dentist|hygienist|nurse|physiotherapist|therapist|practitioner|chiropractor|vet|veterinarian|stylist|barber|chef|accountant|bookkeeper|lawyer|solicitor|associate|lead|supervisor|estimator|designer|trainer)s?\b").unwrap()
+});
+
+static TEAM_CLAIM_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    let staff = r"(?:full[- ]time |qualified |experienced |licensed |dedicated |friendly )?(?:staff|employees|technicians|team members|people|tradespeople|plumbers|electricians|mechanics|professionals|specialists)";
+    vec![
+        Regex::new(r"(?i)\bteam of (?:over |more than )?(\d{1,4})\b").unwrap(),
+        Regex::new(&format!(r"(?i)\b(?:over |more than |with )?(\d{{1,4}})\+? {staff}\b")).unwrap(),
+    ]
+});
+
+// Title-case words that make a heading a section title, not a person
+static NON_NAME_WORDS: &[&str] = &[
+    "meet", "the", "our", "team", "about", "us", "crew", "staff", "people", "contact", "services",
+    "why", "choose", "who", "we", "are", "get", "in", "touch", "call", "book", "now", "today",
+];
+
+/// Larger claims are usually customers served or jobs done, not headcount.
+const MAX_CLAIMED_TEAM: usize = 2000;
+
+// ---------------------------------------------------------------------------
+// Trust badges
+// ---------------------------------------------------------------------------
+
+// (badge, lowercase markers) for associations and certifications
+static TRUST_BADGE_SIGNATURES: &[(&str, &[&str])] = &[
+    ("Master Plumbers", &["master plumbers", "masterplumbers"]),
+    ("Master Electricians Australia", &["master electricians", "masterelectricians"]),
+    ("NECA", &["neca.asn.au", "national electrical and communications association"]),
+    ("Master Builders", &["master builders", "masterbuilders"]),
+    ("Master Painters", &["master painters", "masterpainters"]),
+    ("HIA", &["hia.com.au", "housing industry association"]),
+    ("QBCC", &["qbcc"]),
+    ("ARCtick", &["arctick", "arc tick"]),
+    ("Clean Energy Council", &["clean energy council", "cleanenergycouncil"]),
+    ("MTA", &["mta.org.au", "motor trades association"]),
+    ("VACC", &["vacc.com.au"]),
+    ("AHPRA", &["ahpra"]),
+    ("ISO 9001", &["iso 9001", "iso9001"]),
+    ("Fully Insured", &["fully insured"]),
+    ("Licensed & Insured", &["licensed and insured", "licensed & insured", "licenced and insured"]),
+];
+
+// (issuing authority, pattern with the number in group 1)
+static LICENCE_NUMBER_RES: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    vec![
+        ("QBCC", Regex::new(r"(?i)\bQBCC(?:\s*(?:licen[cs]e|lic\.?|no\.?|number|#))*[\s:#.]*(\d{6,8})\b").unwrap()),
+        ("NSW Fair Trading", Regex::new(r"(?i)\blic(?:en[cs]e|\.)?\s*(?:no\.?|number|#)?[\s:#.]*(\d{4,7}C)\b").unwrap()),
+        ("VBA", Regex::new(r"\b(C?D[BC]-[UL]\s?\d{4,6})\b").unwrap()),
+        ("Energy Safe Victoria", Regex::new(r"\bREC[\s:#.]*(\d{4,6})\b").unwrap()),
+        ("ARCtick", Regex::new(r"(?i)\barc(?:tick)?\b[^0-9a-z]{0,20}(?:licen[cs]e|authorisation)?[\s:#.no]*(AU\d{5})\b").unwrap()),
+    ]
+});
+
+static BADGE_IMG_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("img").unwrap());
+
+// ---------------------------------------------------------------------------
+// Popups and interstitials
+// ---------------------------------------------------------------------------
+
+// Popup / exit-intent / lead-capture libraries
+static POPUP_VENDOR_SIGNATURES: &[(&str, &[&str])] = &[
+    ("OptinMonster", &["a.omappapi.com", "optinmonster", "om-holder"]),
+    ("Sumo", &["load.sumo.com", "sumome.com"]),
+    ("Privy", &["widget.privy.com", "privy-widget"]),
+    ("Klaviyo", &["static.klaviyo.com/onsite/js/klaviyo.js", "klaviyo-form"]),
+    ("Mailchimp", &["chimpstatic.com/mcjs-connected", "signup-forms/popup"]),
+    ("Justuno", &["cdn.justuno.com"]),
+    ("Poptin", &["cdn.popt.in", "poptin.com"]),
+    ("OptiMonk", &["front.optimonk.com"]),
+    ("Wisepops", &["loader.wisepops.com"]),
+    ("Popup Maker", &["pum-overlay", "popmake-"]),
+    ("Elementor Popup", &["elementor-popup-modal"]),
+    ("Hustle", &["hustle-popup", "hustle-modal"]),
+    ("Bloom", &["et_bloom_popup"]),
+    ("Thrive Leads", &["tve-leads-"]),
+];
+
+static MODAL_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("[role='dialog'], [aria-modal='true'], dialog, [class*='modal' i], [id*='modal' i], [class*='popup' i], [id*='popup' i], [class*='lightbox' i], [class*='overlay' i]").unwrap()
+});
+
+static AGE_GATE_MARKERS: &[&str] = &["age-gate", "agegate", "age_gate", "age-verification", "age_verification", "ageverif", "age-verify"];
+
+static AGE_GATE_PHRASE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:are you (?:over|at least) 1[89]|are you of legal (?:drinking|smoking) age|you must be (?:over |at least )?1[89]|confirm (?:that )?you are (?:over )?1[89]|enter your date of birth)").unwrap()
+});
+
+// ---------------------------------------------------------------------------
+// Legacy markup
+// ---------------------------------------------------------------------------
+
+static TABLE_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("table").unwrap());
+
+static TH_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("th").unwrap());
+
+// Page structure that doesn't belong in a data cell
+static TABLE_STRUCTURE_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("td div, td p, td img, td form, td h1, td h2, td h3, td ul, td ol, td iframe, td nav").unwrap()
+});
+
+static OBSOLETE_TAG_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("font, center, marquee, blink").unwrap()
+});
+
+static FRAMESET_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("frameset, frame").unwrap());
+
+static PLUGIN_EMBED_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("object, embed").unwrap());
+
+static BGCOLOR_SEL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("[bgcolor]").unwrap());
+
+static META_REFRESH_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse("meta[http-equiv='refresh' i]").unwrap()
+});
+
+static FLASH_MARKERS: &[&str] = &[".swf", "shockwave", "x-shockwave-flash", "d27cdb6e-ae6d-11cf-96b8-444553540000"];
+
+// ---------------------------------------------------------------------------
+// Map embeds
+// ---------------------------------------------------------------------------
+
+static GOOGLE_MAPS_IFRAME_MARKERS: &[&str] = &["google.com/maps/embed", "maps.google.", "google.com/maps?"];
+
+static GOOGLE_MAPS_JS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)maps\.googleapis\.com/maps/api/js[^"'\s<>]*"#).unwrap()
+});
+
+// Other map providers, checked after Google
+static MAP_PROVIDER_SIGNATURES: &[(&str, &[&str])] = &[
+    ("Mapbox", &["api.mapbox.com", "mapbox-gl"]),
+    ("Leaflet", &["leaflet.js", "leaflet.css", "unpkg.com/leaflet", "leaflet-container"]),
+    ("OpenStreetMap", &["openstreetmap.org/export/embed", "tile.openstreetmap.org"]),
+    ("Apple MapKit", &["cdn.apple-mapkit.com", "mapkit.js"]),
+];
+
+// `!2s<text>` segments of a Google Maps `pb` embed parameter
+static MAPS_PB_TEXT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!2s([^!]+)").unwrap());
+
+// ---------------------------------------------------------------------------
+// A/B testing and personalisation
+// ---------------------------------------------------------------------------
+
+pub(crate) const GOOGLE_OPTIMIZE: &str = "Google Optimize";
+
+// Loader scripts and anti-flicker snippets
+static AB_TESTING_SIGNATURES: &[(&str, &[&str])] = &[
+    ("Optimizely", &["cdn.optimizely.com/js/", "window.optimizely", "optimizely-edge"]),
+    ("VWO", &["dev.visualwebsiteoptimizer.com", "_vwo_code", "vwo_$"]),
+    // Sunset September 2023; the anti-flicker snippet hides the page behind `async-hide`
+    (GOOGLE_OPTIMIZE, &["googleoptimize.com/optimize.js", "www.googleoptimize.com", ".async-hide"]),
+    ("Convert", &["convertexperiments.com/js", "cdn-3.convertexperiments.com", "cdn-4.convertexperiments.com"]),
+    ("AB Tasty", &["try.abtasty.com", "abtasty.com/"]),
+    ("Kameleoon", &["kameleoon.eu", "kameleoon.io"]),
+    ("Dynamic Yield", &["cdn.dynamicyield.com", "st.dynamicyield.com"]),
+];
+
+static WHATSAPP_LINK_MARKERS: &[&str] = &["wa.me/", "api.whatsapp.com/send", "web.whatsapp.com/send"];
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// How strongly a matched marker implies the detection. Orders strongest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Vendor CDN or asset path — near-certain.
+    Definite,
+    /// Class names or other markup that templates can copy.
+    Probable,
+    /// Bare product names or short fragments that also turn up in prose.
+    Possible,
+}
+
+impl Confidence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Confidence::Definite => "definite",
+            Confidence::Probable => "probable",
+            Confidence::Possible => "possible",
+        }
+    }
+}
+
+/// Match a (name, asset markers, class markers) table against lowercased HTML.
+/// Asset markers are checked across every entry before any class marker, so a
+/// CDN hit for one builder beats a copied class name from another.
+fn match_builder(signatures: &TieredSignatures, html_lower: &str) -> Option<(&'static str, Confidence)> {
+    tiered_match(signatures, html_lower).map(|(name, _, confidence)| (name, confidence))
+}
+
+/// `match_builder`, also returning the marker that matched.
+fn tiered_match(
+    signatures: &TieredSignatures,
+    html_lower: &str,
+) -> Option<(&'static str, &'static str, Confidence)> {
+    for (name, assets, _) in signatures {
+        if let Some(m) = assets.iter().find(|m| html_lower.contains(*m)) {
+            return Some((name, m, Confidence::Definite));
+        }
+    }
+    for (name, _, classes) in signatures {
+        if let Some(m) = classes.iter().find(|m| html_lower.contains(*m)) {
+            return Some((name, m, Confidence::Probable));
+        }
+    }
+    None
+}
+
+/// Collect capture group 1 of every match, deduplicated, in first-seen order.
+fn unique_captures(re: &Regex, haystack: &str, out: &mut Vec<String>) {
+    for caps in re.captures_iter(haystack) {
+        let value = &caps[1];
+        if !out.iter().any(|v| v == value) {
+            out.push(value.to_string());
+        }
+    }
+}
+
+fn is_spam_email(email: &str) -> bool {
+    // Check domain blocklist
+    if let Some(pos) = email.rfind('@') {
+        let domain = &email[pos + 1..];
+        if SPAM_EMAIL_DOMAINS.contains(domain) {
+            return true;
+        }
+    }
+
+    // Check spam patterns
+    for re in SPAM_EMAIL_RES.iter() {
+        if re.is_match(email) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn format_au_number(digits: &str) -> String {
+    if digits.len() == 9 {
+        if digits.starts_with('4') {
+            // Mobile: 0XXX XXX XXX
+            format!("0{} {} {}", &digits[0..3], &digits[3..6], &digits[6..])
+        } else {
+            // Landline: 0X XXXX XXXX
+            format!("0{} {} {}", &digits[0..1], &digits[1..5], &digits[5..])
+        }
+    } else {
+        digits.to_string()
+    }
+}
+
+fn normalize_phone(phone: &str) -> String {
+    if phone.is_empty() {
+        return String::new();
+    }
+
+    let digits = PHONE_NORMALIZE_RE.replace_all(phone, "").to_string();
+
+    // Count actual digits (excluding +)
+    let digit_count = digits.chars().filter(|c| c.is_ascii_digit()).count();
+    if digit_count < 8 {
+        return String::new();
+    }
+
+    if let Some(rest) = digits.strip_prefix("+61") {
+        format_au_number(rest.strip_prefix('0').unwrap_or(rest))
+    } else if let Some(rest) = digits.strip_prefix('0') {
+        format_au_number(rest)
+    } else if digits.starts_with("1300") || digits.starts_with("1800") {
+        format!("{} {} {}", &digits[..4], &digits[4..7], &digits[7..])
+    } else if digits.starts_with("13") && digits.len() == 6 {
+        format!("{} {} {}", &digits[..2], &digits[2..4], &digits[4..])
+    } else {
+        phone.trim().to_string()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PyO3 functions
+// ---------------------------------------------------------------------------
+
+/// Accepts `str` or raw `bytes`; bytes are decoded per `encoding::read_html`.
+#[pyfunction]
+pub fn extract_emails(html: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    Ok(find_emails(&crate::encoding::read_html(html)?.text))
+}
+
+/// Whether the part after `@` looks like a real mail domain rather than an
+/// asset filename, a version number or a package registry.
+fn is_plausible_email_domain(domain: &str) -> bool {
+    crate::text::has_known_tld(domain)
+        && !EMAIL_ASSET_EXTENSIONS.iter().any(|ext| domain.ends_with(ext))
+        && !PACKAGE_EMAIL_DOMAINS.iter().any(|d| host_matches(domain, d))
+}
+
+fn find_emails(html: &str) -> Vec<String> {
+    if html.is_empty() {
+        return Vec::new();
+    }
+
+    // Bundled JS and CSS are full of `pkg@1.2.3` and `user@host` strings.
+    // Matches there only count if the same address also appears outside them
+    // (visible text, mailto links, attributes).
+    let code_ranges: Vec<(usize, usize)> = SCRIPT_STYLE_BLOCK_RES
+        .iter()
+        .flat_map(|re| re.find_iter(html).map(|m| (m.start(), m.end())))
+        .collect();
+    let in_code = |pos: usize| code_ranges.iter().any(|&(s, e)| s <= pos && pos < e);
+    let outside_code: HashSet<String> = EMAIL_RE
+        .find_iter(html)
+        .filter(|m| !in_code(m.start()))
+        .map(|m| m.as_str().to_lowercase())
+        .collect();
+
+    let mut valid_emails = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for m in EMAIL_RE.find_iter(html) {
+        let email_lower = m.as_str().to_lowercase();
+
+        if email_lower.len() > 100 {
+            continue;
+        }
+
+        if seen.contains(&email_lower) {
+            continue;
+        }
+
+        if in_code(m.start()) && !outside_code.contains(&email_lower) {
+            continue;
+        }
+
+        match email_lower.rsplit_once('@') {
+            Some((_, domain)) if is_plausible_email_domain(domain) => {}
+            _ => continue,
+        }
+
+        if is_spam_email(&email_lower) {
+            continue;
+        }
+
+        if EXCLUDE_EMAIL_RES.iter().any(|re| re.is_match(&email_lower)) {
+            continue;
+        }
+
+        // Skip hash-like local parts
+        if let Some(pos) = email_lower.find('@') {
+            let local_part = &email_lower[..pos];
+            if local_part.len() > 15 {
+                let hex_count = local_part
+                    .chars()
+                    .filter(|c| matches!(c, '0'..='9' | 'a'..='f'))
+                    .count();
+                if (hex_count as f64 / local_part.len() as f64) > 0.7 {
+                    continue;
+                }
+            }
+        }
+
+        seen.insert(email_lower.clone());
+        valid_emails.push(email_lower);
+
+        if valid_emails.len() >= 5 {
+            break;
+        }
+    }
+
+    valid_emails
+}
+
+/// Accepts `str` or raw `bytes`; bytes are decoded per `encoding::read_html`.
+#[pyfunction]
+pub fn extract_phones(html: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    Ok(find_phones(&crate::encoding::read_html(html)?.text))
+}
+
+fn find_phones(html: &str) -> Vec<String> {
+    if html.is_empty() {
+        return Vec::new();
+    }
+
+    // (number, best context score), in first-seen order
+    let mut phones: Vec<(String, u32)> = Vec::new();
+
+    for pattern in PHONE_PATTERNS.iter() {
+        for m in pattern.find_iter(html) {
+            let before = &html[..m.start()];
+            let after = &html[m.end()..];
+            if is_inside_number(before, after) || has_excluded_label(before) {
+                continue;
+            }
+            let normalized = normalize_phone(m.as_str());
+            if !is_valid_au_phone(&normalized) {
+                continue;
+            }
+            let score = phone_context_score(before);
+            match phones.iter_mut().find(|(n, _)| *n == normalized) {
+                Some(entry) => entry.1 = entry.1.max(score),
+                None => phones.push((normalized, score)),
+            }
+        }
+    }
+
+    // Numbers introduced by "call"/tel: links come first; stable otherwise
+    phones.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    phones.into_iter().map(|(n, _)| n).collect()
+}
+
+/// A match with a digit (or a digit-grouping ",." then digit) on either side
+/// is a slice of a longer number: an ABN, a price, a tracking ID.
+fn is_inside_number(before: &str, after: &str) -> bool {
+    let mut back = before.chars().rev();
+    let mut fwd = after.chars();
+    let joins = |c: Option<char>, next: Option<char>| match c {
+        Some(c) if c.is_ascii_digit() => true,
+        Some(',' | '.') => next.is_some_and(|n| n.is_ascii_digit()),
+        _ => false,
+    };
+    joins(back.next(), back.next()) || joins(fwd.next(), fwd.next())
+}
+
+/// Prices ("$1300 000") and company numbers ("ABN 13 ...") use phone-shaped digits.
+fn has_excluded_label(before: &str) -> bool {
+    let trimmed = before.trim_end();
+    if trimmed.ends_with('$') {
+        return true;
+    }
+    let window = tail_of(trimmed, 12).to_lowercase();
+    window.contains("abn") || window.contains("acn")
+}
+
+fn is_valid_au_phone(normalized: &str) -> bool {
+    let digits: String = normalized.chars().filter(char::is_ascii_digit).collect();
+    AU_PHONE_RULES
+        .iter()
+        .any(|(prefix, len)| digits.len() == *len && digits.starts_with(prefix))
+}
+
+/// 2 for a tel: link, +1 for a call-to-action word or phone-ish class on the
+/// enclosing tag.
+fn phone_context_score(before: &str) -> u32 {
+    let window = tail_of(before, 200).to_lowercase();
+    let tag_start = window.rfind('<').unwrap_or(0);
+    let tag = &window[tag_start..];
+    let mut score = 0;
+    if tail_of(&window, 30).contains("tel:") {
+        score += 2;
+    }
+    if PHONE_CTA_WORDS.iter().any(|w| tail_of(&window, 40).contains(w)) {
+        score += 1;
+    }
+    if tag.contains("class=") && PHONE_CLASS_MARKERS.iter().any(|m| tag.contains(m)) {
+        score += 1;
+    }
+    score
+}
+
+/// Longest evidence string reported alongside a detection.
+const EVIDENCE_MAX_CHARS: usize = 60;
+
+/// A detection with the marker that triggered it.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct Detection {
+    pub value: String,
+    /// "definite", "probable" or "possible".
+    pub confidence: &'static str,
+    /// The matched marker, truncated to `EVIDENCE_MAX_CHARS`.
+    pub evidence: String,
+}
+
+impl Detection {
+    fn new(value: &str, marker: &str, confidence: Confidence) -> Self {
+        Detection {
+            value: value.to_string(),
+            confidence: confidence.as_str(),
+            evidence: marker.chars().take(EVIDENCE_MAX_CHARS).collect(),
+        }
+    }
+}
+
+/// The strongest marker present in `html_lower`, trying every definite marker
+/// before any probable one and so on down the tiers.
+fn strongest_marker(
+    markers: &[(&'static str, Confidence)],
+    html_lower: &str,
+) -> Option<(&'static str, Confidence)> {
+    [Confidence::Definite, Confidence::Probable, Confidence::Possible]
+        .into_iter()
+        .find_map(|tier| {
+            markers
+                .iter()
+                .find(|(m, c)| *c == tier && html_lower.contains(m))
+                .map(|(m, _)| (*m, tier))
+        })
+}
+
+/// Every entry of `table` with at least one marker present, in table order.
+fn table_detections<'a>(
+    table: &'a MarkerTable,
+    html_lower: &'a str,
+) -> impl Iterator<Item = Detection> + 'a {
+    table.iter().filter_map(move |(name, markers)| {
+        strongest_marker(markers, html_lower).map(|(m, c)| Detection::new(name, m, c))
+    })
+}
+
+fn cms_detection(html_lower: &str) -> Option<Detection> {
+    table_detections(&CMS_SIGNATURES, html_lower).next()
+}
+
+fn tracking_detections(html_lower: &str) -> Vec<Detection> {
+    table_detections(&TRACKING_SIGNATURES, html_lower).collect()
+}
+
+fn framework_detections(html_lower: &str) -> Vec<Detection> {
+    table_detections(&FRAMEWORK_SIGNATURES, html_lower).collect()
+}
+
+/// Value is the recognised booking vendor, or "unknown" when only a generic
+/// booking marker matched. Evidence is whichever marker is strongest.
+fn booking_detection(html_lower: &str) -> Option<Detection> {
+    let vendor = tiered_match(&BOOKING_VENDOR_SIGNATURES, html_lower);
+    let generic = strongest_marker(&BOOKING_SIGNATURES, html_lower);
+    let value = vendor.map_or("unknown", |(name, _, _)| name);
+    match (vendor, generic) {
+        (Some((_, _, vc)), Some((marker, gc))) if gc < vc => Some(Detection::new(value, marker, gc)),
+        (Some((_, marker, confidence)), _) | (None, Some((marker, confidence))) => {
+            Some(Detection::new(value, marker, confidence))
+        }
+        (None, None) => None,
+    }
+}
+
+#[pyfunction]
+pub fn detect_cms(html: &str) -> Option<String> {
+    if html.is_empty() {
+        return None;
+    }
+
+    cms_detection(&html.to_lowercase()).map(|d| d.value)
+}
+
+/// Every tracker in the signature table, true for those in `detections`.
+fn tracking_flags(detections: &[Detection]) -> HashMap<String, bool> {
+    let mut result: HashMap<String, bool> = TRACKING_SIGNATURES
+        .iter()
+        .map(|(tracker, _)| (tracker.to_string(), false))
+        .collect();
+
+    for detection in detections {
+        result.insert(detection.value.clone(), true);
+    }
+
+    result
+}
+
+#[pyfunction]
+pub fn detect_tracking(html: &str) -> HashMap<String, bool> {
+    if html.is_empty() {
+        return tracking_flags(&[]);
+    }
+
+    tracking_flags(&tracking_detections(&html.to_lowercase()))
+}
+
+#[pyfunction]
+pub fn detect_booking_system(html: &str) -> bool {
+    if html.is_empty() {
+        return false;
+    }
+
+    booking_detection(&html.to_lowercase()).is_some()
+}
+
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct BookingVendor {
+    pub vendor: String,
+    /// "widget" when the booking flow is embedded on the page, "link" when the
+    /// page only links out to a hosted booking page.
+    pub integration: &'static str,
+}
+
+fn booking_vendor_match(html_lower: &str) -> Option<BookingVendor> {
+    // Embedded widgets are checked first: a widget embed usually also contains
+    // links to the vendor's hosted page.
+    for (vendor, widgets, _) in BOOKING_VENDOR_SIGNATURES.iter() {
+        if widgets.iter().any(|m| html_lower.contains(m)) {
+            return Some(BookingVendor { vendor: vendor.to_string(), integration: "widget" });
+        }
+    }
+    for (vendor, _, links) in BOOKING_VENDOR_SIGNATURES.iter() {
+        if links.iter().any(|m| html_lower.contains(m)) {
+            return Some(BookingVendor { vendor: vendor.to_string(), integration: "link" });
+        }
+    }
+    None
+}
+
+/// Identify the booking vendor and whether it is embedded or linked out to.
+///
+/// Returns None when no known vendor matches; detect_booking_system still
+/// reports generic "book now" style pages.
+#[pyfunction]
+pub fn detect_booking_vendor(html: &str) -> Option<BookingVendor> {
+    if html.is_empty() {
+        return None;
+    }
+    booking_vendor_match(&html.to_lowercase())
+}
+
+#[pyfunction]
+pub fn detect_frameworks(html: &str) -> Vec<String> {
+    if html.is_empty() {
+        return Vec::new();
+    }
+
+    framework_detections(&html.to_lowercase()).into_iter().map(|d| d.value).collect()
+}
+
+fn has_responsive_indicator(html_lower: &str) -> bool {
+    RESPONSIVE_INDICATORS.iter().any(|ind| html_lower.contains(ind))
+}
+
+#[pyfunction]
+pub fn detect_responsive(html: &str) -> bool {
+    if html.is_empty() {
+        return false;
+    }
+
+    has_responsive_indicator(&html.to_lowercase())
+}
+
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct FrameworkVersion {
+    pub framework: String,
+    pub version: Option<String>,
+    /// AngularJS 1.x, React <16 or Vue 1.x.
+    pub legacy: bool,
+}
+
+fn major_version(version: &str) -> Option<u32> {
+    version.split('.').next()?.parse().ok()
+}
+
+/// Identify React / Vue / Angular with their version where the markup gives
+/// it away (root attributes, devtools hooks, CDN bundle paths).
+#[pyfunction]
+pub fn detect_framework_versions(html: &str) -> Vec<FrameworkVersion> {
+    if html.is_empty() {
+        return Vec::new();
+    }
+    framework_versions(&html.to_lowercase())
+}
+
+fn framework_versions(html_lower: &str) -> Vec<FrameworkVersion> {
+    let mut found = Vec::new();
+
+    let react_version = REACT_VERSION_RES
+        .iter()
+        .find_map(|re| capture_package_version(re, html_lower));
+    // data-reactid was dropped in React 16; data-reactroot is 15+
+    let reactid = html_lower.contains("data-reactid");
+    if react_version.is_some() || reactid || html_lower.contains("data-reactroot") {
+        let legacy = reactid
+            || react_version.as_deref().and_then(major_version).is_some_and(|m| m < 16);
+        found.push(FrameworkVersion {
+            framework: "React".to_string(),
+            version: react_version,
+            legacy,
+        });
+    }
+
+    let vue_version = capture_package_version(&VUE_VERSION_RE, html_lower);
+    if vue_version.is_some() || VUE_MARKERS.iter().any(|m| html_lower.contains(m)) {
+        let legacy = vue_version.as_deref().and_then(major_version).is_some_and(|m| m < 2);
+        found.push(FrameworkVersion {
+            framework: "Vue.js".to_string(),
+            version: vue_version,
+            legacy,
+        });
+    }
+
+    if let Some(caps) = NG_VERSION_RE.captures(html_lower) {
+        found.push(FrameworkVersion {
+            framework: "Angular".to_string(),
+            version: Some(caps[1].to_string()),
+            legacy: false,
+        });
+    } else {
+        let angularjs_version = ANGULARJS_VERSION_RES
+            .iter()
+            .find_map(|re| capture_package_version(re, html_lower))
+            .filter(|v| major_version(v) == Some(1));
+        if angularjs_version.is_some() || ANGULARJS_MARKERS.iter().any(|m| html_lower.contains(m)) {
+            found.push(FrameworkVersion {
+                framework: "AngularJS".to_string(),
+                version: angularjs_version,
+                legacy: true,
+            });
+        }
+    }
+
+    found
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct JQueryInfo {
+    pub present: bool,
+    pub version: Option<String>,
+    /// Version below 3.0.
+    pub is_legacy: bool,
+    pub legacy_plugins: Vec<String>,
+}
+
+/// Detect jQuery, its version (CDN or locally hosted filename) and
+/// recognisable legacy slider/lightbox plugins.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_jquery(html: &str, max_bytes: Option<usize>) -> PyResult<JQueryInfo> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(JQueryInfo::default());
+    }
+    Ok(jquery_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn jquery_document(document: &Html, html_lower: &str) -> JQueryInfo {
+    let mut info = JQueryInfo::default();
+
+    info.version = JQUERY_VERSION_RES
+        .iter()
+        .find_map(|re| capture_package_version(re, html_lower));
+    info.present = info.version.is_some()
+        || FRAMEWORK_SIGNATURES
+            .iter()
+            .filter(|(name, _)| *name == "jQuery")
+            .any(|(_, sigs)| sigs.iter().any(|(s, _)| html_lower.contains(s)));
+    info.is_legacy = info.version.as_deref().and_then(major_version).is_some_and(|m| m < 3);
+
+    let srcs: Vec<String> = document
+        .select(&SCRIPT_SRC_SEL)
+        .filter_map(|s| s.value().attr("src"))
+        .map(str::to_lowercase)
+        .collect();
+    for (plugin, markers) in LEGACY_JQUERY_PLUGINS {
+        if srcs.iter().any(|src| markers.iter().any(|m| src.contains(m))) {
+            info.legacy_plugins.push(plugin.to_string());
+        }
+    }
+
+    info
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct ResponsiveAnalysis {
+    pub has_viewport_meta: bool,
+    pub viewport_device_width: bool,
+    pub has_media_queries: bool,
+    pub has_responsive_images: bool,
+    pub has_fixed_width_layout: bool,
+    /// Links to a separate m. mobile site.
+    pub has_mobile_subdomain_links: bool,
+    pub responsive_score: u32,
+    /// Same as `detect_responsive`.
+    pub is_responsive: bool,
+}
+
+fn has_fixed_width(css: &str) -> bool {
+    FIXED_WIDTH_RE
+        .captures_iter(css)
+        .any(|caps| caps[1].parse::<u32>().is_ok_and(|px| px >= FIXED_LAYOUT_MIN_PX))
+}
+
+/// Weigh the evidence that a layout actually adapts to small screens.
+///
+/// A viewport meta alone is not enough: fixed-width wrappers or a separate
+/// m. mobile site cap the score however the other signals look.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn analyze_responsive(html: &str, max_bytes: Option<usize>) -> PyResult<ResponsiveAnalysis> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(ResponsiveAnalysis::default());
+    }
+    Ok(responsive_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn responsive_document(document: &Html, html_lower: &str) -> ResponsiveAnalysis {
+    let mut analysis = ResponsiveAnalysis::default();
+    if html_lower.is_empty() {
+        return analysis;
+    }
+    analysis.is_responsive = has_responsive_indicator(html_lower);
+
+    // Browsers apply the last viewport meta
+    if let Some(viewport) = document.select(&VIEWPORT_SEL).next_back() {
+        analysis.has_viewport_meta = true;
+        let content = viewport.value().attr("content").unwrap_or("");
+        analysis.viewport_device_width =
+            crate::metadata::parse_viewport(content).width.as_deref() == Some("device-width");
+    }
+
+    let style_blocks: Vec<String> = document
+        .select(&STYLE_SEL)
+        .map(|style| style.text().collect::<String>())
+        .collect();
+    analysis.has_media_queries = style_blocks.iter().any(|css| MEDIA_QUERY_RE.is_match(css))
+        || document.select(&MEDIA_LINK_SEL).any(|link| {
+            let media = link.value().attr("media").unwrap_or("").to_lowercase();
+            media.contains("max-width") || media.contains("min-width")
+        });
+
+    analysis.has_responsive_images = document.select(&SRCSET_SEL).next().is_some()
+        || document.select(&PICTURE_SEL).next().is_some();
+
+    analysis.has_fixed_width_layout = document
+        .select(&LAYOUT_WRAPPER_SEL)
+        .filter_map(|el| el.value().attr("style"))
+        .any(has_fixed_width)
+        || style_blocks.iter().any(|css| {
+            FIXED_WRAPPER_RULE_RE
+                .captures_iter(css)
+                .any(|rule| has_fixed_width(&rule[1]))
+        })
+        || document.select(&LAYOUT_TABLE_SEL).any(|table| {
+            let width = table.value().attr("width").unwrap_or("");
+            width.trim_end_matches("px").parse::<u32>().is_ok_and(|px| px >= FIXED_LAYOUT_MIN_PX)
+        });
+
+    analysis.has_mobile_subdomain_links = document
+        .select(&LINK_SEL)
+        .filter_map(|a| a.value().attr("href"))
+        .any(|href| MOBILE_SUBDOMAIN_RE.is_match(href.trim()));
+
+    let mut score: u32 = 0;
+    if analysis.viewport_device_width {
+        score += 35;
+    } else if analysis.has_viewport_meta {
+        score += 10;
+    }
+    if analysis.has_media_queries {
+        score += 30;
+    }
+    if analysis.has_responsive_images {
+        score += 15;
+    }
+    if !analysis.has_fixed_width_layout {
+        score += 20;
+    } else {
+        score = score.min(30);
+    }
+    if analysis.has_mobile_subdomain_links {
+        score = score.min(40);
+    }
+    analysis.responsive_score = score;
+
+    analysis
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct WordPressDetails {
+    pub is_wordpress: bool,
+    pub theme: Option<String>,
+    pub plugins: Vec<String>,
+    pub uses_default_theme: bool,
+    pub has_page_builder: bool,
+}
+
+/// Enumerate the active WordPress theme and visible plugins from asset paths.
+///
+/// Returns an empty result (is_wordpress=False) when no WordPress markers are present.
+#[pyfunction]
+pub fn detect_wordpress_details(html: &str) -> WordPressDetails {
+    let html_lower = html.to_lowercase();
+    if !WP_MARKERS.iter().any(|m| html_lower.contains(m)) {
+        return WordPressDetails::default();
+    }
+
+    // Child themes load the parent's assets too; the first one referenced is
+    // almost always the active (child) theme.
+    let theme = WP_THEME_RE
+        .captures(&html_lower)
+        .map(|c| c[1].to_string());
+
+    let mut plugins: Vec<String> = Vec::new();
+    for caps in WP_PLUGIN_RE.captures_iter(&html_lower) {
+        let slug = &caps[1];
+        if !plugins.iter().any(|p| p == slug) {
+            plugins.push(slug.to_string());
+        }
+    }
+
+    let uses_default_theme = theme
+        .as_deref()
+        .is_some_and(|t| WP_DEFAULT_THEMES.contains(&t));
+    let has_page_builder = plugins
+        .iter()
+        .map(String::as_str)
+        .chain(theme.as_deref())
+        .any(|slug| WP_PAGE_BUILDER_SLUGS.contains(&slug));
+
+    WordPressDetails {
+        is_wordpress: true,
+        theme,
+        plugins,
+        uses_default_theme,
+        has_page_builder,
+    }
+}
+
+/// Detect hosted site builders (Wix, Squarespace, Weebly, GoDaddy, Duda).
+#[pyfunction]
+pub fn detect_site_builder(html: &str) -> Option<String> {
+    if html.is_empty() {
+        return None;
+    }
+    match_builder(&SITE_BUILDER_SIGNATURES, &html.to_lowercase()).map(|(name, _)| name.to_string())
+}
+
+/// Detect WordPress page builders (Elementor, Divi, Beaver Builder, WPBakery).
+#[pyfunction]
+pub fn detect_page_builder(html: &str) -> Option<String> {
+    if html.is_empty() {
+        return None;
+    }
+    match_builder(&PAGE_BUILDER_SIGNATURES, &html.to_lowercase()).map(|(name, _)| name.to_string())
+}
+
+/// Detect embedded live chat / chatbot widgets and return the vendor name.
+///
+/// WhatsApp click-to-chat links are not widgets; analyze_tech_stack reports
+/// them separately as has_whatsapp_link.
+#[pyfunction]
+pub fn detect_chat_widget(html: &str) -> Option<String> {
+    if html.is_empty() {
+        return None;
+    }
+    chat_widget_vendor(&html.to_lowercase())
+}
+
+fn chat_widget_vendor(html_lower: &str) -> Option<String> {
+    CHAT_WIDGET_SIGNATURES
+        .iter()
+        .find(|(_, signatures)| signatures.iter().any(|sig| html_lower.contains(sig)))
+        .map(|(vendor, _)| vendor.to_string())
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct AbTesting {
+    pub vendors: Vec<String>,
+    /// Google Optimize still loaded after its 2023 shutdown.
+    pub has_dead_google_optimize: bool,
+}
+
+/// Detect A/B testing and personalisation tools from their loader scripts
+/// and anti-flicker snippets.
+#[pyfunction]
+pub fn detect_ab_testing(html: &str) -> AbTesting {
+    if html.is_empty() {
+        return AbTesting::default();
+    }
+    ab_testing(&html.to_lowercase())
+}
+
+fn ab_testing(html_lower: &str) -> AbTesting {
+    let mut ab = AbTesting::default();
+    ab.vendors = AB_TESTING_SIGNATURES
+        .iter()
+        .filter(|(_, markers)| markers.iter().any(|m| html_lower.contains(m)))
+        .map(|(vendor, _)| vendor.to_string())
+        .collect();
+    ab.has_dead_google_optimize = ab.vendors.iter().any(|v| v == GOOGLE_OPTIMIZE);
+    ab
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct MapEmbed {
+    pub has_map: bool,
+    /// "Google Maps", "Mapbox", "Leaflet", "OpenStreetMap" or "Apple MapKit".
+    pub provider: Option<&'static str>,
+    /// Place or address the Google Maps embed points at, decoded.
+    pub embedded_place_query: Option<String>,
+    /// Maps JS API loaded without a key: renders the grey "for development
+    /// purposes only" map.
+    pub broken_api_key_suspected: bool,
+}
+
+/// The place searched by a Google Maps embed URL: `q`/`query` for the classic
+/// and v1 embeds, otherwise the longest text segment of the `pb` parameter
+/// (short ones are language and region codes).
+fn maps_place_query(src: &str) -> Option<String> {
+    let url = Url::parse(src).or_else(|_| Url::parse(&format!("https:{src}"))).ok()?;
+    let mut pb = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "q" | "query" if !value.trim().is_empty() => return Some(value.trim().to_string()),
+            "pb" => pb = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+    MAPS_PB_TEXT_RE
+        .captures_iter(&pb?)
+        .filter_map(|c| {
+            url::form_urlencoded::parse(c[1].as_bytes()).next().map(|(k, _)| k.into_owned())
+        })
+        .filter(|s| s.chars().count() > 3)
+        .max_by_key(|s| s.len())
+}
+
+/// Detect embedded maps and the provider behind them.
+///
+/// For Google Maps iframes the embedded place query is extracted so it can be
+/// compared with the prospect's address.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_map_embed(html: &str, max_bytes: Option<usize>) -> PyResult<MapEmbed> {
+    let html = limit_input(html, max_bytes)?;
+    let mut map = MapEmbed::default();
+    if html.is_empty() {
+        return Ok(map);
+    }
+
+    let document = Html::parse_document(html);
+    for iframe in document.select(&IFRAME_SEL) {
+        let Some(src) = embed_src(&iframe) else { continue };
+        let lower = src.to_lowercase();
+        if GOOGLE_MAPS_IFRAME_MARKERS.iter().any(|m| lower.contains(m)) {
+            map.provider = Some("Google Maps");
+            map.embedded_place_query = maps_place_query(src);
+            if map.embedded_place_query.is_some() {
+                break;
+            }
+        }
+    }
+
+    if let Some(m) = GOOGLE_MAPS_JS_RE.find(html) {
+        let has_key = Url::parse(&format!("https://{}", m.as_str()))
+            .map(|u| u.query_pairs().any(|(k, v)| k == "key" && !v.trim().is_empty()))
+            .unwrap_or(false);
+        map.provider.get_or_insert("Google Maps");
+        map.broken_api_key_suspected = !has_key;
+    }
+
+    if map.provider.is_none() {
+        let html_lower = html.to_lowercase();
+        map.provider = MAP_PROVIDER_SIGNATURES
+            .iter()
+            .find(|(_, markers)| markers.iter().any(|m| html_lower.contains(m)))
+            .map(|(provider, _)| *provider);
+    }
+    map.has_map = map.provider.is_some();
+    Ok(map)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct LegacyMarkup {
+    pub layout_tables: usize,
+    /// font, center, marquee and blink elements.
+    pub obsolete_tags: usize,
+    pub frameset_elements: usize,
+    pub flash_embeds: usize,
+    pub bgcolor_attributes: usize,
+    pub meta_refresh: bool,
+    /// 0–100: how strongly the markup says "built before 2010".
+    pub legacy_score: u32,
+}
+
+impl LegacyMarkup {
+    fn score(&self) -> u32 {
+        let mut score = 0;
+        if self.frameset_elements > 0 {
+            score += 30;
+        }
+        if self.flash_embeds > 0 {
+            score += 30;
+        }
+        score += (self.layout_tables as u32 * 10).min(25);
+        score += (self.obsolete_tags as u32 * 2).min(20);
+        score += (self.bgcolor_attributes as u32 * 2).min(15);
+        if self.meta_refresh {
+            score += 10;
+        }
+        score.min(100)
+    }
+}
+
+/// A table used for page layout rather than data: it nests another table, or
+/// has no header cells and holds block structure (divs, images, forms).
+fn is_layout_table(table: &ElementRef) -> bool {
+    let nests_table = table.select(&TABLE_SEL).any(|t| t.id() != table.id());
+    nests_table
+        || (table.select(&TH_SEL).next().is_none() && table.select(&TABLE_STRUCTURE_SEL).next().is_some())
+}
+
+/// Count table layouts, obsolete presentational tags, frames, Flash embeds,
+/// bgcolor attributes and meta refresh redirects.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_legacy_markup(html: &str, max_bytes: Option<usize>) -> PyResult<LegacyMarkup> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(LegacyMarkup::default());
+    }
+    Ok(legacy_markup_document(&Html::parse_document(html)))
+}
+
+fn legacy_markup_document(document: &Html) -> LegacyMarkup {
+    let mut legacy = LegacyMarkup::default();
+    legacy.layout_tables = document.select(&TABLE_SEL).filter(is_layout_table).count();
+    legacy.obsolete_tags = document.select(&OBSOLETE_TAG_SEL).count();
+    legacy.frameset_elements = document.select(&FRAMESET_SEL).count();
+    legacy.flash_embeds = document
+        .select(&PLUGIN_EMBED_SEL)
+        .filter(|el| {
+            el.value().attrs().any(|(_, v)| {
+                let v = v.to_lowercase();
+                FLASH_MARKERS.iter().any(|m| v.contains(m))
+            })
+        })
+        .count();
+    legacy.bgcolor_attributes = document.select(&BGCOLOR_SEL).count();
+    legacy.meta_refresh = document.select(&META_REFRESH_SEL).next().is_some();
+    legacy.legacy_score = legacy.score();
+    legacy
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct Popups {
+    /// A popup library, or an email field inside modal/overlay markup.
+    pub has_email_popup: bool,
+    pub popup_vendor: Option<String>,
+    pub has_age_gate: bool,
+}
+
+/// Detect newsletter/lead-capture popups and age-verification overlays.
+///
+/// An email field only counts when it sits inside dialog, modal, popup or
+/// overlay markup; a plain newsletter form in the footer is not a popup.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_popups(html: &str, max_bytes: Option<usize>) -> PyResult<Popups> {
+    let html = limit_input(html, max_bytes)?;
+    let mut popups = Popups::default();
+    if html.is_empty() {
+        return Ok(popups);
+    }
+
+    let html_lower = html.to_lowercase();
+    popups.popup_vendor = POPUP_VENDOR_SIGNATURES
+        .iter()
+        .find(|(_, markers)| markers.iter().any(|m| html_lower.contains(m)))
+        .map(|(vendor, _)| vendor.to_string());
+
+    let document = Html::parse_document(html);
+    let mut modal_email = false;
+    let mut modal_age_gate = false;
+    for modal in document.select(&MODAL_SEL) {
+        modal_email = modal_email || modal.select(&EMAIL_INPUT_SEL).next().is_some();
+        if !modal_age_gate {
+            let mut text = String::new();
+            collect_text(modal, &|_| false, &mut text);
+            modal_age_gate = AGE_GATE_PHRASE_RE.is_match(&text);
+        }
+    }
+
+    popups.has_email_popup = popups.popup_vendor.is_some() || modal_email;
+    popups.has_age_gate = modal_age_gate || AGE_GATE_MARKERS.iter().any(|m| html_lower.contains(m));
+    Ok(popups)
+}
+
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct LicenceNumber {
+    pub authority: &'static str,
+    pub number: String,
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct TrustBadges {
+    pub badges: Vec<String>,
+    pub licence_numbers: Vec<LicenceNumber>,
+}
+
+/// Find association memberships, certifications and licence numbers in image
+/// alt/title/src, link hrefs and page text.
+///
+/// `extra_badges` adds vertical-specific names (matched case-insensitively)
+/// on top of the built-in list, like `extra_filter_domains` for directories.
+#[pyfunction]
+#[pyo3(signature = (html, extra_badges=None, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_trust_badges(
+    html: &str,
+    extra_badges: Option<Vec<String>>,
+    max_bytes: Option<usize>,
+) -> PyResult<TrustBadges> {
+    let html = limit_input(html, max_bytes)?;
+    let mut found = TrustBadges::default();
+    if html.is_empty() {
+        return Ok(found);
+    }
+
+    let document = Html::parse_document(html);
+    let text = document_text(&document);
+    let mut haystack = text.to_lowercase();
+    for img in document.select(&BADGE_IMG_SEL) {
+        for attr in ["alt", "title", "src"] {
+            if let Some(value) = img.value().attr(attr) {
+                haystack.push(' ');
+                haystack.push_str(&value.to_lowercase());
+            }
+        }
+    }
+    for anchor in document.select(&LINK_SEL) {
+        haystack.push(' ');
+        haystack.push_str(&anchor.value().attr("href").unwrap_or("").to_lowercase());
+    }
+
+    for (badge, markers) in TRUST_BADGE_SIGNATURES {
+        if markers.iter().any(|m| haystack.contains(m)) {
+            found.badges.push(badge.to_string());
+        }
+    }
+    for badge in extra_badges.unwrap_or_default() {
+        let marker = badge.trim().to_lowercase();
+        if !marker.is_empty() && haystack.contains(&marker) && !found.badges.contains(&badge) {
+            found.badges.push(badge);
+        }
+    }
+
+    for (authority, re) in LICENCE_NUMBER_RES.iter() {
+        for caps in re.captures_iter(&text) {
+            let number = caps[1].to_uppercase();
+            if !found.licence_numbers.iter().any(|l| l.number == number) {
+                found.licence_numbers.push(LicenceNumber { authority, number });
+            }
+        }
+    }
+    Ok(found)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct TeamSize {
+    /// Distinct people shown as name + role cards in team/staff sections.
+    pub team_mentions: usize,
+    /// Headcount stated in copy ("our team of 12 technicians").
+    pub claimed_size: Option<usize>,
+    /// "high" for an explicit claim, "medium" for two or more team cards,
+    /// "low" otherwise.
+    pub confidence: &'static str,
+}
+
+/// Estimate headcount from team-member cards and explicit "team of N" claims.
+///
+/// A card counts only when a person-shaped name sits next to a role word, so
+/// testimonial cards (customer name + quote) are not mistaken for staff.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn estimate_team_size(html: &str, max_bytes: Option<usize>) -> PyResult<TeamSize> {
+    let html = limit_input(html, max_bytes)?;
+    let mut team = TeamSize { confidence: "low", ..Default::default() };
+    if html.is_empty() {
+        return Ok(team);
+    }
+
+    let document = Html::parse_document(html);
+    let mut names = HashSet::new();
+    for section in document.select(&TEAM_SECTION_SEL) {
+        for el in section.select(&TEAM_NAME_SEL) {
+            let name = el.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" ");
+            let is_name = PERSON_NAME_RE.is_match(&name)
+                && !TEAM_ROLE_RE.is_match(&name)
+                && !name.split(' ').any(|w| NON_NAME_WORDS.contains(&w.to_lowercase().as_str()));
+            if !is_name {
+                continue;
+            }
+            let in_testimonial =
+                el.ancestors().filter_map(ElementRef::wrap).any(|a| TESTIMONIAL_SEL.matches(&a));
+            if in_testimonial {
+                continue;
+            }
+            // The card holding the name: its text minus the name must mention a role
+            let Some(card) = el.parent().and_then(ElementRef::wrap) else { continue };
+            let card_text = card.text().collect::<String>().replacen(&name, "", 1);
+            if TEAM_ROLE_RE.is_match(&card_text) {
+                names.insert(name);
+            }
+        }
+    }
+    team.team_mentions = names.len();
+
+    let text = document_text(&document);
+    team.claimed_size = TEAM_CLAIM_RES
+        .iter()
+        .flat_map(|re| re.captures_iter(&text))
+        .filter_map(|c| c[1].parse::<usize>().ok())
+        .filter(|n| (1..=MAX_CLAIMED_TEAM).contains(n))
+        .max();
+
+    team.confidence = if team.claimed_size.is_some() {
+        "high"
+    } else if team.team_mentions >= 2 {
+        "medium"
+    } else {
+        "low"
+    };
+    Ok(team)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct Locations {
+    pub estimated_location_count: usize,
+    /// Street addresses, one per distinct location.
+    pub location_addresses: Vec<String>,
+    pub has_store_locator: bool,
+}
+
+/// Street number + postcode: the same place written "St" in JSON-LD and
+/// "Street" in the footer collapses to one location.
+fn location_key(address: &str) -> String {
+    let number = STREET_NUMBER_RE.find(address).map_or("", |m| m.as_str());
+    let postcode = POSTCODE_TOKEN_RE.find_iter(address).last().map_or("", |m| m.as_str());
+    format!("{number}|{postcode}")
+}
+
+/// Estimate how many physical locations a business has.
+///
+/// Counts distinct street addresses (PO boxes and repeats of the same address
+/// excluded) and landline area codes; "our locations" links and store-locator
+/// plugins lift the estimate to at least two.
+#[pyfunction]
+#[pyo3(signature = (html, region, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_locations(html: &str, region: &str, max_bytes: Option<usize>) -> PyResult<Locations> {
+    let html = limit_input(html, max_bytes)?;
+    let mut locations = Locations::default();
+    if html.is_empty() {
+        return Ok(locations);
+    }
+
+    let document = Html::parse_document(html);
+    let mut seen = HashSet::new();
+    for address in document_addresses(&document, region) {
+        if !POSTAL_ADDRESS_RE.is_match(&address) && seen.insert(location_key(&address)) {
+            locations.location_addresses.push(address);
+        }
+    }
+
+    // Landlines in different states ("02", "03", "07", "08") are different sites
+    let area_codes: HashSet<String> = find_phones(html)
+        .into_iter()
+        .filter(|p| p.starts_with('0') && !p.starts_with("04"))
+        .map(|p| p[..2].to_string())
+        .collect();
+
+    let html_lower = html.to_lowercase();
+    locations.has_store_locator = STORE_LOCATOR_MARKERS.iter().any(|m| html_lower.contains(m));
+    let has_locations_link = document.select(&LINK_SEL).any(|a| {
+        let href = a.value().attr("href").unwrap_or("");
+        LOCATIONS_PATH_RE.is_match(href)
+            || LOCATIONS_LINK_TEXT_RE.is_match(&a.text().collect::<String>())
+    });
+
+    let mut count = locations.location_addresses.len().max(area_codes.len());
+    if locations.has_store_locator || has_locations_link {
+        count = count.max(2);
+    }
+    locations.estimated_location_count = count;
+    Ok(locations)
+}
+
+fn error_page_kind(document: &Html, html_lower: &str) -> Option<&'static str> {
+    let kind = ERROR_PAGE_SIGNATURES
+        .iter()
+        .find(|(_, markers)| markers.iter().any(|m| html_lower.contains(m)))
+        .map(|(kind, _)| *kind)?;
+
+    let mut text = String::new();
+    collect_text(document.root_element(), &is_non_content, &mut text);
+    (text.split_whitespace().count() < ERROR_PAGE_MAX_WORDS).then_some(kind)
+}
+
+/// Classify maintenance pages, database errors, Cloudflare error and bot
+/// challenge pages, and "Access denied" blocks that were served as content.
+///
+/// Returns "maintenance", "database_error", "cloudflare_error", "challenge" or
+/// "access_denied"; None for an ordinary page.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_error_page(html: &str, max_bytes: Option<usize>) -> PyResult<Option<String>> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(None);
+    }
+    Ok(error_page_kind(&Html::parse_document(html), &html.to_lowercase()).map(str::to_string))
+}
+
+/// Collapse an error page kind to analyze_tech_stack's page_status.
+fn page_status_for(kind: &str) -> &'static str {
+    match kind {
+        "maintenance" => "maintenance",
+        "challenge" | "access_denied" => "challenge",
+        _ => "error",
+    }
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct PlaceholderPage {
+    pub is_placeholder: bool,
+    /// "coming_soon_plugin", "builder_default", "server_default" or "generic".
+    pub placeholder_type: Option<&'static str>,
+    /// Plugin, site builder or server behind the placeholder, when known.
+    pub builder_hint: Option<String>,
+}
+
+/// Recognise coming-soon and under-construction pages: WordPress plugins,
+/// site-builder placeholders, web server / control panel default pages, and
+/// near-empty pages built around a "coming soon" phrase.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_placeholder_page(html: &str, max_bytes: Option<usize>) -> PyResult<PlaceholderPage> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(PlaceholderPage::default());
+    }
+    Ok(placeholder_page_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn placeholder_page_document(document: &Html, html_lower: &str) -> PlaceholderPage {
+    let mut page = PlaceholderPage::default();
+    let signature = |table: &[(&'static str, &[&'static str])]| {
+        table
+            .iter()
+            .find(|(_, markers)| markers.iter().any(|m| html_lower.contains(m)))
+            .map(|(name, _)| name.to_string())
+    };
+
+    let (placeholder_type, hint) = if let Some(server) = signature(SERVER_DEFAULT_SIGNATURES) {
+        (Some("server_default"), Some(server))
+    } else if let Some(plugin) = signature(PLACEHOLDER_PLUGIN_SIGNATURES) {
+        (Some("coming_soon_plugin"), Some(plugin))
+    } else {
+        let mut text = String::new();
+        collect_text(document.root_element(), &is_non_content, &mut text);
+        let placeholder_text = text.split_whitespace().count() < PLACEHOLDER_MAX_WORDS
+            && COMING_SOON_RE.is_match(&text);
+        match match_builder(&SITE_BUILDER_SIGNATURES, html_lower) {
+            Some((builder, _)) if placeholder_text => {
+                (Some("builder_default"), Some(builder.to_string()))
+            }
+            _ if placeholder_text => (Some("generic"), None),
+            _ => (None, None),
+        }
+    };
+
+    page.is_placeholder = placeholder_type.is_some();
+    page.placeholder_type = placeholder_type;
+    page.builder_hint = hint;
+    page
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct ParkedDomain {
+    pub is_parked: bool,
+    pub parking_provider: Option<String>,
+    pub is_for_sale: bool,
+}
+
+/// Recognise registrar parking pages and "this domain is for sale" landers.
+///
+/// A page is parked when it uses a known parking template or ad feed, or is a
+/// near-empty shell around a single frame. is_for_sale is reported on its own:
+/// a for-sale notice on a page with real content doesn't make it parked.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_parked_domain(html: &str, max_bytes: Option<usize>) -> PyResult<ParkedDomain> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(ParkedDomain::default());
+    }
+    Ok(parked_domain_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn parked_domain_document(document: &Html, html_lower: &str) -> ParkedDomain {
+    let mut parked = ParkedDomain {
+        parking_provider: PARKING_SIGNATURES
+            .iter()
+            .find(|(_, markers)| markers.iter().any(|m| html_lower.contains(m)))
+            .map(|(provider, _)| provider.to_string()),
+        ..Default::default()
+    };
+    let ad_feed = PARKED_AD_FEED_MARKERS.iter().any(|m| html_lower.contains(m));
+
+    let mut text = String::new();
+    collect_text(document.root_element(), &is_non_content, &mut text);
+    parked.is_for_sale = DOMAIN_FOR_SALE_RE.is_match(&text);
+
+    let near_empty = text.split_whitespace().count() < PARKED_MAX_WORDS;
+    let frame_shell = near_empty && document.select(&IFRAME_SEL).count() == 1;
+
+    parked.is_parked = parked.parking_provider.is_some()
+        || ad_feed
+        || frame_shell
+        || (parked.is_for_sale && near_empty);
+    parked
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct HiringSignals {
+    pub has_careers_page: bool,
+    pub careers_links: Vec<String>,
+    pub job_board_links: Vec<String>,
+    /// "Now hiring"-style phrases in nav/header/footer/hero text.
+    pub hiring_mentions_count: usize,
+}
+
+impl HiringSignals {
+    pub fn is_hiring(&self) -> bool {
+        self.has_careers_page || !self.job_board_links.is_empty() || self.hiring_mentions_count > 0
+    }
+}
+
+/// Look for careers links, outbound job-board links and "now hiring" phrases.
+///
+/// Careers links and phrases only count inside site chrome (nav, header,
+/// footer, hero) so blog posts about hiring advice don't match.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_hiring_signals(html: &str, max_bytes: Option<usize>) -> PyResult<HiringSignals> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(HiringSignals::default());
+    }
+    Ok(hiring_signals_document(&Html::parse_document(html)))
+}
+
+fn hiring_signals_document(document: &Html) -> HiringSignals {
+    let mut hiring = HiringSignals::default();
+    for region in document.select(&HIRING_REGION_SEL) {
+        // Nested regions (a nav inside a header) are visited through the outer one
+        if region
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|a| HIRING_REGION_SEL.matches(&a))
+        {
+            continue;
+        }
+        for anchor in region.select(&LINK_SEL) {
+            let href = anchor.value().attr("href").unwrap_or("").trim();
+            let text = anchor.text().collect::<String>();
+            if CAREERS_PATH_RE.is_match(href) || CAREERS_TEXT_RE.is_match(&text) {
+                push_unique(&mut hiring.careers_links, href.to_string());
+            }
+        }
+        let mut text = String::new();
+        collect_text(region, &|_| false, &mut text);
+        hiring.hiring_mentions_count += HIRING_PHRASE_RE.find_iter(&text).count();
+    }
+    hiring.has_careers_page = !hiring.careers_links.is_empty();
+
+    for anchor in document.select(&LINK_SEL) {
+        let href = anchor.value().attr("href").unwrap_or("").trim();
+        let Some(domain) = absolute_url_domain(href) else { continue };
+        let lower = href.to_lowercase();
+        let is_job_board = JOB_BOARD_SIGNATURES
+            .iter()
+            .any(|(host, path)| host_matches(&domain, host) && lower.contains(path));
+        if is_job_board {
+            push_unique(&mut hiring.job_board_links, href.to_string());
+        }
+    }
+
+    hiring
+}
+
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct YouTubeEmbed {
+    pub url: String,
+    pub video_id: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct VideoEmbeds {
+    pub total_count: usize,
+    pub youtube: Vec<YouTubeEmbed>,
+    pub vimeo: Vec<String>,
+    pub wistia: Vec<String>,
+    pub facebook: Vec<String>,
+    /// Self-hosted <video> elements (their src, when one is given).
+    pub self_hosted: Vec<String>,
+    /// A video appears before the first <h2> (above-the-fold heuristic).
+    pub has_video_above_fold: bool,
+}
+
+fn embed_src<'a>(el: &ElementRef<'a>) -> Option<&'a str> {
+    EMBED_SRC_ATTRS
+        .iter()
+        .find_map(|attr| el.value().attr(attr))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Record one element if it is a video embed; returns whether it was.
+fn record_video(el: &ElementRef, videos: &mut VideoEmbeds) -> bool {
+    let value = el.value();
+    match value.name() {
+        "iframe" => {
+            let Some(src) = embed_src(el) else { return false };
+            let lower = src.to_lowercase();
+            let is_youtube = ["youtube.com/", "youtube-nocookie.com/", "youtu.be/"]
+                .iter()
+                .any(|host| lower.contains(host));
+            if is_youtube {
+                videos.youtube.push(YouTubeEmbed {
+                    url: src.to_string(),
+                    video_id: YOUTUBE_ID_RE.captures(src).map(|c| c[1].to_string()),
+                });
+            } else if lower.contains("vimeo.com/") {
+                videos.vimeo.push(src.to_string());
+            } else if lower.contains("wistia.net/") || lower.contains("wistia.com/") {
+                videos.wistia.push(src.to_string());
+            } else if lower.contains("facebook.com/plugins/video") {
+                videos.facebook.push(src.to_string());
+            } else {
+                return false;
+            }
+        }
+        "video" => {
+            let src = embed_src(el).map(str::to_string).or_else(|| {
+                el.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|c| c.value().name() == "source")
+                    .find_map(|c| embed_src(&c).map(str::to_string))
+            });
+            videos.self_hosted.push(src.unwrap_or_default());
+        }
+        _ => {
+            let class = value.attr("class").unwrap_or("");
+            if let Some(caps) = WISTIA_ASYNC_RE.captures(class) {
+                videos.wistia.push(format!("https://fast.wistia.net/embed/iframe/{}", &caps[1]));
+            } else if class.split_whitespace().any(|c| c == "fb-video") {
+                videos.facebook.push(value.attr("data-href").unwrap_or_default().to_string());
+            } else {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Find YouTube/Vimeo/Wistia/Facebook embeds and self-hosted <video>,
+/// including lazy-loaded iframes that carry their URL in data-src.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_video_embeds(html: &str, max_bytes: Option<usize>) -> PyResult<VideoEmbeds> {
+    let html = limit_input(html, max_bytes)?;
+    let mut videos = VideoEmbeds::default();
+    if html.is_empty() {
+        return Ok(videos);
+    }
+
+    let document = Html::parse_document(html);
+    let mut seen_h2 = false;
+    for el in document.root_element().descendants().filter_map(ElementRef::wrap) {
+        if el.value().name() == "h2" {
+            seen_h2 = true;
+        } else if record_video(&el, &mut videos) {
+            videos.total_count += 1;
+            videos.has_video_above_fold |= !seen_h2;
+        }
+    }
+
+    Ok(videos)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct ReviewWidgets {
+    pub vendors: Vec<String>,
+    /// AggregateRating in JSON-LD or microdata.
+    pub has_aggregate_rating: bool,
+    pub rating_value: Option<f64>,
+    pub rating_count: Option<u64>,
+    pub has_testimonials_section: bool,
+}
+
+/// A JSON-LD number that may be published as a string ("4.8").
+fn json_number(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().replace(',', "").parse().ok(),
+        _ => None,
+    }
+}
+
+fn element_value(el: &ElementRef) -> String {
+    el.value()
+        .attr("content")
+        .map(str::to_string)
+        .unwrap_or_else(|| el.text().collect::<String>())
+        .trim()
+        .replace(',', "")
+}
+
+/// Detect review/social-proof widgets, an AggregateRating (with value and
+/// count, to cross-check the Maps rating) and a testimonials section.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_review_widgets(html: &str, max_bytes: Option<usize>) -> PyResult<ReviewWidgets> {
+    let html = limit_input(html, max_bytes)?;
+    let mut reviews = ReviewWidgets::default();
+    if html.is_empty() {
+        return Ok(reviews);
+    }
+
+    let html_lower = html.to_lowercase();
+    reviews.vendors = REVIEW_WIDGET_SIGNATURES
+        .iter()
+        .filter(|(_, signatures)| signatures.iter().any(|sig| html_lower.contains(sig)))
+        .map(|(vendor, _)| vendor.to_string())
+        .collect();
+
+    let document = Html::parse_document(html);
+    let aggregate = json_ld_objects(&document).into_iter().find(|object| {
+        object.get("@type").and_then(|t| t.as_str()) == Some("AggregateRating")
+    });
+    if let Some(rating) = aggregate {
+        reviews.has_aggregate_rating = true;
+        reviews.rating_value = rating.get("ratingValue").and_then(json_number);
+        reviews.rating_count = rating
+            .get("reviewCount")
+            .or_else(|| rating.get("ratingCount"))
+            .and_then(json_number)
+            .map(|n| n as u64);
+    } else if let Some(value) = document.select(&RATING_VALUE_SEL).next() {
+        reviews.has_aggregate_rating = true;
+        reviews.rating_value = element_value(&value).parse().ok();
+        reviews.rating_count = document
+            .select(&RATING_COUNT_SEL)
+            .next()
+            .and_then(|count| element_value(&count).parse().ok());
+    }
+
+    reviews.has_testimonials_section = document
+        .select(&TESTIMONIAL_CONTAINER_SEL)
+        .next()
+        .is_some()
+        || document.select(&TESTIMONIAL_HEADING_SEL).any(|heading| {
+            let text = heading.text().collect::<String>().to_lowercase();
+            TESTIMONIAL_HEADINGS.iter().any(|h| text.contains(h))
+        });
+
+    Ok(reviews)
+}
+
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct PaymentProvider {
+    pub vendor: String,
+    /// "definite" for payment SDK scripts, "probable" for card-logo markup.
+    pub confidence: &'static str,
+}
+
+/// Detect payment providers from their checkout/widget scripts, falling back
+/// to card-logo alt text and class names.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_payment_providers(
+    html: &str,
+    max_bytes: Option<usize>,
+) -> PyResult<Vec<PaymentProvider>> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(payment_providers_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn payment_providers_document(document: &Html, html_lower: &str) -> Vec<PaymentProvider> {
+    let mut providers: Vec<PaymentProvider> = Vec::new();
+    for (vendor, signatures) in PAYMENT_SIGNATURES.iter() {
+        if signatures.iter().any(|sig| html_lower.contains(sig)) {
+            providers.push(PaymentProvider {
+                vendor: vendor.to_string(),
+                confidence: Confidence::Definite.as_str(),
+            });
+        }
+    }
+
+    let mut tokens: HashSet<String> = HashSet::new();
+    for el in document.select(&PAYMENT_LOGO_SEL) {
+        let value = el.value();
+        for attr in ["alt", "src", "class"] {
+            let text = value.attr(attr).unwrap_or("").to_lowercase();
+            tokens.extend(
+                text.split(|c: char| !c.is_ascii_alphanumeric())
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    for (vendor, markers) in PAYMENT_LOGO_TOKENS {
+        let already_found = providers.iter().any(|p| p.vendor == *vendor);
+        if !already_found && markers.iter().any(|m| tokens.contains(*m)) {
+            providers.push(PaymentProvider {
+                vendor: vendor.to_string(),
+                confidence: Confidence::Probable.as_str(),
+            });
+        }
+    }
+
+    providers
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct OnlineOrdering {
+    /// The venue takes orders through its own ordering system.
+    pub has_online_ordering: bool,
+    pub ordering_vendor: Option<String>,
+    pub delivery_platform_links: Vec<String>,
+    /// Only reachable through delivery aggregators (a different pitch).
+    pub aggregator_only: bool,
+}
+
+/// Detect first-party online ordering (Mr Yum, me&u, Square Online, …) and
+/// outbound links to delivery aggregators (Uber Eats, DoorDash, Menulog).
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_online_ordering(html: &str, max_bytes: Option<usize>) -> PyResult<OnlineOrdering> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(OnlineOrdering::default());
+    }
+    Ok(online_ordering_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn online_ordering_document(document: &Html, html_lower: &str) -> OnlineOrdering {
+    let mut ordering = OnlineOrdering::default();
+    ordering.ordering_vendor = ORDERING_SIGNATURES
+        .iter()
+        .find(|(_, signatures)| signatures.iter().any(|sig| html_lower.contains(sig)))
+        .map(|(vendor, _)| vendor.to_string());
+    ordering.has_online_ordering = ordering.ordering_vendor.is_some();
+
+    for anchor in document.select(&LINK_SEL) {
+        let href = anchor.value().attr("href").unwrap_or("").trim();
+        let Some(domain) = absolute_url_domain(href) else { continue };
+        if DELIVERY_PLATFORM_DOMAINS.iter().any(|d| host_matches(&domain, d)) {
+            push_unique(&mut ordering.delivery_platform_links, href.to_string());
+        }
+    }
+    ordering.aggregator_only =
+        !ordering.has_online_ordering && !ordering.delivery_platform_links.is_empty();
+
+    ordering
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct TrackingIds {
+    pub ga4_ids: Vec<String>,
+    pub ua_ids: Vec<String>,
+    pub gtm_ids: Vec<String>,
+    pub fb_pixel_ids: Vec<String>,
+    pub google_ads_conversion_ids: Vec<String>,
+}
+
+/// Extract GA4 / Universal Analytics / GTM / Facebook pixel / Google Ads IDs
+/// from gtag, analytics.js, GTM and fbq snippets (inline scripts included).
+#[pyfunction]
+pub fn extract_tracking_ids(html: &str) -> TrackingIds {
+    let mut ids = TrackingIds::default();
+    if html.is_empty() {
+        return ids;
+    }
+
+    unique_captures(&GA4_ID_RE, html, &mut ids.ga4_ids);
+    unique_captures(&UA_ID_RE, html, &mut ids.ua_ids);
+    unique_captures(&GTM_ID_RE, html, &mut ids.gtm_ids);
+    for re in FB_PIXEL_ID_RES.iter() {
+        unique_captures(re, html, &mut ids.fb_pixel_ids);
+    }
+    unique_captures(&GOOGLE_ADS_ID_RE, html, &mut ids.google_ads_conversion_ids);
+
+    ids
+}
+
+/// Append the text under `el` to `out`, skipping non-rendering elements and
+/// any element `skip` rejects. Text nodes are separated by single spaces.
+fn collect_text(el: ElementRef, skip: &dyn Fn(&ElementRef) -> bool, out: &mut String) {
+    for child in el.children() {
+        match child.value() {
+            Node::Text(text) => {
+                let trimmed = text.trim();
+                if !trimmed.is_empty() {
+                    if !out.is_empty() {
+                        out.push(' ');
+                    }
+                    out.push_str(trimmed);
+                }
+            }
+            Node::Element(element) => {
+                if NON_VISIBLE_TAGS.contains(&element.name()) {
+                    continue;
+                }
+                if let Some(child_el) = ElementRef::wrap(child) {
+                    if !skip(&child_el) {
+                        collect_text(child_el, skip, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Every JSON object in the page's JSON-LD blocks, nested ones (@graph etc.) included.
+pub(crate) fn json_ld_objects(document: &Html) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    fn collect(value: &serde_json::Value, out: &mut Vec<serde_json::Map<String, serde_json::Value>>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                out.push(map.clone());
+                map.values().for_each(|v| collect(v, out));
+            }
+            serde_json::Value::Array(items) => items.iter().for_each(|v| collect(v, out)),
+            _ => {}
+        }
+    }
+
+    let mut objects = Vec::new();
+    for script in document.select(&JSON_LD_SEL) {
+        let raw = script.text().collect::<String>();
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(raw.trim()) {
+            collect(&value, &mut objects);
+        }
+    }
+    objects
+}
+
+/// Rendered text of the whole document (scripts, styles and the head excluded).
+fn document_text(document: &Html) -> String {
+    let mut out = String::new();
+    collect_text(document.root_element(), &|_| false, &mut out);
+    out
+}
+
+/// Text of the main content area: <main>/[role=main] when present, otherwise
+/// the body minus nav/header/footer/aside chrome.
+fn main_content_text(document: &Html) -> String {
+    let mut out = String::new();
+    let skip_chrome = |el: &ElementRef| CHROME_TAGS.contains(&el.value().name());
+    if let Some(main) = document.select(&MAIN_SEL).next() {
+        collect_text(main, &skip_chrome, &mut out);
+    } else if let Some(body) = document.select(&BODY_SEL).next() {
+        collect_text(body, &skip_chrome, &mut out);
+    }
+    out
+}
+
+fn has_ancestor_tag(el: &ElementRef, tags: &[&str]) -> bool {
+    el.ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|a| tags.contains(&a.value().name()))
+}
+
+/// Visible text of an element, or its value attribute for inputs.
+fn element_label(el: &ElementRef) -> String {
+    let text = el.text().collect::<String>();
+    let text = text.trim();
+    if text.is_empty() {
+        el.value().attr("value").unwrap_or_default().trim().to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// A hand-rolled banner: a cookie/consent container holding an accept-style control.
+fn has_custom_cookie_banner(document: &Html) -> bool {
+    document.select(&COOKIE_CONTAINER_SEL).any(|container| {
+        container
+            .select(&INTERACTIVE_SEL)
+            .any(|control| ACCEPT_LABEL_RE.is_match(&element_label(&control)))
+    })
+}
+
+/// Detect the consent management platform behind a cookie banner.
+///
+/// Known vendors match on loader scripts / DOM containers. A hand-rolled
+/// banner (cookie container with an accept control) is reported as "custom";
+/// pages that only mention cookies in text return None.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_consent_platform(html: &str, max_bytes: Option<usize>) -> PyResult<Option<String>> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(None);
+    }
+    Ok(consent_platform_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn consent_platform_document(document: &Html, html_lower: &str) -> Option<String> {
+    if let Some((vendor, _)) = CONSENT_SIGNATURES
+        .iter()
+        .find(|(_, signatures)| signatures.iter().any(|sig| html_lower.contains(sig)))
+    {
+        return Some(vendor.to_string());
+    }
+
+    if !html_lower.contains("cookie") && !html_lower.contains("consent") {
+        return None;
+    }
+
+    has_custom_cookie_banner(document).then(|| "custom".to_string())
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct CaptchaInfo {
+    pub provider: Option<String>,
+    /// reCAPTCHA only: "v2", "v3" or "enterprise".
+    pub version: Option<String>,
+    pub inside_form: bool,
+    /// Script loaded but no form on the page for it to protect.
+    pub present_unused: bool,
+}
+
+fn recaptcha_version(html_lower: &str) -> &'static str {
+    if html_lower.contains("recaptcha/enterprise.js") {
+        "enterprise"
+    } else if RECAPTCHA_V3_RENDER_RE.is_match(html_lower) || html_lower.contains("grecaptcha.execute") {
+        "v3"
+    } else {
+        "v2"
+    }
+}
+
+/// Detect CAPTCHA providers (reCAPTCHA, hCaptcha, Turnstile, Friendly Captcha)
+/// and whether they protect a form.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_captcha(html: &str, max_bytes: Option<usize>) -> PyResult<CaptchaInfo> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(CaptchaInfo::default());
+    }
+    Ok(captcha_document(&Html::parse_document(html), &html.to_lowercase()))
+}
+
+fn captcha_document(document: &Html, html_lower: &str) -> CaptchaInfo {
+    let mut info = CaptchaInfo::default();
+    let signature = CAPTCHA_SIGNATURES.iter().find(|(_, scripts, class, _)| {
+        scripts.iter().any(|s| html_lower.contains(s)) || html_lower.contains(class)
+    });
+    let Some((provider, _, _, widget_sel)) = signature else {
+        return info;
+    };
+
+    info.provider = Some(provider.to_string());
+    let version = (*provider == "reCAPTCHA").then(|| recaptcha_version(html_lower));
+    info.version = version.map(str::to_string);
+
+    let has_form = document.select(&FORM_SEL).next().is_some();
+
+    // v3 / enterprise are invisible: there is no widget, so any form on the
+    // page counts as protected.
+    info.inside_form = document.select(widget_sel).next().is_some()
+        || (has_form && matches!(version, Some("v3" | "enterprise")));
+    info.present_unused = !has_form;
+
+    info
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormKind {
+    Contact,
+    Newsletter,
+    Search,
+    Other,
+}
+
+/// Classify a <form> by its inputs: textareas mean contact, a lone email
+/// field means newsletter, search inputs / role=search mean search.
+fn classify_form(form: &ElementRef) -> FormKind {
+    let attrs = format!(
+        "{} {} {} {}",
+        form.value().id().unwrap_or_default(),
+        form.value().attr("class").unwrap_or_default(),
+        form.value().attr("action").unwrap_or_default(),
+        form.value().attr("role").unwrap_or_default(),
+    )
+    .to_lowercase();
+
+    let field_count = form.select(&FIELD_SEL).count();
+    let has_textarea = form.select(&TEXTAREA_SEL).next().is_some();
+    let has_email = form.select(&EMAIL_INPUT_SEL).next().is_some();
+
+    let is_search = form.select(&SEARCH_INPUT_SEL).next().is_some() || attrs.contains("search");
+    if is_search && !has_textarea && field_count <= 2 {
+        return FormKind::Search;
+    }
+    if has_textarea {
+        return FormKind::Contact;
+    }
+    if has_email {
+        if NEWSLETTER_MARKERS.iter().any(|m| attrs.contains(m)) || field_count <= 2 {
+            return FormKind::Newsletter;
+        }
+        return FormKind::Contact;
+    }
+    FormKind::Other
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct FormsInfo {
+    pub has_contact_form: bool,
+    /// Plausible contact forms among the page's <form> elements.
+    pub form_count: usize,
+    pub form_providers: Vec<String>,
+    pub newsletter_form: bool,
+}
+
+/// Detect contact forms (native and embedded third-party) and newsletter signups.
+///
+/// Search boxes and newsletter signups never count towards has_contact_form.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_forms(html: &str, max_bytes: Option<usize>) -> PyResult<FormsInfo> {
+    let html = limit_input(html, max_bytes)?;
+    let mut info = FormsInfo::default();
+    if html.is_empty() {
+        return Ok(info);
+    }
+
+    let html_lower = html.to_lowercase();
+    let mut has_embedded_form = false;
+    for (provider, markers, embedded) in FORM_PROVIDER_SIGNATURES.iter() {
+        if markers.iter().any(|m| html_lower.contains(m)) {
+            info.form_providers.push(provider.to_string());
+            has_embedded_form |= *embedded;
+        }
+    }
+
+    let document = Html::parse_document(html);
+    for form in document.select(&FORM_SEL) {
+        match classify_form(&form) {
+            FormKind::Contact => info.form_count += 1,
+            FormKind::Newsletter => info.newsletter_form = true,
+            FormKind::Search | FormKind::Other => {}
+        }
+    }
+
+    info.has_contact_form = info.form_count > 0 || has_embedded_form;
+    Ok(info)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct MixedContent {
+    /// False when the page is not known to be served over HTTPS.
+    pub checked: bool,
+    pub blockable_count: usize,
+    pub upgradeable_count: usize,
+    pub blockable_examples: Vec<String>,
+    pub upgradeable_examples: Vec<String>,
+}
+
+fn push_example(examples: &mut Vec<String>, url: &str) {
+    if examples.len() < MIXED_CONTENT_EXAMPLES && !examples.iter().any(|e| e == url) {
+        examples.push(url.to_string());
+    }
+}
+
+/// Find http:// resources on an HTTPS page.
+///
+/// Scripts, iframes and stylesheets are "blockable" (browsers refuse them);
+/// images, media and CSS url() values are "upgradeable" (shown with a
+/// warning). Protocol-relative // URLs inherit https and are never flagged.
+#[pyfunction]
+#[pyo3(signature = (html, page_url=None, assume_https=false, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_mixed_content(
+    html: &str,
+    page_url: Option<&str>,
+    assume_https: bool,
+    max_bytes: Option<usize>,
+) -> PyResult<MixedContent> {
+    let html = limit_input(html, max_bytes)?;
+    let mut result = MixedContent::default();
+
+    let is_https = assume_https
+        || page_url.is_some_and(|u| u.trim().to_lowercase().starts_with("https://"));
+    if html.is_empty() || !is_https {
+        return Ok(result);
+    }
+    result.checked = true;
+
+    let document = Html::parse_document(html);
+    let is_insecure = |url: &str| url.trim().to_lowercase().starts_with("http://");
+
+    for el in document.select(&MIXED_BLOCKABLE_SEL) {
+        let v = el.value();
+        if let Some(url) = v.attr("src").or(v.attr("href")).or(v.attr("data")).filter(|u| is_insecure(u)) {
+            result.blockable_count += 1;
+            push_example(&mut result.blockable_examples, url.trim());
+        }
+    }
+
+    for el in document.select(&MIXED_UPGRADEABLE_SEL) {
+        let v = el.value();
+        if let Some(url) = v.attr("src").or(v.attr("href")).filter(|u| is_insecure(u)) {
+            result.upgradeable_count += 1;
+            push_example(&mut result.upgradeable_examples, url.trim());
+        }
+    }
+
+    let inline_css = document
+        .select(&STYLE_SEL)
+        .map(|el| el.text().collect::<String>())
+        .chain(document.select(&STYLE_ATTR_SEL).filter_map(|el| el.value().attr("style").map(str::to_string)));
+    for css in inline_css {
+        for caps in CSS_HTTP_URL_RE.captures_iter(&css) {
+            result.upgradeable_count += 1;
+            push_example(&mut result.upgradeable_examples, &caps[1]);
+        }
+    }
+
+    Ok(result)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct CopyrightYear {
+    /// Latest copyright year found (range end for "2010–2024").
+    pub year: Option<i32>,
+    pub years_stale: Option<i32>,
+    /// "footer" or "document" (fallback when the page has no footer region).
+    pub source: Option<&'static str>,
+}
+
+fn latest_copyright_year(text: &str, current_year: i32) -> Option<i32> {
+    COPYRIGHT_RE
+        .captures_iter(text)
+        .filter_map(|caps| caps.get(2).or(caps.get(1)).and_then(|m| m.as_str().parse::<i32>().ok()))
+        // Future years are typos or countdown widgets, not copyright notices
+        .filter(|y| *y <= current_year + 1)
+        .max()
+}
+
+/// Find the footer copyright year and how many years out of date it is.
+///
+/// Only footer-ish regions (footer element, class/id containing footer or
+/// copyright) are scanned, so blog dates elsewhere are ignored; the whole
+/// document is used only when the page has no such region.
+#[pyfunction]
+#[pyo3(signature = (html, current_year, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_copyright_year(
+    html: &str,
+    current_year: i32,
+    max_bytes: Option<usize>,
+) -> PyResult<CopyrightYear> {
+    let html = limit_input(html, max_bytes)?;
+    let mut result = CopyrightYear::default();
+    if html.is_empty() {
+        return Ok(result);
+    }
+
+    let document = Html::parse_document(html);
+    let regions: Vec<String> = document
+        .select(&FOOTER_REGION_SEL)
+        .map(|el| el.text().collect::<Vec<_>>().join(" "))
+        .collect();
+
+    let (year, source) = if regions.is_empty() {
+        let text = document.root_element().text().collect::<Vec<_>>().join(" ");
+        (latest_copyright_year(&text, current_year), "document")
+    } else {
+        let year = regions.iter().filter_map(|t| latest_copyright_year(t, current_year)).max();
+        (year, "footer")
+    };
+
+    if let Some(y) = year {
+        result.year = Some(y);
+        result.years_stale = Some((current_year - y).max(0));
+        result.source = Some(source);
+    }
+    Ok(result)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct PageLanguage {
+    /// Primary language subtag declared by the page (html lang, og:locale,
+    /// content-language meta — first one present).
+    pub declared_lang: Option<String>,
+    /// Best guess: the declaration when consistent, otherwise content-based.
+    pub detected_lang: Option<String>,
+    pub confidence: f64,
+    pub is_multilingual: bool,
+}
+
+/// "en-AU" / "en_au" / " EN " → "en"
+fn primary_language_subtag(tag: &str) -> Option<String> {
+    let primary = tag.trim().split(['-', '_']).next()?.to_lowercase();
+    (primary.len() >= 2 && primary.len() <= 3 && primary.chars().all(|c| c.is_ascii_alphabetic()))
+        .then_some(primary)
+}
+
+/// Detect the page language from declarations, falling back to trigram
+/// detection over the visible text when they are absent or disagree.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_page_language(html: &str, max_bytes: Option<usize>) -> PyResult<PageLanguage> {
+    let html = limit_input(html, max_bytes)?;
+    let mut result = PageLanguage::default();
+    if html.is_empty() {
+        return Ok(result);
+    }
+
+    let document = Html::parse_document(html);
+    let attr_of = |sel: &Selector, attr: &str| {
+        document.select(sel).next().and_then(|el| el.value().attr(attr).map(str::to_string))
+    };
+
+    let declared: Vec<String> = [
+        document.root_element().value().attr("lang").map(str::to_string),
+        attr_of(&OG_LOCALE_SEL, "content"),
+        attr_of(&CONTENT_LANGUAGE_SEL, "content"),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|tag| primary_language_subtag(&tag))
+    .collect();
+
+    result.declared_lang = declared.first().cloned();
+    let consistent = !declared.is_empty() && declared.iter().all(|l| *l == declared[0]);
+
+    if consistent {
+        result.detected_lang = result.declared_lang.clone();
+        result.confidence = 0.9;
+    } else if let Some((lang, confidence)) = crate::text::detect_language(&document_text(&document)) {
+        result.detected_lang = Some(lang.to_string());
+        result.confidence = (confidence * 100.0).round() / 100.0;
+    }
+
+    let mut alternates: HashSet<String> = HashSet::new();
+    for el in document.select(&HREFLANG_SEL) {
+        if let Some(tag) = el.value().attr("hreflang").filter(|t| !t.eq_ignore_ascii_case("x-default")) {
+            alternates.insert(tag.to_lowercase());
+        }
+    }
+    result.is_multilingual = alternates.len() >= 2;
+
+    Ok(result)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct SeoAudit {
+    pub has_title: bool,
+    pub title_length: usize,
+    pub title_is_default: bool,
+    pub has_meta_description: bool,
+    pub meta_description_length: usize,
+    pub meta_description_is_default: bool,
+    pub h1_count: usize,
+    pub images_total: usize,
+    pub images_missing_alt: usize,
+    pub has_canonical: bool,
+    pub is_noindex: bool,
+    pub word_count: usize,
+    pub issues: Vec<String>,
+}
+
+fn is_default_text(text: &str, defaults: &[&str]) -> bool {
+    let normalized = text.trim().trim_end_matches(['.', '!']).to_lowercase();
+    defaults.contains(&normalized.as_str())
+}
+
+fn seo_audit_document(document: &Html) -> SeoAudit {
+    let mut audit = SeoAudit::default();
+
+    let title = document
+        .select(&HEAD_TITLE_SEL)
+        .next()
+        .map(|el| el.text().collect::<String>().trim().to_string())
+        .filter(|t| !t.is_empty());
+    if let Some(ref t) = title {
+        audit.has_title = true;
+        audit.title_length = t.chars().count();
+        audit.title_is_default = is_default_text(t, DEFAULT_TITLES);
+    }
+
+    let description = document
+        .select(&META_DESCRIPTION_SEL)
+        .next()
+        .and_then(|el| el.value().attr("content"))
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if let Some(ref d) = description {
+        audit.has_meta_description = true;
+        audit.meta_description_length = d.chars().count();
+        audit.meta_description_is_default = is_default_text(d, DEFAULT_DESCRIPTIONS);
+    }
+
+    audit.h1_count = document
+        .select(&H1_SEL)
+        .filter(|el| !el.text().collect::<String>().trim().is_empty())
+        .count();
+
+    for img in document.select(&IMG_SEL) {
+        audit.images_total += 1;
+        if img.value().attr("alt").is_none_or(|a| a.trim().is_empty()) {
+            audit.images_missing_alt += 1;
+        }
+    }
+
+    audit.has_canonical = document.select(&CANONICAL_SEL).next().is_some();
+    audit.is_noindex = document.select(&META_ROBOTS_SEL).any(|el| {
+        el.value().attr("content").is_some_and(|c| c.to_lowercase().contains("noindex"))
+    });
+    audit.word_count = main_content_text(document).split_whitespace().count();
+
+    let issues = &mut audit.issues;
+    match audit.title_length {
+        0 => issues.push("Missing <title> tag".to_string()),
+        n if n < TITLE_MIN_CHARS => issues.push(format!("Title is too short ({n} characters)")),
+        n if n > TITLE_MAX_CHARS => issues.push(format!("Title is too long ({n} characters)")),
+        _ => {}
+    }
+    if audit.title_is_default {
+        issues.push("Title looks like a CMS default".to_string());
+    }
+    match audit.meta_description_length {
+        0 => issues.push("Missing meta description".to_string()),
+        n if n < DESCRIPTION_MIN_CHARS => issues.push(format!("Meta description is too short ({n} characters)")),
+        n if n > DESCRIPTION_MAX_CHARS => issues.push(format!("Meta description is too long ({n} characters)")),
+        _ => {}
+    }
+    if audit.meta_description_is_default {
+        issues.push("Meta description looks like a CMS default".to_string());
+    }
+    match audit.h1_count {
+        0 => issues.push("No H1 heading".to_string()),
+        1 => {}
+        n => issues.push(format!("Multiple H1 headings ({n})")),
+    }
+    if audit.images_missing_alt > 0 {
+        issues.push(format!(
+            "{} of {} images missing alt text",
+            audit.images_missing_alt, audit.images_total
+        ));
+    }
+    if !audit.has_canonical {
+        issues.push("No canonical link".to_string());
+    }
+    if audit.is_noindex {
+        issues.push("Page is set to noindex".to_string());
+    }
+    if audit.word_count < THIN_CONTENT_WORDS {
+        issues.push(format!("Thin content ({} words)", audit.word_count));
+    }
+
+    audit
+}
+
+/// Basic on-page SEO findings plus a human-readable `issues` list.
+///
+/// analyze_tech_stack(html, include_seo=True) returns the same audit under
+/// "seo" without parsing the document twice.
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct AccessibilityAudit {
+    pub images_missing_alt: usize,
+    pub unlabelled_fields: usize,
+    pub missing_lang: bool,
+    /// Links with no text, aria-label/title or alt-text image inside.
+    pub unnamed_links: usize,
+    pub legacy_tags: usize,
+    pub issues: Vec<String>,
+}
+
+fn has_aria_name(el: &ElementRef) -> bool {
+    ["aria-label", "aria-labelledby", "title"]
+        .iter()
+        .any(|attr| el.value().attr(attr).is_some_and(|v| !v.trim().is_empty()))
+}
+
+fn accessibility_audit_document(document: &Html) -> AccessibilityAudit {
+    // alt="" is valid for decorative images; only a missing attribute is flagged
+    let images_missing_alt = document
+        .select(&IMG_SEL)
+        .filter(|img| {
+            let value = img.value();
+            value.attr("alt").is_none()
+                && value.attr("aria-hidden") != Some("true")
+                && value.attr("role") != Some("presentation")
+        })
+        .count();
+
+    let labelled_ids: HashSet<&str> = document
+        .select(&LABEL_FOR_SEL)
+        .filter_map(|label| label.value().attr("for"))
+        .collect();
+    let unlabelled_fields = document
+        .select(&LABELLABLE_SEL)
+        .filter(|field| {
+            !has_aria_name(field)
+                && !field.value().attr("id").is_some_and(|id| labelled_ids.contains(id))
+                && !has_ancestor_tag(field, &["label"])
+        })
+        .count();
+
+    let missing_lang = document
+        .select(&HTML_SEL)
+        .next()
+        .and_then(|el| el.value().attr("lang"))
+        .is_none_or(|lang| lang.trim().is_empty());
+
+    let unnamed_links = document
+        .select(&LINK_SEL)
+        .filter(|link| {
+            link.text().all(|t| t.trim().is_empty())
+                && !has_aria_name(link)
+                && !link
+                    .select(&IMG_SEL)
+                    .any(|img| img.value().attr("alt").is_some_and(|a| !a.trim().is_empty()))
+        })
+        .count();
+
+    let legacy_tags = document.select(&LEGACY_PRESENTATION_SEL).count();
+
+    let mut audit = AccessibilityAudit {
+        images_missing_alt,
+        unlabelled_fields,
+        missing_lang,
+        unnamed_links,
+        legacy_tags,
+        issues: Vec::new(),
+    };
+
+    let issues = &mut audit.issues;
+    if audit.images_missing_alt > 0 {
+        issues.push(format!("{} images without alt text", audit.images_missing_alt));
+    }
+    if audit.unlabelled_fields > 0 {
+        issues.push(format!("{} form fields without labels", audit.unlabelled_fields));
+    }
+    if audit.missing_lang {
+        issues.push("Page language not declared".to_string());
+    }
+    if audit.unnamed_links > 0 {
+        issues.push(format!("{} icon-only links without an accessible name", audit.unnamed_links));
+    }
+    if audit.legacy_tags > 0 {
+        issues.push(format!("{} legacy presentational tags (font/center/marquee)", audit.legacy_tags));
+    }
+
+    audit
+}
+
+/// Basic WCAG-style checks: alt text, field labels, page language, link
+/// names and legacy presentational tags.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn accessibility_audit(html: &str, max_bytes: Option<usize>) -> PyResult<AccessibilityAudit> {
+    let html = limit_input(html, max_bytes)?;
+    Ok(accessibility_audit_document(&Html::parse_document(html)))
+}
+
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct BusinessIdMatch {
+    /// Digits (and country/letter prefix) only, whitespace removed.
+    pub number: String,
+    /// The label it was printed with ("ABN", "Company No."), if any.
+    pub label: Option<String>,
+    pub context: String,
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct BusinessIds {
+    pub abn: Vec<BusinessIdMatch>,
+    pub acn: Vec<BusinessIdMatch>,
+    pub nzbn: Vec<BusinessIdMatch>,
+    pub uk_company: Vec<BusinessIdMatch>,
+    pub vat: Vec<BusinessIdMatch>,
+}
+
+fn digits_of(number: &str) -> Vec<u32> {
+    number.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+/// ABN: subtract 1 from the first digit, weighted sum divisible by 89.
+fn is_valid_abn(number: &str) -> bool {
+    let mut digits = digits_of(number);
+    if digits.len() != 11 || digits[0] == 0 {
+        return false;
+    }
+    digits[0] -= 1;
+    const WEIGHTS: [u32; 11] = [10, 1, 3, 5, 7, 9, 11, 13, 15, 17, 19];
+    digits.iter().zip(WEIGHTS).map(|(d, w)| d * w).sum::<u32>().is_multiple_of(89)
+}
+
+/// ACN: weights 8..1 over the first eight digits, complement of mod 10 is the check digit.
+fn is_valid_acn(number: &str) -> bool {
+    let digits = digits_of(number);
+    if digits.len() != 9 {
+        return false;
+    }
+    let sum: u32 = digits[..8].iter().zip((1..=8).rev()).map(|(d, w)| d * w).sum();
+    (10 - sum % 10) % 10 == digits[8]
+}
+
+/// NZBN: a GS1 GTIN-13 check digit.
+fn is_valid_nzbn(number: &str) -> bool {
+    let digits = digits_of(number);
+    if digits.len() != 13 {
+        return false;
+    }
+    let sum: u32 = digits[..12]
+        .iter()
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { *d } else { d * 3 })
+        .sum();
+    (10 - sum % 10) % 10 == digits[12]
+}
+
+/// UK VAT: mod-97 over the first nine digits (old and post-2010 schemes).
+/// Other EU numbers have per-country schemes and are accepted as labelled.
+fn is_valid_vat(number: &str) -> bool {
+    let upper = number.to_uppercase();
+    let is_uk = upper.starts_with("GB") || upper.chars().all(|c| c.is_ascii_digit());
+    if !is_uk {
+        return true;
+    }
+    let digits = digits_of(&upper);
+    if digits.len() != 9 && digits.len() != 12 {
+        return false;
+    }
+    let weighted: u32 = digits[..7].iter().zip((2..=8).rev()).map(|(d, w)| d * w).sum();
+    let check = digits[7] * 10 + digits[8];
+    (weighted + check).is_multiple_of(97) || (weighted + check + 55).is_multiple_of(97)
+}
+
+/// Up to `ID_CONTEXT_CHARS` characters either side of a match.
+fn context_around(text: &str, start: usize, end: usize) -> String {
+    let before_start = text[..start]
+        .char_indices()
+        .rev()
+        .nth(ID_CONTEXT_CHARS - 1)
+        .map_or(0, |(i, _)| i);
+    let after_end = text[end..]
+        .char_indices()
+        .nth(ID_CONTEXT_CHARS)
+        .map_or(text.len(), |(i, _)| end + i);
+    text[before_start..after_end].split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn find_business_ids(
+    text: &str,
+    re: &Regex,
+    is_valid: fn(&str) -> bool,
+    accept_unlabelled: fn(&str) -> bool,
+) -> Vec<BusinessIdMatch> {
+    let mut found: Vec<BusinessIdMatch> = Vec::new();
+    for caps in re.captures_iter(text) {
+        let raw = &caps["num"];
+        let label = caps.name("label").map(|m| m.as_str().trim().to_string());
+        if label.is_none() && !accept_unlabelled(raw) {
+            continue;
+        }
+        let number: String = raw
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if !is_valid(&number) || found.iter().any(|f| f.number == number) {
+            continue;
+        }
+        let whole = caps.get(0).unwrap();
+        found.push(BusinessIdMatch {
+            number,
+            label,
+            context: context_around(text, whole.start(), whole.end()),
+        });
+    }
+    // Labelled matches are the stronger evidence
+    found.sort_by_key(|m| m.label.is_none());
+    found
+}
+
+fn business_ids_in_text(text: &str, region: &str) -> BusinessIds {
+    let region = region.trim().to_uppercase();
+    let known = ["AU", "NZ", "UK", "GB"].contains(&region.as_str());
+    let wants = |codes: &[&str]| !known || codes.contains(&region.as_str());
+    let never = |_: &str| false;
+    let mut ids = BusinessIds::default();
+
+    if wants(&["AU"]) {
+        // Printed "51 824 753 556" grouping
+        let grouped = |raw: &str| raw.split_whitespace().count() == 4;
+        ids.abn = find_business_ids(text, &ABN_RE, is_valid_abn, grouped);
+        ids.acn = find_business_ids(text, &ACN_RE, is_valid_acn, never);
+    }
+    if wants(&["NZ"]) {
+        ids.nzbn = find_business_ids(text, &NZBN_RE, is_valid_nzbn, never);
+    }
+    if wants(&["UK", "GB"]) {
+        ids.uk_company = find_business_ids(text, &UK_COMPANY_RE, |_| true, never);
+        ids.vat = find_business_ids(text, &VAT_RE, is_valid_vat, never);
+    }
+    ids
+}
+
+/// The most credible checksum-valid ABN on the page.
+pub(crate) fn best_abn(document: &Html) -> Option<String> {
+    business_ids_in_text(&document_text(document), "AU")
+        .abn
+        .into_iter()
+        .next()
+        .map(|m| m.number)
+}
+
+/// Find business registration numbers: ABN, ACN (checksums validated), NZBN,
+/// UK company numbers and VAT numbers, each with its label and context.
+///
+/// `region` ("AU", "NZ", "UK") limits the search; anything else looks for all.
+#[pyfunction]
+#[pyo3(signature = (html, region, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn extract_business_ids(
+    html: &str,
+    region: &str,
+    max_bytes: Option<usize>,
+) -> PyResult<BusinessIds> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(BusinessIds::default());
+    }
+    Ok(business_ids_in_text(&document_text(&Html::parse_document(html)), region))
+}
+
+/// An address candidate and how many of street/locality/state/postcode it has.
+struct AddressCandidate {
+    address: String,
+    completeness: usize,
+}
+
+fn format_address(
+    region_us: bool,
+    street: &str,
+    locality: &str,
+    state: &str,
+    postcode: &str,
+) -> String {
+    let tail = [state, postcode]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut parts: Vec<&str> = vec![street];
+    if !locality.is_empty() {
+        parts.push(locality);
+    }
+    if region_us {
+        // "123 Main St, Springfield, IL 62701"
+        if !tail.is_empty() {
+            parts.push(&tail);
+        }
+        parts.join(", ")
+    } else {
+        // "12 Smith St, Brisbane QLD 4000"
+        let head = parts.join(", ");
+        if tail.is_empty() { head } else { format!("{head} {tail}") }
+    }
+}
+
+fn json_ld_addresses(document: &Html, region_us: bool) -> Vec<AddressCandidate> {
+    let field = |object: &serde_json::Map<String, serde_json::Value>, key: &str| {
+        let value = object.get(key).and_then(|v| v.as_str()).unwrap_or("");
+        value.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+
+    json_ld_objects(document)
+        .iter()
+        .filter(|object| object.contains_key("streetAddress"))
+        .filter_map(|object| {
+            let street = field(object, "streetAddress");
+            if street.is_empty() {
+                return None;
+            }
+            let locality = field(object, "addressLocality");
+            let mut state = field(object, "addressRegion");
+            if let Some((_, abbr)) =
+                AU_STATE_NAMES.iter().find(|(name, _)| state.eq_ignore_ascii_case(name))
+            {
+                state = abbr.to_string();
+            }
+            let postcode = field(object, "postalCode");
+            let completeness =
+                1 + [&locality, &state, &postcode].iter().filter(|s| !s.is_empty()).count();
+            Some(AddressCandidate {
+                address: format_address(region_us, &street, &locality, &state, &postcode),
+                completeness,
+            })
+        })
+        .collect()
+}
+
+fn text_addresses(text: &str, re: &Regex, region_us: bool) -> Vec<AddressCandidate> {
+    re.captures_iter(text)
+        .filter_map(|caps| {
+            let part = |name: &str| caps.name(name).map_or("", |m| m.as_str().trim());
+            let (state, postcode) = (part("state"), part("postcode"));
+            // A street name in running text is not an address without these
+            if state.is_empty() && postcode.is_empty() {
+                return None;
+            }
+            let street = part("street").split_whitespace().collect::<Vec<_>>().join(" ");
+            let locality = part("locality");
+            let completeness =
+                1 + [locality, state, postcode].iter().filter(|s| !s.is_empty()).count();
+            Some(AddressCandidate {
+                address: format_address(region_us, &street, locality, state, postcode),
+                completeness,
+            })
+        })
+        .collect()
+}
+
+/// Find postal addresses in JSON-LD PostalAddress nodes and footer/contact text.
+///
+/// `region` is "AU" or "US" (anything else tries both). Candidates must carry
+/// a state or postcode; results are deduplicated and most complete first,
+/// formatted as "street, locality STATE postcode".
+#[pyfunction]
+#[pyo3(signature = (html, region, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn extract_addresses(
+    html: &str,
+    region: &str,
+    max_bytes: Option<usize>,
+) -> PyResult<Vec<String>> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(document_addresses(&Html::parse_document(html), region))
+}
+
+fn document_addresses(document: &Html, region: &str) -> Vec<String> {
+    let region = region.trim().to_uppercase();
+    let patterns: Vec<(&Regex, bool)> = match region.as_str() {
+        "AU" => vec![(&*AU_ADDRESS_RE, false)],
+        "US" => vec![(&*US_ADDRESS_RE, true)],
+        _ => vec![(&*AU_ADDRESS_RE, false), (&*US_ADDRESS_RE, true)],
+    };
+
+    let mut candidates = json_ld_addresses(document, region == "US");
+    for el in document.select(&ADDRESS_REGION_SEL) {
+        let mut text = String::new();
+        collect_text(el, &|_| false, &mut text);
+        for (re, region_us) in &patterns {
+            candidates.extend(text_addresses(&text, re, *region_us));
+        }
+    }
+
+    // Stable sort keeps JSON-LD ahead of text at equal completeness
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.completeness));
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|c| {
+            let key: String =
+                c.address.to_lowercase().chars().filter(|ch| ch.is_alphanumeric()).collect();
+            seen.insert(key)
+        })
+        .map(|c| c.address)
+        .collect()
+}
+
+type DayHours = Vec<(String, String)>;
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct OpeningHours {
+    /// Weekday ("monday") → (open, close) pairs in 24-hour "HH:MM".
+    /// A day with an empty list is closed; days never mentioned are absent.
+    pub hours: HashMap<String, DayHours>,
+    pub by_appointment: Vec<String>,
+    pub is_24_7: bool,
+    /// "json-ld" or "text".
+    pub source: Option<&'static str>,
+    /// "high" (structured data), "medium" (consistent text, or structured
+    /// data contradicted by text) or "low" (conflicting text matches).
+    pub confidence: Option<&'static str>,
+    pub raw_matches: Vec<String>,
+}
+
+/// Weekday index from "Monday", "Mo", "Tues", "https://schema.org/Friday".
+fn weekday_index(name: &str) -> Option<usize> {
+    let name = name.rsplit('/').next().unwrap_or(name).trim_end_matches('.').to_lowercase();
+    if name.len() < 2 {
+        return None;
+    }
+    WEEKDAYS.iter().position(|day| day.starts_with(&name))
+}
+
+/// Days from `start` to `end` inclusive, wrapping past Sunday ("Fri-Mon").
+fn weekday_span(start: usize, end: usize) -> Vec<usize> {
+    let len = (end + 7 - start) % 7 + 1;
+    (0..len).map(|i| (start + i) % 7).collect()
+}
+
+fn format_clock(hour: u32, minute: u32, is_close: bool) -> String {
+    if is_close && hour == 0 && minute == 0 {
+        "24:00".to_string()
+    } else {
+        format!("{hour:02}:{minute:02}")
+    }
+}
+
+/// "09:00:00" / "9:00" → "09:00".
+fn normalize_clock(raw: &str, is_close: bool) -> Option<String> {
+    let mut parts = raw.trim().split(':');
+    let hour: u32 = parts.next()?.trim().parse().ok()?;
+    let minute: u32 = parts.next().unwrap_or("0").trim().parse().ok()?;
+    (hour <= 24 && minute < 60).then(|| format_clock(hour % 24, minute, is_close))
+}
+
+fn to_24_hour(hour: u32, meridiem: Option<char>) -> u32 {
+    match meridiem {
+        Some('a') if hour == 12 => 0,
+        Some('p') if hour < 12 => hour + 12,
+        _ => hour,
+    }
+}
+
+/// One "7am–5pm" / "7:00 - 17:00" range, resolving missing am/pm from context.
+fn parse_time_range(caps: &regex::Captures) -> Option<(String, String)> {
+    let number = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
+    let meridiem = |i: usize| caps.get(i).and_then(|m| m.as_str().to_lowercase().chars().next());
+    let (open_h, open_m, open_mer) = (number(1)?, number(2).unwrap_or(0), meridiem(3));
+    let (close_h, close_m, close_mer) = (number(4)?, number(5).unwrap_or(0), meridiem(6));
+
+    // A bare "1-2" is more likely a quantity than a time
+    let looks_like_time = open_mer.is_some()
+        || close_mer.is_some()
+        || caps.get(2).is_some()
+        || caps.get(5).is_some()
+        || open_h > 12
+        || close_h > 12;
+    if !looks_like_time || open_h > 24 || close_h > 24 || open_m >= 60 || close_m >= 60 {
+        return None;
+    }
+
+    let open_mer = open_mer.or(match close_mer {
+        // "1-5pm" is afternoon, "7-5pm" is morning-to-afternoon
+        Some('p') if open_h != 12 && open_h <= close_h => Some('p'),
+        Some('p') if open_h == 12 => Some('p'),
+        Some(_) => Some('a'),
+        None => None,
+    });
+    let close_mer = close_mer.or(match open_mer {
+        Some('p') => Some('p'),
+        Some('a') if close_h <= open_h && close_h < 12 => Some('p'),
+        _ => None,
+    });
+
+    let open = to_24_hour(open_h, open_mer);
+    let mut close = to_24_hour(close_h, close_mer);
+    // "7:00 - 5:00" with no am/pm anywhere
+    if open_mer.is_none() && close_mer.is_none() && close <= open && close < 12 {
+        close += 12;
+    }
+
+    Some((format_clock(open % 24, open_m, false), format_clock(close % 24, close_m, true)))
+}
+
+/// Days named by a "Mon–Fri" / "Sat & Sun" / "7 days" spec.
+fn parse_day_spec(spec: &str) -> Vec<usize> {
+    let tokens: Vec<_> = DAY_RE
+        .find_iter(spec)
+        .filter_map(|m| weekday_index(m.as_str()).map(|day| (day, m.start(), m.end())))
+        .collect();
+    if tokens.is_empty() {
+        return (0..7).collect();
+    }
+
+    let mut days: Vec<usize> = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let (day, _, end) = tokens[i];
+        match tokens.get(i + 1) {
+            Some(&(next, next_start, _)) if DAY_RANGE_SEP_RE.is_match(&spec[end..next_start]) => {
+                days.extend(weekday_span(day, next));
+                i += 2;
+            }
+            _ => {
+                days.push(day);
+                i += 1;
+            }
+        }
+    }
+    days.dedup();
+    days
+}
+
+/// schema.org `openingHours` strings: "Mo-Fr 09:00-17:00", "Sa,Su 10:00-14:00".
+fn parse_schema_hours_string(spec: &str, hours: &mut HashMap<usize, DayHours>) {
+    let mut parts = spec.split_whitespace();
+    let (Some(day_part), Some(time_part)) = (parts.next(), parts.next()) else {
+        return;
+    };
+    let Some((open, close)) = time_part.split_once('-') else {
+        return;
+    };
+    let (Some(open), Some(close)) = (normalize_clock(open, false), normalize_clock(close, true))
+    else {
+        return;
+    };
+
+    for group in day_part.split(',') {
+        let days = match group.split_once('-') {
+            Some((start, end)) => match (weekday_index(start), weekday_index(end)) {
+                (Some(s), Some(e)) => weekday_span(s, e),
+                _ => continue,
+            },
+            None => weekday_index(group).into_iter().collect(),
+        };
+        for day in days {
+            hours.entry(day).or_default().push((open.clone(), close.clone()));
+        }
+    }
+}
+
+fn json_ld_opening_hours(document: &Html) -> HashMap<usize, DayHours> {
+    let mut hours: HashMap<usize, DayHours> = HashMap::new();
+
+    for object in json_ld_objects(document) {
+        if let Some(spec) = object.get("openingHoursSpecification") {
+            let entries = match spec {
+                serde_json::Value::Array(items) => items.iter().collect(),
+                other => vec![other],
+            };
+            for entry in entries {
+                let days: Vec<usize> = match entry.get("dayOfWeek") {
+                    Some(serde_json::Value::String(day)) => weekday_index(day).into_iter().collect(),
+                    Some(serde_json::Value::Array(list)) => {
+                        list.iter().filter_map(|d| d.as_str().and_then(weekday_index)).collect()
+                    }
+                    _ => continue,
+                };
+                let clock = |key: &str, is_close: bool| {
+                    let raw = entry.get(key).and_then(|v| v.as_str())?;
+                    normalize_clock(raw, is_close)
+                };
+                let (opens, closes) = (clock("opens", false), clock("closes", true));
+                for day in days {
+                    let day_hours = hours.entry(day).or_default();
+                    match (&opens, &closes) {
+                        // schema.org: opens == closes == 00:00 marks a closed day
+                        (Some(o), Some(c)) if o == "00:00" && c == "24:00" => {}
+                        (Some(o), Some(c)) => day_hours.push((o.clone(), c.clone())),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        match object.get("openingHours") {
+            Some(serde_json::Value::String(spec)) => parse_schema_hours_string(spec, &mut hours),
+            Some(serde_json::Value::Array(specs)) => specs
+                .iter()
+                .filter_map(|s| s.as_str())
+                .for_each(|spec| parse_schema_hours_string(spec, &mut hours)),
+            _ => {}
+        }
+    }
+
+    hours
+}
+
+/// Extract opening hours, preferring JSON-LD `openingHoursSpecification` /
+/// `openingHours` over day/time patterns in the visible text.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn extract_opening_hours(html: &str, max_bytes: Option<usize>) -> PyResult<OpeningHours> {
+    let html = limit_input(html, max_bytes)?;
+    let mut result = OpeningHours::default();
+    if html.is_empty() {
+        return Ok(result);
+    }
+
+    let document = Html::parse_document(html);
+    let structured = json_ld_opening_hours(&document);
+
+    let text = document_text(&document);
+    let mut text_hours: HashMap<usize, DayHours> = HashMap::new();
+    let mut appointment_days: Vec<usize> = Vec::new();
+    let mut conflicting = false;
+
+    if let Some(m) = OPEN_24_7_RE.find(&text) {
+        result.is_24_7 = true;
+        result.raw_matches.push(m.as_str().to_string());
+        for day in 0..7 {
+            text_hours.insert(day, vec![("00:00".to_string(), "24:00".to_string())]);
+        }
+    }
+
+    for caps in HOURS_LINE_RE.captures_iter(&text) {
+        let value = caps["value"].to_lowercase();
+        let day_hours: DayHours = if value.starts_with("closed") {
+            Vec::new()
+        } else if value.starts_with("by") {
+            appointment_days.extend(parse_day_spec(&caps["days"]));
+            result.raw_matches.push(caps[0].trim().to_string());
+            continue;
+        } else {
+            let ranges: DayHours = TIME_RANGE_RE
+                .captures_iter(&value)
+                .filter_map(|c| parse_time_range(&c))
+                .collect();
+            if ranges.is_empty() {
+                continue;
+            }
+            ranges
+        };
+
+        result.raw_matches.push(caps[0].trim().to_string());
+        for day in parse_day_spec(&caps["days"]) {
+            match text_hours.get(&day) {
+                Some(existing) if *existing != day_hours => conflicting = true,
+                Some(_) => {}
+                None => {
+                    text_hours.insert(day, day_hours.clone());
+                }
+            }
+        }
+    }
+
+    let (hours, source, confidence) = if !structured.is_empty() {
+        let contradicted = structured
+            .iter()
+            .any(|(day, h)| text_hours.get(day).is_some_and(|t| t != h));
+        (structured, "json-ld", if contradicted { "medium" } else { "high" })
+    } else if !text_hours.is_empty() || !appointment_days.is_empty() {
+        (text_hours, "text", if conflicting { "low" } else { "medium" })
+    } else {
+        return Ok(result);
+    };
+
+    result.hours = hours
+        .into_iter()
+        .map(|(day, h)| (WEEKDAYS[day].to_string(), h))
+        .collect();
+    appointment_days.sort_unstable();
+    appointment_days.dedup();
+    result.by_appointment = appointment_days
+        .into_iter()
+        .map(|d| WEEKDAYS[d].to_string())
+        .collect();
+    result.source = Some(source);
+    result.confidence = Some(confidence);
+
+    Ok(result)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct AmpInfo {
+    pub is_amp_page: bool,
+    /// The page's AMP alternate (rel="amphtml"), resolved against `base_url`.
+    pub amp_url: Option<String>,
+}
+
+/// Detect AMP pages (⚡/amp on <html>, AMP runtime script) and AMP alternates.
+///
+/// Only markup is considered, so content linking to amp.dev does not count.
+#[pyfunction]
+#[pyo3(signature = (html, base_url=None, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_amp(
+    html: &str,
+    base_url: Option<&str>,
+    max_bytes: Option<usize>,
+) -> PyResult<AmpInfo> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(AmpInfo::default());
+    }
+    Ok(amp_document(&Html::parse_document(html), base_url))
+}
+
+fn amp_document(document: &Html, base_url: Option<&str>) -> AmpInfo {
+    let mut amp = AmpInfo::default();
+    let root_is_amp = document.select(&HTML_SEL).next().is_some_and(|root| {
+        root.value().attrs().any(|(name, _)| name == "amp" || name == "⚡")
+    });
+    amp.is_amp_page = root_is_amp
+        || document
+            .select(&SCRIPT_SRC_SEL)
+            .filter_map(|s| s.value().attr("src"))
+            .any(|src| src.to_lowercase().contains(AMP_RUNTIME_MARKER));
+
+    let base = parse_base_url(base_url);
+    amp.amp_url = document
+        .select(&AMPHTML_SEL)
+        .next()
+        .and_then(|link| link.value().attr("href"))
+        .map(|href| resolve_href(base.as_ref(), href));
+
+    amp
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct DiscoveredFeeds {
+    pub rss_feeds: Vec<String>,
+    pub sitemaps: Vec<String>,
+    /// An RSS/Atom feed other than a comments feed.
+    pub has_blog_feed: bool,
+}
+
+fn push_unique(list: &mut Vec<String>, url: String) {
+    if !list.contains(&url) {
+        list.push(url);
+    }
+}
+
+/// Parse a base URL, assuming https:// when the scheme is missing.
+pub(crate) fn parse_base_url(base_url: Option<&str>) -> Option<Url> {
+    let u = base_url?.trim();
+    if u.contains("://") {
+        Url::parse(u).ok()
+    } else {
+        Url::parse(&format!("https://{u}")).ok()
+    }
+}
+
+/// Resolve `href` against `base`; left as written when there is no base.
+pub(crate) fn resolve_href(base: Option<&Url>, href: &str) -> String {
+    let href = href.trim();
+    base.and_then(|b| b.join(href).ok())
+        .map(String::from)
+        .unwrap_or_else(|| href.to_string())
+}
+
+/// Collect advertised RSS/Atom feeds and sitemaps.
+///
+/// Relative URLs resolve against `base_url` when given; duplicates are dropped
+/// after resolution.
+#[pyfunction]
+#[pyo3(signature = (html, base_url=None, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn discover_feeds(
+    html: &str,
+    base_url: Option<&str>,
+    max_bytes: Option<usize>,
+) -> PyResult<DiscoveredFeeds> {
+    let html = limit_input(html, max_bytes)?;
+    let mut feeds = DiscoveredFeeds::default();
+    if html.is_empty() {
+        return Ok(feeds);
+    }
+
+    let base = parse_base_url(base_url);
+    let resolve = |href: &str| resolve_href(base.as_ref(), href);
+
+    let document = Html::parse_document(html);
+    for link in document.select(&FEED_LINK_SEL) {
+        if let Some(href) = link.value().attr("href") {
+            push_unique(&mut feeds.rss_feeds, resolve(href));
+        }
+    }
+    for link in document.select(&SITEMAP_LINK_SEL) {
+        if let Some(href) = link.value().attr("href") {
+            push_unique(&mut feeds.sitemaps, resolve(href));
+        }
+    }
+    for anchor in document.select(&LINK_SEL) {
+        let href = anchor.value().attr("href").unwrap_or("");
+        let path = href.split(['?', '#']).next().unwrap_or("").trim().to_lowercase();
+        let path = path.trim_end_matches('/');
+        if SITEMAP_PATH_SUFFIXES.iter().any(|s| path.ends_with(s)) {
+            push_unique(&mut feeds.sitemaps, resolve(href));
+        } else if FEED_PATH_SUFFIXES.iter().any(|s| path.ends_with(s)) {
+            push_unique(&mut feeds.rss_feeds, resolve(href));
+        }
+    }
+
+    feeds.has_blog_feed = feeds
+        .rss_feeds
+        .iter()
+        .any(|f| !f.to_lowercase().contains("comments"));
+
+    Ok(feeds)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct LinkClassification {
+    pub internal_count: usize,
+    pub internal_samples: Vec<String>,
+    pub external_count: usize,
+    pub external_samples: Vec<String>,
+    /// Registrable domain → number of links to it.
+    pub external_domains: HashMap<String, usize>,
+    pub nofollow_count: usize,
+    pub sponsored_count: usize,
+    pub ugc_count: usize,
+    /// `href="#"` / `javascript:` links left over from unfinished templates.
+    pub placeholder_count: usize,
+    pub mailto_count: usize,
+    pub tel_count: usize,
+}
+
+fn push_sample(samples: &mut Vec<String>, url: &str) {
+    if samples.len() < LINK_SAMPLE_LIMIT && !samples.iter().any(|s| s == url) {
+        samples.push(url.to_string());
+    }
+}
+
+/// Classify every `<a href>` on the page.
+///
+/// Relative links resolve against `base_domain` and count as internal, as do
+/// absolute links on the same registrable domain. Sample lists are capped.
+#[pyfunction]
+#[pyo3(signature = (html, base_domain, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn classify_links(
+    html: &str,
+    base_domain: &str,
+    max_bytes: Option<usize>,
+) -> PyResult<LinkClassification> {
+    let html = limit_input(html, max_bytes)?;
+    let mut links = LinkClassification::default();
+    if html.is_empty() {
+        return Ok(links);
+    }
+
+    let site = crate::text::normalize_domain(base_domain)
+        .unwrap_or_else(|| "site.invalid".to_string());
+    let site_registrable = crate::text::registrable_domain(&site);
+    let Ok(base) = Url::parse(&format!("https://{site}/")) else {
+        return Ok(links);
+    };
+
+    let document = Html::parse_document(html);
+    for anchor in document.select(&LINK_SEL) {
+        let value = anchor.value();
+        let href = value.attr("href").unwrap_or("").trim();
+        let lower = href.to_lowercase();
+
+        if let Some(rel) = value.attr("rel") {
+            for token in rel.split_ascii_whitespace() {
+                match token.to_lowercase().as_str() {
+                    "nofollow" => links.nofollow_count += 1,
+                    "sponsored" => links.sponsored_count += 1,
+                    "ugc" => links.ugc_count += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        if href.is_empty() || href == "#" || href == "#!" || lower.starts_with("javascript:") {
+            links.placeholder_count += 1;
+            continue;
+        }
+        if lower.starts_with("mailto:") {
+            links.mailto_count += 1;
+            continue;
+        }
+        if lower.starts_with("tel:") {
+            links.tel_count += 1;
+            continue;
+        }
+
+        let Ok(url) = base.join(href) else { continue };
+        if !matches!(url.scheme(), "http" | "https") {
+            continue;
+        }
+        let Some(host) = url.host_str() else { continue };
+        let registrable = crate::text::registrable_domain(host.trim_start_matches("www."));
+        if registrable == site_registrable {
+            links.internal_count += 1;
+            push_sample(&mut links.internal_samples, url.as_str());
+        } else {
+            links.external_count += 1;
+            push_sample(&mut links.external_samples, url.as_str());
+            *links.external_domains.entry(registrable).or_insert(0) += 1;
+        }
+    }
+
+    Ok(links)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct HostingHints {
+    pub cdn_providers: Vec<String>,
+    pub hosting_hint: Option<String>,
+    /// Registrable domains of assets served from outside the site.
+    pub asset_domains: Vec<String>,
+}
+
+fn host_matches(host: &str, suffix: &str) -> bool {
+    host == suffix || host.strip_suffix(suffix).is_some_and(|rest| rest.ends_with('.'))
+}
+
+/// Infer hosting platform and CDNs from the hosts that serve page assets.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn detect_hosting_hints(html: &str, max_bytes: Option<usize>) -> PyResult<HostingHints> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(HostingHints::default());
+    }
+    Ok(hosting_hints_document(&Html::parse_document(html)))
+}
+
+fn hosting_hints_document(document: &Html) -> HostingHints {
+    let mut hints = HostingHints::default();
+    let own = site_domain(document, None).map(|d| crate::text::registrable_domain(&d));
+
+    for el in document.select(&ASSET_SEL) {
+        let value = el.value();
+        let url = value.attr("src").or_else(|| value.attr("href")).unwrap_or("");
+        let Some(host) = absolute_url_domain(url) else { continue };
+
+        for (provider, suffix, kind) in HOSTING_SIGNATURES {
+            if !host_matches(&host, suffix) {
+                continue;
+            }
+            match kind {
+                HostingKind::Host => {
+                    hints.hosting_hint.get_or_insert_with(|| provider.to_string());
+                }
+                HostingKind::Cdn => push_unique(&mut hints.cdn_providers, provider.to_string()),
+            }
+        }
+
+        let registrable = crate::text::registrable_domain(&host);
+        if own.as_deref() != Some(registrable.as_str()) {
+            push_unique(&mut hints.asset_domains, registrable);
+        }
+    }
+
+    hints
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct ModernPractices {
+    pub uses_lazy_loading: bool,
+    pub lazy_loaded_count: usize,
+    pub uses_resource_hints: bool,
+    pub preconnect_count: usize,
+    pub dns_prefetch_count: usize,
+    pub preload_count: usize,
+    pub uses_responsive_images: bool,
+    pub srcset_count: usize,
+    pub picture_count: usize,
+    pub uses_modern_image_formats: bool,
+    pub modern_format_count: usize,
+    pub uses_module_scripts: bool,
+    pub module_script_count: usize,
+    pub nomodule_script_count: usize,
+}
+
+impl ModernPractices {
+    /// 0–100, 20 points per practice in use.
+    pub fn maturity(&self) -> u32 {
+        let used = [
+            self.uses_lazy_loading,
+            self.uses_resource_hints,
+            self.uses_responsive_images,
+            self.uses_modern_image_formats,
+            self.uses_module_scripts,
+        ];
+        used.iter().filter(|u| **u).count() as u32 * 20
+    }
+}
+
+fn is_modern_image_source(el: &ElementRef) -> bool {
+    let value = el.value();
+    let declared_type = value.attr("type").unwrap_or("").trim().to_lowercase();
+    if let Some(subtype) = declared_type.strip_prefix("image/") {
+        if MODERN_IMAGE_EXTENSIONS.contains(&subtype) {
+            return true;
+        }
+    }
+    let srcset_urls = value
+        .attr("srcset")
+        .into_iter()
+        .flat_map(|s| s.split(','))
+        .filter_map(|candidate| candidate.split_whitespace().next());
+    value
+        .attr("src")
+        .into_iter()
+        .chain(srcset_urls)
+        .filter_map(image_extension)
+        .any(|ext| MODERN_IMAGE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Credit front-end practices of a well-built site: lazy loading, resource
+/// hints, responsive images, WebP/AVIF and module/nomodule scripts.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn modern_practices(html: &str, max_bytes: Option<usize>) -> PyResult<ModernPractices> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(ModernPractices::default());
+    }
+    Ok(modern_practices_document(&Html::parse_document(html)))
+}
+
+fn modern_practices_document(document: &Html) -> ModernPractices {
+    let mut practices = ModernPractices {
+        lazy_loaded_count: document.select(&LAZY_LOADED_SEL).count(),
+        preconnect_count: document.select(&PRECONNECT_SEL).count(),
+        dns_prefetch_count: document.select(&DNS_PREFETCH_SEL).count(),
+        preload_count: document.select(&PRELOAD_SEL).count(),
+        srcset_count: document.select(&SRCSET_SEL).count(),
+        picture_count: document.select(&PICTURE_SEL).count(),
+        modern_format_count: document
+            .select(&IMAGE_SOURCE_SEL)
+            .filter(is_modern_image_source)
+            .count(),
+        ..Default::default()
+    };
+    for script in document.select(&SCRIPT_SEL) {
+        if script.value().attr("nomodule").is_some() {
+            practices.nomodule_script_count += 1;
+        } else if script_type(&script) == "module" {
+            practices.module_script_count += 1;
+        }
+    }
+
+    practices.uses_lazy_loading = practices.lazy_loaded_count > 0;
+    practices.uses_resource_hints =
+        practices.preconnect_count + practices.dns_prefetch_count + practices.preload_count > 0;
+    practices.uses_responsive_images = practices.srcset_count + practices.picture_count > 0;
+    practices.uses_modern_image_formats = practices.modern_format_count > 0;
+    practices.uses_module_scripts = practices.module_script_count > 0;
+
+    practices
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct PerformanceHints {
+    /// Scripts in <head> without async/defer (parser-blocking).
+    pub sync_head_scripts: usize,
+    pub blocking_stylesheets: usize,
+    pub async_scripts: usize,
+    pub defer_scripts: usize,
+    pub third_party_host_count: usize,
+    pub third_party_hosts: Vec<String>,
+    pub inline_script_bytes: usize,
+    pub uses_http2_push_hints: bool,
+}
+
+impl PerformanceHints {
+    /// Coarse heaviness bucket for scoring: "low", "medium" or "high".
+    pub fn risk(&self) -> &'static str {
+        let blocking = self.sync_head_scripts + self.blocking_stylesheets;
+        if self.sync_head_scripts >= 6 || self.third_party_host_count >= 10 || blocking >= 12 {
+            "high"
+        } else if self.sync_head_scripts >= 3
+            || self.third_party_host_count >= 5
+            || self.blocking_stylesheets >= 5
+            || self.inline_script_bytes > 100_000
+        {
+            "medium"
+        } else {
+            "low"
+        }
+    }
+}
+
+pub(crate) fn script_type(el: &ElementRef) -> String {
+    el.value().attr("type").unwrap_or("").trim().to_lowercase()
+}
+
+/// Host of an absolute or protocol-relative URL, without www.
+fn absolute_url_domain(src: &str) -> Option<String> {
+    let src = src.trim();
+    let lower = src.to_lowercase();
+    if let Some(rest) = src.strip_prefix("//") {
+        crate::text::normalize_domain(rest)
+    } else if lower.starts_with("http://") || lower.starts_with("https://") {
+        crate::text::normalize_domain(src)
+    } else {
+        None
+    }
+}
+
+/// The site's own domain: from `page_url`, else the canonical/og:url.
+fn site_domain(document: &Html, page_url: Option<&str>) -> Option<String> {
+    page_url.and_then(crate::text::normalize_domain).or_else(|| {
+        document.select(&SITE_URL_SEL).find_map(|el| {
+            let value = el.value();
+            value.attr("href").or_else(|| value.attr("content")).and_then(absolute_url_domain)
+        })
+    })
+}
+
+/// Estimate page heaviness from markup alone (no asset fetching).
+///
+/// Third-party hosts are script hosts other than the site's own domain, taken
+/// from `page_url` or else the canonical/og:url. Without either, every
+/// absolute script host counts.
+#[pyfunction]
+#[pyo3(signature = (html, page_url=None, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn performance_hints(
+    html: &str,
+    page_url: Option<&str>,
+    max_bytes: Option<usize>,
+) -> PyResult<PerformanceHints> {
+    let html = limit_input(html, max_bytes)?;
+    if html.is_empty() {
+        return Ok(PerformanceHints::default());
+    }
+    Ok(performance_hints_document(&Html::parse_document(html), page_url))
+}
+
+fn performance_hints_document(document: &Html, page_url: Option<&str>) -> PerformanceHints {
+    let mut hints = PerformanceHints::default();
+    let site_domain = site_domain(document, page_url);
+
+    for script in document.select(&HEAD_SCRIPT_SRC_SEL) {
+        let value = script.value();
+        // Module scripts are deferred by default
+        if value.attr("async").is_none()
+            && value.attr("defer").is_none()
+            && script_type(&script) != "module"
+        {
+            hints.sync_head_scripts += 1;
+        }
+    }
+
+    hints.blocking_stylesheets = document
+        .select(&HEAD_STYLESHEET_SEL)
+        .filter(|link| {
+            let value = link.value();
+            value.attr("disabled").is_none()
+                && value.attr("media").is_none_or(|m| !m.trim().eq_ignore_ascii_case("print"))
+        })
+        .count();
+
+    let mut hosts: Vec<String> = Vec::new();
+    for script in document.select(&SCRIPT_SEL) {
+        let value = script.value();
+        match value.attr("src") {
+            Some(src) => {
+                if value.attr("async").is_some() {
+                    hints.async_scripts += 1;
+                }
+                if value.attr("defer").is_some() {
+                    hints.defer_scripts += 1;
+                }
+                if let Some(host) = absolute_url_domain(src) {
+                    if site_domain.as_deref() != Some(host.as_str()) && !hosts.contains(&host) {
+                        hosts.push(host);
+                    }
+                }
+            }
+            None if EXECUTABLE_SCRIPT_TYPES.contains(&script_type(&script).as_str()) => {
+                hints.inline_script_bytes += script.text().map(str::len).sum::<usize>();
+            }
+            None => {}
+        }
+    }
+    hints.third_party_host_count = hosts.len();
+    hints.third_party_hosts = hosts;
+
+    hints.uses_http2_push_hints = document.select(&RESOURCE_HINT_SEL).next().is_some();
+
+    hints
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct ImageAudit {
+    pub total: usize,
+    pub missing_alt: usize,
+    pub lazy_loaded: usize,
+    pub with_dimensions: usize,
+    pub data_uri: usize,
+    pub background_images: usize,
+    /// Lowercased file extension → count (data URIs excluded).
+    pub extensions: HashMap<String, usize>,
+}
+
+/// File extension of an image URL, ignoring query string and fragment.
+fn image_extension(src: &str) -> Option<String> {
+    let path = src.split(['?', '#']).next().unwrap_or("");
+    let file = path.rsplit('/').next().unwrap_or("");
+    let (_, ext) = file.rsplit_once('.')?;
+    (!ext.is_empty() && ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then(|| ext.to_lowercase())
+}
+
+/// Summarise `<img>` usage: alt coverage, lazy loading, explicit dimensions
+/// and the format mix. Inline-style background images are counted separately.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn audit_images(html: &str, max_bytes: Option<usize>) -> PyResult<ImageAudit> {
+    let html = limit_input(html, max_bytes)?;
+    let mut audit = ImageAudit::default();
+    if html.is_empty() {
+        return Ok(audit);
+    }
+
+    let document = Html::parse_document(html);
+    for img in document.select(&IMG_SEL) {
+        let value = img.value();
+        audit.total += 1;
+        if value.attr("alt").is_none_or(|a| a.trim().is_empty()) {
+            audit.missing_alt += 1;
+        }
+        if value.attr("loading").is_some_and(|l| l.eq_ignore_ascii_case("lazy")) {
+            audit.lazy_loaded += 1;
+        }
+        if value.attr("width").is_some() && value.attr("height").is_some() {
+            audit.with_dimensions += 1;
+        }
+
+        // Lazy-load libraries keep the real URL in data-src
+        let src = value
+            .attr("src")
+            .filter(|s| !s.trim().is_empty())
+            .or_else(|| value.attr("data-src"))
+            .unwrap_or("")
+            .trim();
+        if src.get(..5).is_some_and(|s| s.eq_ignore_ascii_case("data:")) {
+            audit.data_uri += 1;
+        } else if let Some(ext) = image_extension(src) {
+            *audit.extensions.entry(ext).or_insert(0) += 1;
+        }
+    }
+
+    audit.background_images = document
+        .select(&STYLE_ATTR_SEL)
+        .filter_map(|el| el.value().attr("style"))
+        .map(|style| BACKGROUND_IMAGE_RE.find_iter(style).count())
+        .sum();
+
+    Ok(audit)
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct ContentMetrics {
+    pub word_count: usize,
+    pub text_length: usize,
+    pub paragraph_count: usize,
+    pub is_thin_content: bool,
+}
+
+/// Elements whose text a visitor never reads as page content.
+fn is_non_content(el: &ElementRef) -> bool {
+    let value = el.value();
+    if CONTENT_EXCLUDED_TAGS.contains(&value.name()) || value.attr("hidden").is_some() {
+        return true;
+    }
+    if let Some(style) = value.attr("style") {
+        let style: String = style.to_lowercase().split_whitespace().collect();
+        if style.contains("display:none") || style.contains("visibility:hidden") {
+            return true;
+        }
+    }
+    if matches!(value.name(), "html" | "body") {
+        return false;
+    }
+    let class_id = format!(
+        "{} {}",
+        value.attr("class").unwrap_or(""),
+        value.attr("id").unwrap_or("")
+    )
+    .to_lowercase();
+    BOILERPLATE_MARKERS.iter().any(|m| class_id.contains(m))
+}
+
+/// `<p>` elements outside nav/footer chrome, cookie banners and hidden or
+/// non-rendered containers, in document order.
+pub(crate) fn visible_paragraphs(document: &Html) -> impl Iterator<Item = ElementRef<'_>> {
+    document.select(&PARAGRAPH_SEL).filter(|p| {
+        !is_non_content(p)
+            && !p.ancestors().filter_map(ElementRef::wrap).any(|a| {
+                is_non_content(&a) || NON_VISIBLE_TAGS.contains(&a.value().name())
+            })
+    })
+}
+
+/// Measure the visible content of a page.
+///
+/// Navigation, footers, hidden elements, cookie banners and skip-links are
+/// excluded. `thin_threshold` is the word count below which content is thin.
+#[pyfunction]
+#[pyo3(signature = (html, thin_threshold=THIN_CONTENT_WORDS, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn content_metrics(
+    html: &str,
+    thin_threshold: usize,
+    max_bytes: Option<usize>,
+) -> PyResult<ContentMetrics> {
+    let html = limit_input(html, max_bytes)?;
+    let document = Html::parse_document(html);
+
+    let mut text = String::new();
+    collect_text(document.root_element(), &is_non_content, &mut text);
+
+    let paragraph_count = visible_paragraphs(&document)
+        .filter(|p| !p.text().collect::<String>().trim().is_empty())
+        .count();
+
+    let word_count = text.split_whitespace().count();
+    Ok(ContentMetrics {
+        word_count,
+        text_length: text.chars().count(),
+        paragraph_count,
+        is_thin_content: word_count < thin_threshold,
+    })
+}
+
+#[derive(Debug, Default, Clone, IntoPyObject)]
+pub struct Headings {
+    pub h1: Vec<String>,
+    pub h2: Vec<String>,
+    pub h3: Vec<String>,
+    pub h1_count: usize,
+    /// A heading more than one level below its predecessor (h1 → h4).
+    pub has_hierarchy_gaps: bool,
+    pub first_h1: Option<String>,
+}
+
+/// Extract the page's heading outline (h1–h3 text, capped per level).
+///
+/// Headings inside nav/footer/aside and empty (icon-only) headings are ignored.
+#[pyfunction]
+#[pyo3(signature = (html, max_per_level=10, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn extract_headings(
+    html: &str,
+    max_per_level: usize,
+    max_bytes: Option<usize>,
+) -> PyResult<Headings> {
+    let html = limit_input(html, max_bytes)?;
+    let mut headings = Headings::default();
+    if html.is_empty() {
+        return Ok(headings);
+    }
+
+    let document = Html::parse_document(html);
+    let mut previous_level: Option<u8> = None;
+
+    for el in document.select(&HEADING_SEL) {
+        if has_ancestor_tag(&el, OUTLINE_EXCLUDED_TAGS) {
+            continue;
+        }
+        let text = el.text().collect::<Vec<_>>().join(" ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            continue;
+        }
+
+        let level = el.value().name().as_bytes()[1] - b'0';
+        if previous_level.is_some_and(|prev| level > prev + 1) {
+            headings.has_hierarchy_gaps = true;
+        }
+        previous_level = Some(level);
+
+        let bucket = match level {
+            1 => {
+                headings.h1_count += 1;
+                if headings.first_h1.is_none() {
+                    headings.first_h1 = Some(text.clone());
+                }
+                &mut headings.h1
+            }
+            2 => &mut headings.h2,
+            3 => &mut headings.h3,
+            _ => continue,
+        };
+        if bucket.len() < max_per_level {
+            bucket.push(text);
+        }
+    }
+
+    Ok(headings)
+}
+
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(DEFAULT_MAX_BYTES)))]
+pub fn seo_audit(html: &str, max_bytes: Option<usize>) -> PyResult<SeoAudit> {
+    let html = limit_input(html, max_bytes)?;
+    Ok(seo_audit_document(&Html::parse_document(html)))
+}
+
+/// Default `max_bytes` for the DOM-parsing entry points. Past this `scraper`
+/// gets slow and memory-hungry for little extra signal.
+pub(crate) const DEFAULT_MAX_BYTES: usize = 3 * 1024 * 1024;
+
+/// Inputs above this are rejected outright rather than risking an OOM.
+pub(crate) const HARD_MAX_BYTES: usize = 50 * 1024 * 1024;
+
+/// How much of the end of a truncated page is re-scanned for footer markers.
+pub(crate) const TAIL_SCAN_BYTES: usize = 64 * 1024;
+
+/// Raise ValueError for input above `HARD_MAX_BYTES`.
+pub(crate) fn check_size(html: &str) -> PyResult<()> {
+    size_error(html).map_or(Ok(()), |e| Err(pyo3::exceptions::PyValueError::new_err(e)))
+}
+
+pub(crate) fn size_error(html: &str) -> Option<String> {
+    (html.len() > HARD_MAX_BYTES).then(|| {
+        format!("html is {} bytes, above the {} byte limit", html.len(), HARD_MAX_BYTES)
+    })
+}
+
+/// The guard every DOM-parsing pyfunction runs first: ValueError above
+/// `HARD_MAX_BYTES`, then truncation to `max_bytes` at a tag boundary.
+pub(crate) fn limit_input(html: &str, max_bytes: Option<usize>) -> PyResult<&str> {
+    check_size(html)?;
+    Ok(max_bytes.map_or(html, |max| truncate_at_tag(html, max)))
+}
+
+/// Cut `html` to at most `max_bytes`, backing up to the last `<` so the parser
+/// never sees half a tag. Returns the input unchanged when it already fits.
+pub(crate) fn truncate_at_tag(html: &str, max_bytes: usize) -> &str {
+    if html.len() <= max_bytes {
+        return html;
+    }
+    let mut end = max_bytes;
+    while !html.is_char_boundary(end) {
+        end -= 1;
+    }
+    match html[..end].rfind('<') {
+        Some(pos) if pos > 0 => &html[..pos],
+        _ => &html[..end],
+    }
+}
+
+/// The last `len` bytes of `html`, starting on a char boundary.
+pub(crate) fn tail_of(html: &str, len: usize) -> &str {
+    let mut start = html.len().saturating_sub(len);
+    while !html.is_char_boundary(start) {
+        start += 1;
+    }
+    &html[start..]
+}
+
+/// Optional sections of the `analyze_tech_stack` report.
+#[derive(Debug, Clone, Copy)]
+struct TechStackOptions {
+    include_seo: bool,
+    include_accessibility: bool,
+    include_confidence: bool,
+    /// Truncate longer input before analysis; `None` analyses everything.
+    max_bytes: Option<usize>,
+}
+
+/// Confidence-tagged versions of the headline detections.
+#[derive(Debug, Clone, IntoPyObject)]
+struct Detections {
+    cms: Option<Detection>,
+    tracking: Vec<Detection>,
+    frameworks: Vec<Detection>,
+    booking: Option<Detection>,
+}
+
+/// Everything `analyze_tech_stack` reports, computed without touching Python
+/// so batches can run off the GIL.
+#[derive(Default)]
+struct TechStack {
+    cms: Option<String>,
+    tracking: HashMap<String, bool>,
+    has_booking: bool,
+    frameworks: Vec<String>,
+    has_responsive: bool,
+    responsive_score: u32,
+    framework_versions: Vec<FrameworkVersion>,
+    has_legacy_jquery: bool,
+    amp: AmpInfo,
+    hosting: HostingHints,
+    site_builder: Option<(&'static str, Confidence)>,
+    page_builder: Option<(&'static str, Confidence)>,
+    chat_vendor: Option<String>,
+    has_whatsapp_link: bool,
+    has_online_ordering: bool,
+    has_online_payments: bool,
+    has_only_universal_analytics: bool,
+    consent_vendor: Option<String>,
+    captcha_provider: Option<String>,
+    is_hiring: bool,
+    is_parked: bool,
+    is_placeholder: bool,
+    legacy_score: u32,
+    ab_testing_tools: Vec<String>,
+    performance_risk: &'static str,
+    digital_maturity: u32,
+    seo: Option<SeoAudit>,
+    accessibility: Option<AccessibilityAudit>,
+    detections: Option<Detections>,
+    truncated: bool,
+    /// Set when the page is an error, challenge or maintenance stand-in.
+    page_status: Option<&'static str>,
+}
+
+impl TechStack {
+    fn analyze(html: &str, options: TechStackOptions) -> Self {
+        let head = match options.max_bytes {
+            Some(max) => truncate_at_tag(html, max),
+            None => html,
+        };
+        // Every detector below shares this one parse and lowercased copy
+        let document = Html::parse_document(head);
+        let head_lower = head.to_lowercase();
+        if let Some(kind) = error_page_kind(&document, &head_lower) {
+            // Signals from a stand-in page describe the host, not the site
+            return TechStack {
+                page_status: Some(page_status_for(kind)),
+                truncated: head.len() < html.len(),
+                ..Default::default()
+            };
+        }
+        let mut report = Self::analyze_full(head, &document, &head_lower, options);
+        if head.len() < html.len() {
+            report.truncated = true;
+            report.merge_tail(&tail_of(html, TAIL_SCAN_BYTES).to_lowercase());
+        }
+        report
+    }
+
+    /// Fold string-matched detections from the end of a truncated page into
+    /// the report; copyright footers often carry the only CMS marker.
+    fn merge_tail(&mut self, tail_lower: &str) {
+        if self.cms.is_none() {
+            self.cms = cms_detection(tail_lower).map(|d| d.value);
+        }
+        for detection in tracking_detections(tail_lower) {
+            self.tracking.insert(detection.value, true);
+        }
+        for detection in framework_detections(tail_lower) {
+            if !self.frameworks.contains(&detection.value) {
+                self.frameworks.push(detection.value);
+            }
+        }
+        self.has_booking = self.has_booking || booking_detection(tail_lower).is_some();
+
+        if let Some(detections) = self.detections.as_mut() {
+            if detections.cms.is_none() {
+                detections.cms = cms_detection(tail_lower);
+            }
+            for detection in tracking_detections(tail_lower) {
+                if !detections.tracking.iter().any(|d| d.value == detection.value) {
+                    detections.tracking.push(detection);
+                }
+            }
+            for detection in framework_detections(tail_lower) {
+                if !detections.frameworks.iter().any(|d| d.value == detection.value) {
+                    detections.frameworks.push(detection);
+                }
+            }
+            if detections.booking.is_none() {
+                detections.booking = booking_detection(tail_lower);
+            }
+        }
+    }
+
+    fn analyze_full(
+        html: &str,
+        document: &Html,
+        html_lower: &str,
+        options: TechStackOptions,
+    ) -> Self {
+        let tracking_ids = extract_tracking_ids(html);
+        let cms = cms_detection(html_lower);
+        let tracking = tracking_detections(html_lower);
+        let frameworks = framework_detections(html_lower);
+        let booking = booking_detection(html_lower);
+
+        TechStack {
+            cms: cms.as_ref().map(|d| d.value.clone()),
+            tracking: tracking_flags(&tracking),
+            has_booking: booking.is_some(),
+            frameworks: frameworks.iter().map(|d| d.value.clone()).collect(),
+            has_responsive: has_responsive_indicator(html_lower),
+            responsive_score: responsive_document(document, html_lower).responsive_score,
+            framework_versions: framework_versions(html_lower),
+            has_legacy_jquery: jquery_document(document, html_lower).is_legacy,
+            amp: amp_document(document, None),
+            hosting: hosting_hints_document(document),
+            site_builder: match_builder(&SITE_BUILDER_SIGNATURES, html_lower),
+            page_builder: match_builder(&PAGE_BUILDER_SIGNATURES, html_lower),
+            chat_vendor: chat_widget_vendor(html_lower),
+            has_whatsapp_link: WHATSAPP_LINK_MARKERS.iter().any(|m| html_lower.contains(m)),
+            has_online_ordering: online_ordering_document(document, html_lower)
+                .has_online_ordering,
+            has_online_payments: payment_providers_document(document, html_lower)
+                .iter()
+                .any(|p| p.confidence == Confidence::Definite.as_str()),
+            // UA stopped processing data in 2023 — a UA tag with no GA4 is a dead setup
+            has_only_universal_analytics: !tracking_ids.ua_ids.is_empty()
+                && tracking_ids.ga4_ids.is_empty(),
+            consent_vendor: consent_platform_document(document, html_lower),
+            captcha_provider: captcha_document(document, html_lower).provider,
+            is_hiring: hiring_signals_document(document).is_hiring(),
+            is_parked: parked_domain_document(document, html_lower).is_parked,
+            is_placeholder: placeholder_page_document(document, html_lower).is_placeholder,
+            legacy_score: legacy_markup_document(document).legacy_score,
+            ab_testing_tools: ab_testing(html_lower).vendors,
+            performance_risk: performance_hints_document(document, None).risk(),
+            digital_maturity: modern_practices_document(document).maturity(),
+            seo: options.include_seo.then(|| seo_audit_document(document)),
+            accessibility: options
+                .include_accessibility
+                .then(|| accessibility_audit_document(document)),
+            detections: options.include_confidence.then_some(Detections {
+                cms,
+                tracking,
+                frameworks,
+                booking,
+            }),
+            truncated: false,
+            page_status: None,
+        }
+    }
+
+    fn into_dict(self, py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        let dict = PyDict::new(py);
+        let page_status = self.page_status;
+
+        dict.set_item("cms", self.cms)?;
+
+        let tracking_dict = PyDict::new(py);
+        for (k, v) in &self.tracking {
+            tracking_dict.set_item(k, *v)?;
+        }
+        dict.set_item("tracking", tracking_dict)?;
+        dict.set_item("tracking_sophistication", self.tracking.values().filter(|v| **v).count())?;
+
+        dict.set_item("has_booking", self.has_booking)?;
+        dict.set_item("frameworks", &self.frameworks)?;
+        dict.set_item("has_ssl", false)?;
+        dict.set_item("has_responsive", self.has_responsive)?;
+        dict.set_item("responsive_score", self.responsive_score)?;
+        dict.set_item("has_legacy_framework", self.framework_versions.iter().any(|f| f.legacy))?;
+        dict.set_item("framework_versions", self.framework_versions)?;
+        dict.set_item("has_legacy_jquery", self.has_legacy_jquery)?;
+
+        dict.set_item("is_amp_page", self.amp.is_amp_page)?;
+        dict.set_item("amp_url", self.amp.amp_url)?;
+
+        dict.set_item("hosting_hint", self.hosting.hosting_hint)?;
+        dict.set_item("cdn_providers", self.hosting.cdn_providers)?;
+
+        dict.set_item("site_builder", self.site_builder.map(|(name, _)| name))?;
+        dict.set_item("site_builder_confidence", self.site_builder.map(|(_, c)| c.as_str()))?;
+        dict.set_item("page_builder", self.page_builder.map(|(name, _)| name))?;
+        dict.set_item("page_builder_confidence", self.page_builder.map(|(_, c)| c.as_str()))?;
+
+        dict.set_item("has_chat_widget", self.chat_vendor.is_some())?;
+        dict.set_item("chat_vendor", self.chat_vendor)?;
+        dict.set_item("has_whatsapp_link", self.has_whatsapp_link)?;
+        dict.set_item("has_online_ordering", self.has_online_ordering)?;
+        dict.set_item("has_online_payments", self.has_online_payments)?;
+
+        dict.set_item("has_only_universal_analytics", self.has_only_universal_analytics)?;
+
+        dict.set_item("has_cookie_banner", self.consent_vendor.is_some())?;
+        dict.set_item("consent_vendor", self.consent_vendor)?;
+
+        dict.set_item("has_captcha", self.captcha_provider.is_some())?;
+        dict.set_item("captcha_provider", self.captcha_provider)?;
+
+        dict.set_item("is_hiring", self.is_hiring)?;
+        dict.set_item("is_parked", self.is_parked)?;
+        dict.set_item("is_placeholder", self.is_placeholder)?;
+        dict.set_item("legacy_score", self.legacy_score)?;
+        dict.set_item("ab_testing_tools", self.ab_testing_tools)?;
+
+        dict.set_item("performance_risk", self.performance_risk)?;
+        dict.set_item("digital_maturity", self.digital_maturity)?;
+        dict.set_item("page_status", page_status.unwrap_or("ok"))?;
+        dict.set_item("truncated", self.truncated)?;
+
+        if let Some(seo) = self.seo {
+            dict.set_item("seo", seo)?;
+        }
+        if let Some(accessibility) = self.accessibility {
+            dict.set_item("accessibility", accessibility)?;
+        }
+        if let Some(detections) = self.detections {
+            dict.set_item("detections", detections)?;
+        }
+
+        // Nothing was analysed: report every signal as unknown, not false
+        if page_status.is_some() {
+            for key in dict.keys() {
+                let key: String = key.extract()?;
+                if key != "page_status" && key != "truncated" {
+                    dict.set_item(key, py.None())?;
+                }
+            }
+        }
+
+        Ok(dict)
+    }
+}
+
+/// `html` may be `str` or raw `bytes`. For bytes the detected charset is
+/// reported under an extra `encoding` key; `str` output is unchanged.
+///
+/// `include_confidence` adds a "detections" key holding the CMS, tracking,
+/// framework and booking detections as `{value, confidence, evidence}`.
+///
+/// Pages longer than `max_bytes` are cut at a tag boundary and reported with
+/// `truncated: true`; the final 64KB is still scanned for string markers.
+/// Input above 50MB raises ValueError.
+#[pyfunction]
+#[pyo3(signature = (
+    html,
+    include_seo=false,
+    include_accessibility=false,
+    include_confidence=false,
+    max_bytes=Some(DEFAULT_MAX_BYTES),
+))]
+pub fn analyze_tech_stack(
+    py: Python<'_>,
+    html: &Bound<'_, PyAny>,
+    include_seo: bool,
+    include_accessibility: bool,
+    include_confidence: bool,
+    max_bytes: Option<usize>,
+) -> PyResult<PyObject> {
+    let input = crate::encoding::read_html(html)?;
+    check_size(&input.text)?;
+    let options =
+        TechStackOptions { include_seo, include_accessibility, include_confidence, max_bytes };
+    let report = TechStack::analyze(&input.text, options);
+    let dict = report.into_dict(py)?;
+    if let Some(encoding) = input.encoding {
+        dict.set_item("encoding", encoding)?;
+    }
+    Ok(dict.into())
+}
+
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "analysis panicked".to_string())
+}
+
+/// `analyze_tech_stack` over many `(id, html)` pages in parallel.
+///
+/// The GIL is released while pages are analysed on a rayon pool
+/// (`num_threads` workers, default: rayon's global pool). Results come back
+/// in input order, each with its "id"; a page that panics or exceeds the
+/// 50MB hard cap gets `{"id": ..., "error": ...}` instead of failing the batch.
+#[pyfunction]
+#[pyo3(signature = (
+    pages,
+    include_seo=false,
+    include_accessibility=false,
+    num_threads=None,
+    include_confidence=false,
+    max_bytes=Some(DEFAULT_MAX_BYTES),
+))]
+pub fn analyze_tech_stack_batch(
+    py: Python<'_>,
+    pages: Vec<(String, String)>,
+    include_seo: bool,
+    include_accessibility: bool,
+    num_threads: Option<usize>,
+    include_confidence: bool,
+    max_bytes: Option<usize>,
+) -> PyResult<Vec<PyObject>> {
+    let options =
+        TechStackOptions { include_seo, include_accessibility, include_confidence, max_bytes };
+    let run = || -> Vec<Result<TechStack, String>> {
+        pages
+            .par_iter()
+            .map(|(_, html)| {
+                if let Some(e) = size_error(html) {
+                    return Err(e);
+                }
+                std::panic::catch_unwind(|| TechStack::analyze(html, options))
+                    .map_err(|payload| panic_message(payload.as_ref()))
+            })
+            .collect()
+    };
+
+    let reports = py.allow_threads(|| match num_threads {
+        Some(n) => rayon::ThreadPoolBuilder::new()
+            .num_threads(n.max(1))
+            .build()
+            .map(|pool| pool.install(run))
+            .map_err(|e| e.to_string()),
+        None => Ok(run()),
+    });
+    let reports = reports.map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+
+    pages
+        .iter()
+        .zip(reports)
+        .map(|((id, _), report)| {
+            let dict = PyDict::new(py);
+            dict.set_item("id", id)?;
+            match report {
+                Ok(report) => dict.update(report.into_dict(py)?.as_mapping())?,
+                Err(message) => dict.set_item("error", message)?,
+            }
+            Ok(dict.into())
+        })
+        .collect()
 }
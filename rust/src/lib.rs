@@ -1,5 +1,6 @@
 use pyo3::prelude::*;
 
+mod encoding;
 mod export;
 mod geo;
 mod html;
@@ -24,13 +25,85 @@ fn _leadswarm_native(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(html::detect_cms, m)?)?;
     m.add_function(wrap_pyfunction!(html::detect_tracking, m)?)?;
     m.add_function(wrap_pyfunction!(html::detect_booking_system, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_booking_vendor, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_online_ordering, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_payment_providers, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_review_widgets, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_video_embeds, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_hiring_signals, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_parked_domain, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_placeholder_page, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_error_page, m)?)?;
     m.add_function(wrap_pyfunction!(html::detect_frameworks, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_framework_versions, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_jquery, m)?)?;
     m.add_function(wrap_pyfunction!(html::detect_responsive, m)?)?;
+    m.add_function(wrap_pyfunction!(html::analyze_responsive, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_wordpress_details, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_site_builder, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_page_builder, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_chat_widget, m)?)?;
+    m.add_function(wrap_pyfunction!(html::extract_tracking_ids, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_consent_platform, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_captcha, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_forms, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_mixed_content, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_copyright_year, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_page_language, m)?)?;
+    m.add_function(wrap_pyfunction!(html::seo_audit, m)?)?;
+    m.add_function(wrap_pyfunction!(html::accessibility_audit, m)?)?;
+    m.add_function(wrap_pyfunction!(html::extract_headings, m)?)?;
+    m.add_function(wrap_pyfunction!(html::content_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(html::audit_images, m)?)?;
+    m.add_function(wrap_pyfunction!(html::performance_hints, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_hosting_hints, m)?)?;
+    m.add_function(wrap_pyfunction!(html::modern_practices, m)?)?;
+    m.add_function(wrap_pyfunction!(html::classify_links, m)?)?;
+    m.add_function(wrap_pyfunction!(html::discover_feeds, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_amp, m)?)?;
+    m.add_function(wrap_pyfunction!(html::extract_opening_hours, m)?)?;
+    m.add_function(wrap_pyfunction!(html::extract_addresses, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_locations, m)?)?;
+    m.add_function(wrap_pyfunction!(html::estimate_team_size, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_trust_badges, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_popups, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_legacy_markup, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_map_embed, m)?)?;
+    m.add_function(wrap_pyfunction!(html::detect_ab_testing, m)?)?;
+    m.add_function(wrap_pyfunction!(html::extract_business_ids, m)?)?;
     m.add_function(wrap_pyfunction!(html::analyze_tech_stack, m)?)?;
+    m.add_function(wrap_pyfunction!(html::analyze_tech_stack_batch, m)?)?;
 
     m.add_function(wrap_pyfunction!(scoring::calculate_fit_score, m)?)?;
     m.add_function(wrap_pyfunction!(scoring::calculate_opportunity_score, m)?)?;
     m.add_function(wrap_pyfunction!(scoring::score_prospects_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::get_default_fit_weights, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::explain_fit_score, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::explain_opportunity_score, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::rank_prospects, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::grade_prospects, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::register_scoring_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::get_default_opportunity_weights, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::set_priority_formula, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::get_priority_formula, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::calculate_priority, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::generate_opportunity_notes, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::top_prospects, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::score_deltas, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::compare_weightings, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::reputation_signal, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::load_scoring_config, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::dump_scoring_config, m)?)?;
+    m.add_class::<scoring::ScoringConfig>()?;
+    m.add_function(wrap_pyfunction!(scoring::compile_rules, m)?)?;
+    m.add_class::<scoring::RuleSet>()?;
+    m.add_function(wrap_pyfunction!(scoring::segment_prospects, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::merge_duplicate_prospects, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::prospect_content_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::score_prospects_incremental, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::calculate_confidence, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::score_statistics, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::flag_outliers, m)?)?;
 
     m.add_function(wrap_pyfunction!(geo::fast_cache_key, m)?)?;
     m.add_function(wrap_pyfunction!(geo::haversine_distance, m)?)?;
@@ -40,6 +113,11 @@ fn _leadswarm_native(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(export::serialize_prospects_json, m)?)?;
 
     m.add_function(wrap_pyfunction!(metadata::extract_html_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(metadata::extract_html_metadata_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(metadata::extract_html_metadata_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(metadata::extract_social_profiles, m)?)?;
+    m.add_function(wrap_pyfunction!(metadata::add_social_domains, m)?)?;
+    m.add_function(wrap_pyfunction!(metadata::get_social_domains, m)?)?;
 
     Ok(())
 }
@@ -1,84 +1,1576 @@
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
-use scraper::{Html, Selector};
-use std::sync::LazyLock;
+use rayon::prelude::*;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, RwLock};
+use url::Url;
 
 // Social media domains to match against <a href="..."> links
 static SOCIAL_DOMAINS: &[&str] = &[
     "facebook.com",
     "instagram.com",
     "twitter.com",
+    "x.com",
     "linkedin.com",
     "youtube.com",
     "tiktok.com",
+    "pinterest.com",
+    "threads.net",
+    "wa.me",
+    "api.whatsapp.com",
+    "t.me",
+    "snapchat.com",
+    "nextdoor.com",
+    "houzz.com",
 ];
 
-// Pre-compiled selectors
+// Added at runtime via add_social_domains, on top of SOCIAL_DOMAINS
+static EXTRA_SOCIAL_DOMAINS: LazyLock<RwLock<Vec<String>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+// Profile host → platform key
+static SOCIAL_PLATFORM_HOSTS: &[(&str, &str)] = &[
+    ("facebook.com", "facebook"),
+    ("fb.com", "facebook"),
+    ("fb.me", "facebook"),
+    ("instagram.com", "instagram"),
+    ("twitter.com", "twitter"),
+    ("x.com", "twitter"),
+    ("linkedin.com", "linkedin"),
+    ("youtube.com", "youtube"),
+    ("tiktok.com", "tiktok"),
+    ("pinterest.com", "pinterest"),
+    ("pinterest.com.au", "pinterest"),
+    ("pin.it", "pinterest"),
+    ("threads.net", "threads"),
+    ("wa.me", "whatsapp"),
+    ("whatsapp.com", "whatsapp"),
+    ("t.me", "telegram"),
+    ("telegram.me", "telegram"),
+    ("snapchat.com", "snapchat"),
+    ("nextdoor.com", "nextdoor"),
+    ("nextdoor.com.au", "nextdoor"),
+    ("houzz.com", "houzz"),
+    ("houzz.com.au", "houzz"),
+];
+
+// Alias hosts rewritten when normalizing social links
+static SOCIAL_CANONICAL_HOSTS: &[(&str, &str)] = &[
+    ("fb.com", "facebook.com"),
+    ("twitter.com", "x.com"),
+    ("telegram.me", "t.me"),
+];
+
+// First path segments that are share/intent/content URLs, not profiles
+static SOCIAL_NON_PROFILE_PATHS: &[(&str, &[&str])] = &[
+    (
+        "facebook",
+        &[
+            "sharer", "sharer.php", "share", "share.php", "dialog", "plugins", "tr", "login",
+            "events", "watch", "hashtag", "permalink.php", "story.php",
+        ],
+    ),
+    ("instagram", &["p", "reel", "reels", "explore", "accounts", "tv"]),
+    ("twitter", &["intent", "share", "home", "hashtag", "search", "i"]),
+    ("linkedin", &["sharearticle", "sharing", "feed", "posts", "pulse"]),
+    ("youtube", &["watch", "embed", "results", "playlist", "shorts"]),
+    ("tiktok", &["embed", "share", "tag", "discover"]),
+    ("pinterest", &["pin", "search", "ideas"]),
+    ("threads", &["t", "intent", "search"]),
+    ("houzz", &["photos", "magazine", "products", "discussions"]),
+];
+
+// First path segments of share buttons and tweet intents, dropped from social_links
+static SOCIAL_SHARE_PATHS: &[&str] =
+    &["sharer", "sharer.php", "share", "share.php", "dialog", "intent", "sharearticle", "sharing"];
+
+// Query params that only track where a click came from; `utm_*` is matched by prefix
+static SOCIAL_TRACKING_PARAMS: &[&str] = &[
+    "ref", "ref_src", "ref_url", "fref", "fbclid", "igshid", "igsh", "mibextid", "si", "hl",
+    "locale", "_rdr", "rdr", "__tn__", "__cft__", "s", "t",
+];
+
+// Pre-compiled selectors. `title` also matches SVG <title> tooltips, so
+// candidates are checked with `is_html_title`.
+static HEAD_TITLE_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("head > title").unwrap());
 static TITLE_SEL: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("title").unwrap());
 static META_DESC_SEL: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("meta[name='description']").unwrap());
 static LINK_SEL: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("a[href]").unwrap());
+static H1_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("h1").unwrap());
+static META_CONTENT_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("meta[content]").unwrap());
+static ICON_LINK_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("link[rel][href]").unwrap());
+static INLINE_SCRIPT_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("script:not([src])").unwrap());
+static STYLE_BLOCK_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("style").unwrap());
+static STYLED_CHROME_SEL: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        "header[style], nav[style], button[style], header [style], nav [style], \
+         [class*='btn' i][style], [class*='button' i][style], [class*='header' i][style]",
+    )
+    .unwrap()
+});
+static MASK_ICON_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("link[rel~='mask-icon' i][color]").unwrap());
+static NAV_LINK_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("nav a[href], header a[href]").unwrap());
 
-/// Extract HTML metadata (title, meta_description, social_links) from raw HTML.
+// `navigator.serviceWorker.register(` split across lines or minified
+static SERVICE_WORKER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"serviceWorker\s*\.\s*register\s*\(").unwrap());
+
+// Brand color metas, strongest first: (meta key, source tag)
+static BRAND_COLOR_METAS: &[(&str, &str)] = &[
+    ("theme-color", "theme_color"),
+    ("msapplication-tilecolor", "tile_color"),
+    ("msapplication-navbutton-color", "navbutton_color"),
+];
+// Inline-style colors reported after the declared ones
+const BRAND_STYLE_COLOR_LIMIT: usize = 3;
+// Channels within this of each other read as gray (white and black included)
+const GRAY_CHANNEL_SPREAD: u8 = 16;
+
+static HEX_COLOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"#([0-9a-fA-F]{6}|[0-9a-fA-F]{3})\b").unwrap());
+static RGB_COLOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)rgba?\(\s*(\d{1,3})\s*,?\s*(\d{1,3})\s*,?\s*(\d{1,3})").unwrap()
+});
+// CSS rules aimed at the header, nav and buttons: `selector { declarations }`
+static CHROME_CSS_RULE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)([^{}]*(?:header|nav|btn|button|cta)[^{}]*)\{([^{}]*)\}").unwrap()
+});
+
+// WordPress and friends publish one of these per post; never a blog signal
+const COMMENT_FEED_MARKER: &str = "/comments/feed";
+
+// rel tokens collected into "favicons"; `shortcut icon` matches via "icon"
+static ICON_RELS: &[&str] = &["icon", "apple-touch-icon", "apple-touch-icon-precomposed", "manifest"];
+// iOS renders touch icons declared without `sizes` at 180px
+const APPLE_TOUCH_ICON_DEFAULT_SIZE: u32 = 180;
+
+// A visible paragraph this long can stand in for a missing meta description
+const DESCRIPTION_PARAGRAPH_MIN_CHARS: usize = 80;
+const DESCRIPTION_FALLBACK_MAX_CHARS: usize = 300;
+// content_snippet: the first visible paragraph at least this long, capped
+const SNIPPET_MIN_CHARS: usize = 60;
+const SNIPPET_MAX_CHARS: usize = 280;
+
+// "WordPress 6.4.2", "Drupal 10 (https://www.drupal.org)", "Elementor 3.18.3; features: ..."
+static GENERATOR_VERSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(.+?)\s+v?(\d+(?:\.\d+)*)\b").unwrap());
+
+// Open Graph properties reported under "open_graph", keyed without the "og:" prefix
+static OPEN_GRAPH_KEYS: &[&str] = &["title", "description", "image", "type", "url", "site_name"];
+// Twitter/X card tags reported under "twitter_card", keyed without the "twitter:" prefix
+static TWITTER_CARD_KEYS: &[&str] = &["card", "title", "description", "image", "site"];
+
+static TIME_SEL: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("time[datetime]").unwrap());
+
+// Meta keys for publish/modified dates, strongest first: (key, date_source)
+static PUBLISHED_META_KEYS: &[(&str, &str)] = &[
+    ("article:published_time", "open_graph"),
+    ("date", "meta"),
+    ("dc.date", "meta"),
+    ("dcterms.created", "meta"),
+];
+static MODIFIED_META_KEYS: &[(&str, &str)] = &[
+    ("article:modified_time", "open_graph"),
+    ("og:updated_time", "open_graph"),
+    ("dcterms.modified", "meta"),
+    ("last-modified", "meta"),
+];
+
+// 2024-03-05, 2024-03-05T09:30, 2024-03-05 09:30:12.123+1000, 2024-03-05T09:30:12Z
+static ISO_DATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(\d{4})-(\d{2})-(\d{2})(?:[t ](\d{2}):(\d{2})(?::(\d{2})(?:[.,]\d+)?)?\s*(z|[+-]\d{2}:?\d{2})?)?$",
+    )
+    .unwrap()
+});
+static SLASH_DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{4})/(\d{1,2})/(\d{1,2})$").unwrap());
+// "5 March 2024", "5th Mar, 2024"
+static DAY_MONTH_YEAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(\d{1,2})(?:st|nd|rd|th)?\s+([a-z]{3,9})\.?,?\s+(\d{4})$").unwrap()
+});
+// "March 5, 2024", "Mar 5th 2024"
+static MONTH_DAY_YEAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^([a-z]{3,9})\.?\s+(\d{1,2})(?:st|nd|rd|th)?,?\s+(\d{4})$").unwrap()
+});
+static MONTH_NAMES: &[&str] =
+    &["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+// JSON-LD @types read as the site's own organization for "structured"
+static ORGANIZATION_TYPES: &[&str] = &[
+    "Organization", "Corporation", "LocalBusiness", "ProfessionalService", "Store",
+    "HomeAndConstructionBusiness", "GeneralContractor", "Plumber", "Electrician", "HVACBusiness",
+    "RoofingContractor", "AutoRepair", "LegalService", "Attorney", "AccountingService",
+    "FinancialService", "RealEstateAgent", "MedicalBusiness", "Dentist", "MedicalClinic",
+    "HealthAndBeautyBusiness", "BeautySalon", "HairSalon", "DaySpa", "FoodEstablishment",
+    "Restaurant", "CafeOrCoffeeShop", "EducationalOrganization", "NGO",
+];
+
+/// A social profile link and the handle (or numeric page id) it points at.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct SocialProfile {
+    pub url: String,
+    pub handle_or_id: String,
+}
+
+/// Whether `host` is `domain` or a subdomain of it (www., m., en-gb.).
+fn host_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|rest| rest.ends_with('.'))
+}
+
+/// The (domain, platform) entry of SOCIAL_PLATFORM_HOSTS that `host` belongs to.
+fn platform_for_host(host: &str) -> Option<(&'static str, &'static str)> {
+    let host = host.to_lowercase();
+    SOCIAL_PLATFORM_HOSTS.iter().copied().find(|(domain, _)| host_matches(&host, domain))
+}
+
+/// Parse a profile URL into (platform, handle_or_id).
 ///
-/// Returns a dict with keys:
-///   - "title": str | None
-///   - "meta_description": str | None
-///   - "social_links": list[str]
+/// Share buttons, intents and post/video links return None.
+pub(crate) fn parse_social_profile(href: &str) -> Option<(&'static str, String)> {
+    let href = href.trim();
+    let absolute = if href.starts_with("//") { format!("https:{href}") } else { href.to_string() };
+    let url = Url::parse(&absolute).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let platform = platform_for_host(url.host_str()?)?.1;
+
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|s| s.filter(|seg| !seg.is_empty()).collect())
+        .unwrap_or_default();
+    let first = segments.first().map(|s| s.to_lowercase());
+    if let Some(first) = first.as_deref() {
+        let excluded = SOCIAL_NON_PROFILE_PATHS
+            .iter()
+            .filter(|(p, _)| *p == platform)
+            .any(|(_, paths)| paths.contains(&first));
+        if excluded {
+            return None;
+        }
+    }
+
+    let query_id = || {
+        url.query_pairs()
+            .find(|(k, _)| k == "id")
+            .map(|(_, v)| v.into_owned())
+    };
+    let handle = match (platform, first.as_deref()) {
+        // facebook.com/pages/Joes-Plumbing/123456789, /people/Name/1000…
+        ("facebook", Some("pages" | "people")) => segments
+            .iter()
+            .rev()
+            .find(|s| s.chars().all(|c| c.is_ascii_digit()))
+            .map(|s| s.to_string())
+            .or_else(|| segments.get(1).map(|s| s.to_string())),
+        ("facebook", Some("profile.php")) => query_id(),
+        ("facebook", Some("pg")) => segments.get(1).map(|s| s.to_string()),
+        ("instagram", Some("stories")) => segments.get(1).map(|s| s.to_string()),
+        ("linkedin", Some("company" | "in" | "school" | "showcase")) => {
+            segments.get(1).map(|s| s.to_string())
+        }
+        ("youtube", Some("channel" | "c" | "user")) => segments.get(1).map(|s| s.to_string()),
+        // api.whatsapp.com/send?phone=61400000000
+        ("whatsapp", Some("send")) => url
+            .query_pairs()
+            .find(|(k, _)| k == "phone")
+            .map(|(_, v)| v.into_owned()),
+        ("snapchat", Some("add")) => segments.get(1).map(|s| s.to_string()),
+        ("nextdoor", Some("pages")) => segments.get(1).map(|s| s.to_string()),
+        // houzz.com.au/professionals/plumbers/joes-plumbing-pfvwau-pf~123
+        ("houzz", Some("professionals" | "pro")) => segments.last().map(|s| s.to_string()),
+        _ => segments.first().map(|s| s.to_string()),
+    }?;
+
+    let handle = handle.trim_start_matches('@').to_string();
+    (!handle.is_empty()).then_some((platform, handle))
+}
+
+/// Canonical form of a social link: https, the bare platform host (no `www.`,
+/// `m.` or locale prefix), no tracking params, fragment or trailing slash.
+///
+/// Share/intent URLs return None; links that aren't absolute http(s) are
+/// returned unchanged.
+fn normalize_social_link(link: &str) -> Option<String> {
+    let trimmed = link.trim();
+    let absolute =
+        if trimmed.starts_with("//") { format!("https:{trimmed}") } else { trimmed.to_string() };
+    let Ok(mut url) = Url::parse(&absolute) else { return Some(link.to_string()) };
+    if !matches!(url.scheme(), "http" | "https") {
+        return Some(link.to_string());
+    }
+
+    let host = url.host_str().unwrap_or("").to_lowercase();
+    if let Some((domain, _)) = platform_for_host(&host) {
+        let first = url
+            .path_segments()
+            .and_then(|mut s| s.find(|seg| !seg.is_empty()))
+            .map(|seg| seg.to_lowercase());
+        if first.is_some_and(|f| SOCIAL_SHARE_PATHS.contains(&f.as_str())) {
+            return None;
+        }
+        let domain = SOCIAL_CANONICAL_HOSTS
+            .iter()
+            .find(|(alias, _)| *alias == domain)
+            .map_or(domain, |(_, canonical)| *canonical);
+        url.set_host(Some(domain)).ok()?;
+        url.set_scheme("https").ok()?;
+    } else {
+        url.set_host(Some(&host)).ok()?;
+    }
+
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| {
+            let k = k.to_lowercase();
+            !k.starts_with("utm_") && !SOCIAL_TRACKING_PARAMS.contains(&k.as_str())
+        })
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    url.set_fragment(None);
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(&path);
+
+    let normalized = url.to_string();
+    Some(match url.query() {
+        Some(_) => normalized,
+        None => normalized.trim_end_matches('/').to_string(),
+    })
+}
+
+/// Built-in social domains followed by any added with `add_social_domains`.
+fn social_domains() -> Vec<String> {
+    let extra = EXTRA_SOCIAL_DOMAINS.read().unwrap_or_else(|e| e.into_inner());
+    SOCIAL_DOMAINS.iter().map(|d| d.to_string()).chain(extra.iter().cloned()).collect()
+}
+
+/// Whether `href` points at one of `domains`. Absolute links must be on the
+/// domain or a subdomain of it; relative ones (`/go/facebook.com`) need the
+/// domain to start a word so `x.com` doesn't match `dropbox.com`.
+fn is_social_href(href: &str, domains: &[String]) -> bool {
+    let trimmed = href.trim();
+    let absolute =
+        if trimmed.starts_with("//") { format!("https:{trimmed}") } else { trimmed.to_string() };
+    if let Some(host) = Url::parse(&absolute).ok().and_then(|u| u.host_str().map(str::to_lowercase))
+    {
+        return domains.iter().any(|d| host_matches(&host, d));
+    }
+    let lower = trimmed.to_lowercase();
+    domains.iter().any(|d| {
+        lower.match_indices(d.as_str()).any(|(i, _)| {
+            lower[..i].chars().next_back().is_none_or(|c| !c.is_ascii_alphanumeric() && c != '-')
+        })
+    })
+}
+
+/// Platform key for a normalized social link, by host: built-in platforms
+/// (WhatsApp and Telegram included, as contact channels), else the matching
+/// runtime-added domain. Relative links have no platform.
+fn social_link_platform(link: &str, domains: &[String]) -> Option<String> {
+    let host = Url::parse(link).ok()?.host_str()?.to_lowercase();
+    platform_for_host(&host)
+        .map(|(_, platform)| platform.to_string())
+        .or_else(|| domains.iter().find(|d| host_matches(&host, d)).cloned())
+}
+
+/// The normalized social link for an `<a href>` or JSON-LD `sameAs` value,
+/// resolved against `base` when given. None for non-social, share and
+/// `javascript:`/`#` hrefs.
+fn social_link_for_href(href: &str, base: Option<&Url>, domains: &[String]) -> Option<String> {
+    // `javascript:window.open('https://facebook.com/…')` and `#` anchors
+    // mention a social domain without linking to it
+    let trimmed = href.trim();
+    if trimmed.starts_with('#')
+        || trimmed.get(..11).is_some_and(|s| s.eq_ignore_ascii_case("javascript:"))
+    {
+        return None;
+    }
+    if !is_social_href(href, domains) {
+        return None;
+    }
+    let link = match base {
+        Some(b) => match b.join(trimmed) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url.to_string(),
+            _ => return None,
+        },
+        None => href.to_string(),
+    };
+    normalize_social_link(&link)
+}
+
+/// A deduplicated social link awaiting the per-platform cap.
+struct SocialCandidate {
+    link: String,
+    platform: Option<String>,
+    /// Linked from the header, footer or nav (or declared in JSON-LD) at
+    /// least once, where profile links live; in-content links are mostly
+    /// share buttons and embeds.
+    in_chrome: bool,
+}
+
+fn in_site_chrome(el: &ElementRef) -> bool {
+    el.ancestors().filter_map(ElementRef::wrap).any(|a| {
+        let value = a.value();
+        matches!(value.name(), "header" | "footer" | "nav")
+            || matches!(value.attr("role"), Some("banner" | "contentinfo" | "navigation"))
+    })
+}
+
+/// Keep at most `max_per_platform` links per platform, preferring site-chrome
+/// links and then shorter (more profile-like) paths. Document order is kept;
+/// links without a platform are never dropped.
+fn cap_social_links(
+    candidates: &[SocialCandidate],
+    max_per_platform: Option<usize>,
+) -> Vec<&SocialCandidate> {
+    let Some(max) = max_per_platform else { return candidates.iter().collect() };
+    let path_len = |link: &str| Url::parse(link).map_or(link.len(), |u| u.path().len());
+
+    let mut by_platform: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, candidate) in candidates.iter().enumerate() {
+        if let Some(platform) = candidate.platform.as_deref() {
+            by_platform.entry(platform).or_default().push(i);
+        }
+    }
+    let mut dropped: HashSet<usize> = HashSet::new();
+    for indices in by_platform.values_mut() {
+        // Stable sort keeps document order between equally profile-like links
+        indices.sort_by_key(|&i| (!candidates[i].in_chrome, path_len(&candidates[i].link)));
+        dropped.extend(indices.iter().skip(max));
+    }
+    candidates.iter().enumerate().filter(|(i, _)| !dropped.contains(i)).map(|(_, c)| c).collect()
+}
+
+/// Add domains (e.g. `"yelp.com.au"`) that `extract_html_metadata` treats as
+/// social links, for verticals whose customers live on niche platforms.
+///
+/// Applies process-wide; schemes, `www.` and paths are stripped and
+/// duplicates ignored.
 #[pyfunction]
-pub fn extract_html_metadata(py: Python<'_>, html: &str) -> PyResult<PyObject> {
-    let dict = PyDict::new(py);
+pub fn add_social_domains(domains: Vec<String>) {
+    let mut extra = EXTRA_SOCIAL_DOMAINS.write().unwrap_or_else(|e| e.into_inner());
+    for domain in domains {
+        let domain = domain.trim().to_lowercase();
+        let domain = domain.split_once("://").map_or(domain.as_str(), |(_, rest)| rest);
+        let domain = domain.split('/').next().unwrap_or("");
+        let domain = domain.strip_prefix("www.").unwrap_or(domain);
+        if domain.is_empty()
+            || SOCIAL_DOMAINS.contains(&domain)
+            || extra.iter().any(|d| d == domain)
+        {
+            continue;
+        }
+        extra.push(domain.to_string());
+    }
+}
+
+/// The social domains currently matched: built-ins, then runtime additions.
+#[pyfunction]
+pub fn get_social_domains() -> Vec<String> {
+    social_domains()
+}
 
+/// Extract social profiles keyed by platform, each with its handle or page id.
+///
+/// Share/intent URLs are skipped and profiles are deduplicated by handle.
+#[pyfunction]
+#[pyo3(signature = (html, max_bytes=Some(crate::html::DEFAULT_MAX_BYTES)))]
+pub fn extract_social_profiles(
+    html: &str,
+    max_bytes: Option<usize>,
+) -> PyResult<HashMap<String, Vec<SocialProfile>>> {
+    let html = crate::html::limit_input(html, max_bytes)?;
+    let mut profiles: HashMap<String, Vec<SocialProfile>> = HashMap::new();
     if html.is_empty() {
-        dict.set_item("title", py.None())?;
-        dict.set_item("meta_description", py.None())?;
-        dict.set_item("social_links", PyList::empty(py))?;
-        return Ok(dict.into());
+        return Ok(profiles);
     }
 
     let document = Html::parse_document(html);
+    for element in document.select(&LINK_SEL) {
+        let Some(href) = element.value().attr("href") else { continue };
+        let Some((platform, handle)) = parse_social_profile(href) else { continue };
+        let entries = profiles.entry(platform.to_string()).or_default();
+        if !entries.iter().any(|p| p.handle_or_id.eq_ignore_ascii_case(&handle)) {
+            entries.push(SocialProfile {
+                url: href.trim().to_string(),
+                handle_or_id: handle,
+            });
+        }
+    }
+
+    Ok(profiles)
+}
+
+const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+/// An HTML-namespace `<title>` outside any inline `<svg>`: icon sprites
+/// put "Menu icon" or "Logo" titles ahead of the real one.
+fn is_html_title(el: &ElementRef) -> bool {
+    &*el.value().name.ns == HTML_NAMESPACE
+        && !el.ancestors().filter_map(ElementRef::wrap).any(|a| a.value().name() == "svg")
+}
+
+/// The document's `<title>`: the head's, else the first non-SVG one in the body.
+fn document_title<'a>(document: &'a Html) -> Option<ElementRef<'a>> {
+    document
+        .select(&HEAD_TITLE_SEL)
+        .find(is_html_title)
+        .or_else(|| document.select(&TITLE_SEL).find(is_html_title))
+}
+
+/// Non-empty `<meta>` contents keyed by lowercased `property` or `name`, in
+/// document order. Both attributes are read because `name="og:title"` is a
+/// common misspelling of `property="og:title"`.
+fn meta_tags(document: &Html) -> HashMap<String, Vec<String>> {
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    for el in document.select(&META_CONTENT_SEL) {
+        let value = el.value();
+        let Some(key) = value.attr("property").or_else(|| value.attr("name")) else { continue };
+        let content = value.attr("content").unwrap_or("").trim();
+        if !content.is_empty() {
+            tags.entry(key.trim().to_lowercase()).or_default().push(content.to_string());
+        }
+    }
+    tags
+}
+
+fn first_meta<'a>(tags: &'a HashMap<String, Vec<String>>, key: &str) -> Option<&'a str> {
+    tags.get(key).and_then(|v| v.first()).map(String::as_str)
+}
+
+/// `<prefix>:<key>` meta values for `keys`, None when absent.
+/// Image URLs are resolved against `base`.
+fn prefixed_meta(
+    tags: &HashMap<String, Vec<String>>,
+    prefix: &str,
+    keys: &[&'static str],
+    base: Option<&Url>,
+) -> Vec<(&'static str, Option<String>)> {
+    keys.iter()
+        .map(|key| {
+            let value = first_meta(tags, &format!("{prefix}:{key}")).map(|v| match *key {
+                "image" => crate::html::resolve_href(base, v),
+                _ => v.to_string(),
+            });
+            (*key, value)
+        })
+        .collect()
+}
+
+/// The X handle from twitter:site, without its leading `@`. Some sites put a
+/// profile URL here instead, so only the last path segment is kept.
+fn twitter_handle(site: &str) -> Option<String> {
+    let handle = site.trim().trim_end_matches('/').rsplit('/').next()?;
+    let handle = handle.trim_start_matches('@');
+    (!handle.is_empty()).then(|| handle.to_string())
+}
 
-    // Extract title
-    let title = document
-        .select(&TITLE_SEL)
-        .next()
-        .map(|el| el.text().collect::<String>().trim().to_string())
-        .filter(|s| !s.is_empty());
+/// Normalize a date string to ISO-8601: `YYYY-MM-DD`, or
+/// `YYYY-MM-DDTHH:MM:SS` plus the offset (`Z` or `±HH:MM`) when one was given.
+fn parse_date(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let ymd = |y: &str, m: u32, d: &str| -> Option<String> {
+        let d: u32 = d.parse().ok()?;
+        ((1..=12).contains(&m) && (1..=31).contains(&d)).then(|| format!("{y}-{m:02}-{d:02}"))
+    };
+    let month = |name: &str| {
+        let name = name.to_lowercase();
+        MONTH_NAMES.iter().position(|m| name.starts_with(m)).map(|i| i as u32 + 1)
+    };
 
-    match title {
-        Some(ref t) => dict.set_item("title", t)?,
-        None => dict.set_item("title", py.None())?,
+    if let Some(c) = ISO_DATE_RE.captures(raw) {
+        let date = ymd(&c[1], c[2].parse().ok()?, &c[3])?;
+        let (Some(hour), Some(minute)) = (c.get(4), c.get(5)) else { return Some(date) };
+        let (hour, minute): (u32, u32) = (hour.as_str().parse().ok()?, minute.as_str().parse().ok()?);
+        let second: u32 = c.get(6).map_or(Ok(0), |s| s.as_str().parse()).ok()?;
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+        let offset = match c.get(7).map(|o| o.as_str().replace(':', "")) {
+            Some(o) if o.eq_ignore_ascii_case("z") => "Z".to_string(),
+            Some(o) => format!("{}:{}", &o[..3], &o[3..]),
+            None => String::new(),
+        };
+        return Some(format!("{date}T{hour:02}:{minute:02}:{second:02}{offset}"));
+    }
+    if let Some(c) = SLASH_DATE_RE.captures(raw) {
+        return ymd(&c[1], c[2].parse().ok()?, &c[3]);
+    }
+    if let Some(c) = DAY_MONTH_YEAR_RE.captures(raw) {
+        return ymd(&c[3], month(&c[2])?, &c[1]);
+    }
+    if let Some(c) = MONTH_DAY_YEAR_RE.captures(raw) {
+        return ymd(&c[3], month(&c[1])?, &c[2]);
     }
+    None
+}
 
-    // Extract meta description
-    let meta_desc = document
-        .select(&META_DESC_SEL)
-        .next()
-        .and_then(|el| el.value().attr("content").map(|s| s.to_string()))
-        .filter(|s| !s.is_empty());
+/// Publish/modified date candidates in priority order as (raw, date_source):
+/// meta tags and JSON-LD before visible `<time>` elements.
+fn date_candidates<'a>(
+    tags: &'a HashMap<String, Vec<String>>,
+    json_ld: &[serde_json::Map<String, serde_json::Value>],
+    document: &'a Html,
+    modified: bool,
+) -> Vec<(String, &'static str)> {
+    let (meta_keys, json_key, itemprop) = if modified {
+        (MODIFIED_META_KEYS, "dateModified", Some("datemodified"))
+    } else {
+        (PUBLISHED_META_KEYS, "datePublished", None)
+    };
+    let meta = |source: &'static str| {
+        meta_keys
+            .iter()
+            .filter(move |(_, s)| *s == source)
+            .filter_map(|(k, s)| first_meta(tags, k).map(|v| (v.to_string(), *s)))
+    };
+    // OG tags outrank JSON-LD, which outranks plain meta
+    let mut candidates: Vec<(String, &'static str)> = meta("open_graph").collect();
+    for object in json_ld {
+        if let Some(value) = object.get(json_key).and_then(|v| v.as_str()) {
+            candidates.push((value.to_string(), "json_ld"));
+        }
+    }
+    candidates.extend(meta("meta"));
+    // Visible dates: the first <time> is the publish date; a modified date
+    // needs to say so with itemprop
+    for el in document.select(&TIME_SEL) {
+        let prop = el.value().attr("itemprop").map(str::to_lowercase);
+        if itemprop.is_none() || prop.as_deref() == itemprop {
+            candidates.push((el.value().attr("datetime").unwrap_or("").to_string(), "time"));
+            break;
+        }
+    }
+    candidates.retain(|(raw, _)| !raw.trim().is_empty());
+    candidates
+}
+
+/// Whitespace-collapsed text of the first visible paragraph with at least
+/// `min_chars` characters.
+fn first_paragraph(document: &Html, min_chars: usize) -> Option<String> {
+    crate::html::visible_paragraphs(document)
+        .map(|p| p.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|text| text.chars().count() >= min_chars)
+}
 
-    match meta_desc {
-        Some(ref d) => dict.set_item("meta_description", d)?,
-        None => dict.set_item("meta_description", py.None())?,
+/// Cut `text` to at most `max_chars`, ending on a word boundary with "…".
+fn truncate_at_word(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
     }
+    let cut: String = text.chars().take(max_chars - 1).collect();
+    let cut = match cut.rfind(' ') {
+        Some(i) if i > 0 => &cut[..i],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_whitespace() || ",;:-".contains(c)))
+}
 
-    // Extract social links
-    let mut social_links: Vec<String> = Vec::new();
+/// Organization / WebSite fields declared in JSON-LD.
+#[derive(Debug, Clone, Default, IntoPyObject)]
+pub struct StructuredData {
+    pub org_name: Option<String>,
+    pub logo_url: Option<String>,
+    /// Every http(s) `sameAs` URL, as declared.
+    pub same_as: Vec<String>,
+    pub telephone: Option<String>,
+    pub search_action_url: Option<String>,
+}
 
-    for element in document.select(&LINK_SEL) {
-        if let Some(href) = element.value().attr("href") {
-            for domain in SOCIAL_DOMAINS {
-                if href.contains(domain) && !social_links.iter().any(|l| l == href) {
-                    social_links.push(href.to_string());
-                    break;
+/// A JSON-LD node's `@type`, which may be a single string or a list.
+fn json_ld_types(object: &serde_json::Map<String, serde_json::Value>) -> Vec<&str> {
+    match object.get("@type") {
+        Some(serde_json::Value::String(t)) => vec![t.as_str()],
+        Some(serde_json::Value::Array(items)) => items.iter().filter_map(|t| t.as_str()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// A JSON-LD value that is either a URL string or an object carrying one
+/// under `key` (ImageObject `url`, EntryPoint `urlTemplate`).
+fn json_ld_url(value: &serde_json::Value, key: &str) -> Option<String> {
+    let url = match value {
+        serde_json::Value::String(s) => s.as_str(),
+        serde_json::Value::Object(map) => map.get(key)?.as_str()?,
+        serde_json::Value::Array(items) => return items.iter().find_map(|v| json_ld_url(v, key)),
+        _ => return None,
+    };
+    let url = url.trim();
+    (!url.is_empty()).then(|| url.to_string())
+}
+
+/// The first Organization-like node's name, logo, sameAs and telephone, plus
+/// the WebSite SearchAction target. Blocks that aren't valid JSON are skipped
+/// by `json_ld_objects`.
+fn structured_data(
+    json_ld: &[serde_json::Map<String, serde_json::Value>],
+    base: Option<&Url>,
+) -> StructuredData {
+    let text = |object: &serde_json::Map<String, serde_json::Value>, key: &str| {
+        let value = object.get(key)?.as_str()?.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!value.is_empty()).then_some(value)
+    };
+
+    let mut data = StructuredData::default();
+    if let Some(org) = json_ld
+        .iter()
+        .find(|o| json_ld_types(o).iter().any(|t| ORGANIZATION_TYPES.contains(t)))
+    {
+        data.org_name = text(org, "name").or_else(|| text(org, "legalName"));
+        data.logo_url = org
+            .get("logo")
+            .and_then(|logo| json_ld_url(logo, "url"))
+            .map(|url| crate::html::resolve_href(base, &url));
+        data.telephone = text(org, "telephone");
+        let same_as: Vec<&serde_json::Value> = match org.get("sameAs") {
+            Some(serde_json::Value::Array(items)) => items.iter().collect(),
+            Some(value) => vec![value],
+            None => Vec::new(),
+        };
+        for url in same_as.into_iter().filter_map(|v| v.as_str()).map(str::trim) {
+            let is_http = Url::parse(url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+            if is_http && !data.same_as.iter().any(|s| s == url) {
+                data.same_as.push(url.to_string());
+            }
+        }
+    }
+
+    data.search_action_url = json_ld
+        .iter()
+        .filter(|o| json_ld_types(o).contains(&"WebSite"))
+        .filter_map(|site| site.get("potentialAction"))
+        .flat_map(|action| match action {
+            serde_json::Value::Array(items) => items.iter().collect(),
+            value => vec![value],
+        })
+        .filter_map(|action| action.as_object())
+        .filter(|action| json_ld_types(action).contains(&"SearchAction"))
+        .find_map(|action| action.get("target").and_then(|t| json_ld_url(t, "urlTemplate")))
+        .map(|url| crate::html::resolve_href(base, &url));
+
+    data
+}
+
+/// A BCP 47 language tag split into its language and region subtags.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct LanguageTag {
+    /// Canonical casing: "en-AU", "zh-Hant-TW".
+    pub tag: String,
+    pub language: String,
+    pub region: Option<String>,
+}
+
+/// Parse "en-au", "EN_AU" (og:locale style) or "zh-hant-tw". None when the
+/// primary subtag isn't 2–3 letters or a later subtag isn't alphanumeric.
+fn parse_language_tag(raw: &str) -> Option<LanguageTag> {
+    let subtags: Vec<&str> = raw.trim().split(['-', '_']).collect();
+    let language = subtags.first()?.to_lowercase();
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut parts = vec![language.clone()];
+    let mut region = None;
+    for (i, subtag) in subtags.iter().enumerate().skip(1) {
+        if subtag.is_empty() || subtag.len() > 8 || !subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return None;
+        }
+        let is_alpha = subtag.chars().all(|c| c.is_ascii_alphabetic());
+        let is_digit = subtag.chars().all(|c| c.is_ascii_digit());
+        let is_region = (subtag.len() == 2 && is_alpha) || (subtag.len() == 3 && is_digit);
+        let part = if region.is_none() && is_region {
+            let r = subtag.to_uppercase();
+            region = Some(r.clone());
+            r
+        } else if i == 1 && subtag.len() == 4 && is_alpha {
+            // Script: "Hant"
+            let lower = subtag.to_lowercase();
+            lower[..1].to_uppercase() + &lower[1..]
+        } else {
+            subtag.to_lowercase()
+        };
+        parts.push(part);
+    }
+    Some(LanguageTag { tag: parts.join("-"), language, region })
+}
+
+/// An RSS/Atom feed declared with `<link rel="alternate">`.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct Feed {
+    pub title: Option<String>,
+    pub href: String,
+    /// "application/rss+xml" or "application/atom+xml".
+    #[pyo3(item("type"))]
+    pub feed_type: String,
+}
+
+fn collect_feeds(document: &Html, base: Option<&Url>, exclude_comment_feeds: bool) -> Vec<Feed> {
+    let mut feeds: Vec<Feed> = Vec::new();
+    for el in document.select(&crate::html::FEED_LINK_SEL) {
+        let value = el.value();
+        let href = value.attr("href").unwrap_or("").trim();
+        if href.is_empty() {
+            continue;
+        }
+        let href = crate::html::resolve_href(base, href);
+        if exclude_comment_feeds && href.to_lowercase().contains(COMMENT_FEED_MARKER) {
+            continue;
+        }
+        if feeds.iter().any(|f| f.href == href) {
+            continue;
+        }
+        let title = value.attr("title").map(str::trim).filter(|t| !t.is_empty()).map(String::from);
+        let feed_type = value.attr("type").unwrap_or("").trim().to_lowercase();
+        feeds.push(Feed { title, href, feed_type });
+    }
+    feeds
+}
+
+/// Whether a header or nav link points into a `/blog` section.
+fn has_blog_nav_link(document: &Html) -> bool {
+    document.select(&NAV_LINK_SEL).any(|el| {
+        let href = el.value().attr("href").unwrap_or("").to_lowercase();
+        let path = href.split(['?', '#']).next().unwrap_or("");
+        let path = match path.split_once("://") {
+            Some((_, rest)) => rest.split_once('/').map_or("", |(_, p)| p),
+            None => path,
+        };
+        path.split('/').any(|segment| segment == "blog")
+    })
+}
+
+/// Progressive web app setup. Always reported, with false/None when absent.
+#[derive(Debug, Clone, Default, IntoPyObject)]
+pub struct PwaInfo {
+    pub manifest_url: Option<String>,
+    /// `<meta name="apple-mobile-web-app-capable" content="yes">`.
+    pub apple_mobile_web_app_capable: bool,
+    /// `navigator.serviceWorker.register(...)` in an inline script.
+    pub service_worker_hint: bool,
+}
+
+fn has_service_worker_registration(document: &Html) -> bool {
+    document
+        .select(&INLINE_SCRIPT_SEL)
+        .filter(|el| {
+            crate::html::EXECUTABLE_SCRIPT_TYPES.contains(&crate::html::script_type(el).as_str())
+        })
+        .any(|el| SERVICE_WORKER_RE.is_match(&el.text().collect::<String>()))
+}
+
+/// A parsed `<meta name="viewport">`.
+#[derive(Debug, Clone, Default, IntoPyObject)]
+pub struct Viewport {
+    pub content: String,
+    /// "device-width" or a pixel count, as written.
+    pub width: Option<String>,
+    pub initial_scale: Option<f64>,
+    pub user_scalable: Option<bool>,
+    pub maximum_scale: Option<f64>,
+    /// `user-scalable=no` or `maximum-scale=1` (or lower): pinch-zoom is
+    /// disabled, a WCAG 1.4.4 failure.
+    pub blocks_zoom: bool,
+    /// More than one viewport meta; browsers apply the last, as reported here.
+    pub multiple_declared: bool,
+}
+
+/// Parse a viewport `content` value. Browsers accept `;` as well as `,`
+/// between properties and ignore unknown keys.
+pub(crate) fn parse_viewport(content: &str) -> Viewport {
+    let mut viewport = Viewport { content: content.trim().to_string(), ..Default::default() };
+    for pair in content.split([',', ';']) {
+        let Some((key, value)) = pair.split_once('=') else { continue };
+        let key = key.trim().to_lowercase();
+        let value = value.trim().trim_matches(['"', '\'']).to_lowercase();
+        match key.as_str() {
+            "width" if !value.is_empty() => viewport.width = Some(value),
+            "initial-scale" => viewport.initial_scale = value.parse().ok(),
+            "maximum-scale" => viewport.maximum_scale = value.parse().ok(),
+            "user-scalable" => {
+                viewport.user_scalable = match value.as_str() {
+                    "no" | "0" | "false" => Some(false),
+                    "yes" | "1" | "true" => Some(true),
+                    _ => None,
                 }
             }
+            _ => {}
+        }
+    }
+    viewport.blocks_zoom = viewport.user_scalable == Some(false)
+        || viewport.maximum_scale.is_some_and(|scale| scale <= 1.0);
+    viewport
+}
+
+/// A candidate brand color as lowercase `#rrggbb`.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct BrandColor {
+    pub color: String,
+    /// "theme_color", "tile_color", "navbutton_color", "mask_icon",
+    /// "inline_style" or "stylesheet".
+    pub source: &'static str,
+}
+
+/// Every `#rgb`, `#rrggbb` and `rgb()`/`rgba()` color in `css`, as RGB.
+fn css_colors(css: &str) -> Vec<(u8, u8, u8)> {
+    let mut colors: Vec<(usize, (u8, u8, u8))> = Vec::new();
+    for caps in HEX_COLOR_RE.captures_iter(css) {
+        let hex = &caps[1];
+        let hex: String = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect(),
+            _ => hex.to_string(),
+        };
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        if let (Some(r), Some(g), Some(b)) = (channel(0), channel(2), channel(4)) {
+            colors.push((caps.get(0).map_or(0, |m| m.start()), (r, g, b)));
+        }
+    }
+    for caps in RGB_COLOR_RE.captures_iter(css) {
+        let channel = |i: usize| caps[i].parse::<u16>().ok().filter(|c| *c <= 255).map(|c| c as u8);
+        if let (Some(r), Some(g), Some(b)) = (channel(1), channel(2), channel(3)) {
+            colors.push((caps.get(0).map_or(0, |m| m.start()), (r, g, b)));
+        }
+    }
+    colors.sort_by_key(|(position, _)| *position);
+    colors.into_iter().map(|(_, rgb)| rgb).collect()
+}
+
+fn hex_color((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn is_grayscale((r, g, b): (u8, u8, u8)) -> bool {
+    r.max(g).max(b) - r.min(g).min(b) <= GRAY_CHANNEL_SPREAD
+}
+
+/// Declared brand colors (theme-color, tile and navbutton metas, the Safari
+/// mask-icon color), then the most frequent non-gray colors styled onto the
+/// header, nav and buttons. Deduplicated on the normalized hex.
+fn brand_colors(document: &Html, tags: &HashMap<String, Vec<String>>) -> Vec<BrandColor> {
+    let mut colors: Vec<BrandColor> = Vec::new();
+    let mut push = |color: String, source: &'static str| {
+        if !colors.iter().any(|c| c.color == color) {
+            colors.push(BrandColor { color, source });
+        }
+    };
+
+    let mask_icon = document.select(&MASK_ICON_SEL).next().and_then(|el| el.value().attr("color"));
+    let declared = BRAND_COLOR_METAS
+        .iter()
+        .map(|(key, source)| (first_meta(tags, key), *source))
+        .chain([(mask_icon, "mask_icon")]);
+    for (value, source) in declared {
+        if let Some(rgb) = value.and_then(|v| css_colors(v).into_iter().next()) {
+            push(hex_color(rgb), source);
+        }
+    }
+
+    // (color, source) → count, remembering first-seen order for ties
+    let mut counts: Vec<((u8, u8, u8), &'static str, usize)> = Vec::new();
+    let mut tally = |css: &str, source: &'static str| {
+        for rgb in css_colors(css).into_iter().filter(|rgb| !is_grayscale(*rgb)) {
+            match counts.iter_mut().find(|(c, _, _)| *c == rgb) {
+                Some((_, _, count)) => *count += 1,
+                None => counts.push((rgb, source, 1)),
+            }
+        }
+    };
+    for el in document.select(&STYLED_CHROME_SEL) {
+        tally(el.value().attr("style").unwrap_or(""), "inline_style");
+    }
+    for style in document.select(&STYLE_BLOCK_SEL) {
+        let css = style.text().collect::<String>();
+        for rule in CHROME_CSS_RULE_RE.captures_iter(&css) {
+            tally(&rule[2], "stylesheet");
+        }
+    }
+    // Stable sort keeps first-seen order between equally frequent colors
+    counts.sort_by_key(|(_, _, count)| std::cmp::Reverse(*count));
+    for (rgb, source, _) in counts.into_iter().take(BRAND_STYLE_COLOR_LIMIT) {
+        push(hex_color(rgb), source);
+    }
+    colors
+}
+
+/// A `<meta name="generator">` value split into product name and version.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct Generator {
+    pub name: String,
+    pub version: String,
+}
+
+pub(crate) fn parse_generator(content: &str) -> Option<Generator> {
+    let caps = GENERATOR_VERSION_RE.captures(content)?;
+    Some(Generator { name: caps[1].trim().to_string(), version: caps[2].to_string() })
+}
+
+/// An icon or web app manifest declared with `<link rel=...>`.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct Favicon {
+    pub url: String,
+    pub rel: String,
+    pub sizes: Option<String>,
+    /// `data:` URIs are returned as written rather than resolved.
+    pub is_data_uri: bool,
+}
+
+fn collect_favicons(document: &Html, base: Option<&Url>) -> Vec<Favicon> {
+    let mut favicons: Vec<Favicon> = Vec::new();
+    for el in document.select(&ICON_LINK_SEL) {
+        let value = el.value();
+        let rel = value.attr("rel").unwrap_or("").to_lowercase();
+        let rel = rel.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !rel.split(' ').any(|token| ICON_RELS.contains(&token)) {
+            continue;
+        }
+        let href = value.attr("href").unwrap_or("").trim();
+        if href.is_empty() {
+            continue;
         }
+        let is_data_uri = href.get(..5).is_some_and(|s| s.eq_ignore_ascii_case("data:"));
+        let url = if is_data_uri { href.to_string() } else { crate::html::resolve_href(base, href) };
+        if favicons.iter().any(|f| f.url == url && f.rel == rel) {
+            continue;
+        }
+        let sizes = value.attr("sizes").map(str::trim).filter(|s| !s.is_empty()).map(String::from);
+        favicons.push(Favicon { url, rel, sizes, is_data_uri });
     }
+    favicons
+}
 
-    dict.set_item("social_links", PyList::new(py, &social_links)?)?;
+/// Rank an icon for `best_favicon`: SVG, then PNG, then anything else (ICO,
+/// GIF), each ordered by declared size. Manifests are never picked.
+fn favicon_rank(favicon: &Favicon) -> Option<(u8, u32)> {
+    if favicon.rel == "manifest" {
+        return None;
+    }
+    let path = favicon.url.split(['?', '#']).next().unwrap_or("").to_lowercase();
+    let format = if path.ends_with(".svg") || path.starts_with("data:image/svg") {
+        2
+    } else if path.ends_with(".png") || path.starts_with("data:image/png") {
+        1
+    } else {
+        0
+    };
+    let declared = favicon.sizes.as_deref().map(|sizes| {
+        sizes
+            .split_whitespace()
+            .map(|s| if s.eq_ignore_ascii_case("any") { u32::MAX } else { icon_width(s) })
+            .max()
+            .unwrap_or(0)
+    });
+    let size = match declared {
+        Some(size) if size > 0 => size,
+        _ if favicon.rel.contains("apple-touch-icon") => APPLE_TOUCH_ICON_DEFAULT_SIZE,
+        _ => 0,
+    };
+    Some((format, size))
+}
 
+fn icon_width(size: &str) -> u32 {
+    size.split(['x', 'X']).next().and_then(|w| w.parse().ok()).unwrap_or(0)
+}
+
+/// Pick the best declared icon; with no usable icon, fall back to
+/// `/favicon.ico` on the site root when `base` is known.
+fn best_favicon(favicons: &[Favicon], base: Option<&Url>) -> Option<String> {
+    favicons
+        .iter()
+        .filter_map(|f| favicon_rank(f).map(|rank| (rank, f)))
+        // max_by_key keeps the last maximum; reverse so the first declared wins ties
+        .rev()
+        .max_by_key(|(rank, _)| *rank)
+        .map(|(_, f)| f.url.clone())
+        .or_else(|| base.map(|b| crate::html::resolve_href(Some(b), "/favicon.ico")))
+}
+
+/// Extract HTML metadata (title, meta_description, social_links) from raw HTML.
+///
+/// Returns a dict with keys:
+///   - "title": str | None  (first non-blank of <title>, og:title, twitter:title, <h1>)
+///   - "title_source": "title" | "og:title" | "twitter:title" | "h1" | None
+///   - "raw_title": str | None  (the <title> text exactly as written)
+///   - "meta_description": str | None  (falls back to og/twitter:description, then
+///     the first 80+ char visible paragraph cut to 300 chars)
+///   - "meta_description_source": "meta" | "og:description" | "twitter:description"
+///     | "paragraph" | None
+///   - "social_links": list[str]  (normalized, one per profile, no share links;
+///     JSON-LD sameAs URLs included)
+///   - "social_profiles": dict[str, list[str]]  (social_links keyed by platform)
+///   - "social_links_truncated": int  (links dropped by `max_per_platform`)
+///   - "abn": str | None  (checksum-valid Australian Business Number)
+///   - "open_graph": dict[str, str | None]
+///   - "twitter_card": dict[str, str | None]
+///   - "twitter_handle": str | None  (twitter:site without the "@")
+///   - "favicons": list[{url, rel, sizes, is_data_uri}]  (icons and manifests)
+///   - "best_favicon": str | None  (largest SVG/PNG; /favicon.ico with base_url)
+///   - "generator": str | None  (first generator meta, raw)
+///   - "generator_parsed": {name, version} | None  (when it reads "Name X.Y")
+///   - "generators": list[str]  (every generator meta; plugins add their own)
+///   - "meta_keywords": list[str] | None
+///   - "theme_color": str | None
+///   - "published_at" / "modified_at": str | None  (ISO-8601)
+///   - "date_source": "open_graph" | "json_ld" | "meta" | "time" | None
+///   - "date_raw": str | None  (the strongest date that couldn't be parsed)
+///   - "structured": {org_name, logo_url, same_as, telephone, search_action_url}
+///     (JSON-LD Organization and WebSite SearchAction; invalid blocks skipped)
+///   - "lang": {tag, language, region} | None  (<html lang>, else og:locale;
+///     "en-au" → tag "en-AU")
+///   - "lang_raw": str | None  (a declared language that couldn't be parsed)
+///   - "dir": "ltr" | "rtl" | None  (<html dir>)
+///   - "feeds": list[{title, href, type}]  (RSS/Atom link[rel=alternate], resolved
+///     and deduplicated)
+///   - "has_blog": bool  (a non-comment feed, or a /blog link in the header or nav)
+///   - "pwa": {manifest_url, apple_mobile_web_app_capable, service_worker_hint}
+///   - "viewport": {content, width, initial_scale, user_scalable, maximum_scale,
+///     blocks_zoom, multiple_declared} | None  (the last viewport meta)
+///   - "content_snippet": str | None  (with `include_summary`: the first 60+ char
+///     visible paragraph, cut to 280 chars at a word boundary)
+///   - "brand_colors": list[{color, source}]  (lowercase "#rrggbb": declared metas,
+///     then up to 3 frequent non-gray header/nav/button style colors)
+///
+/// Input longer than `max_bytes` is cut at a tag boundary and reported with
+/// `truncated: true`; the final 64KB is parsed separately for a footer ABN.
+///
+/// `open_graph` holds og:title/description/image/type/url/site_name (None when
+/// absent). With `base_url`, a relative og:image and every social link are
+/// resolved against it, and social links that aren't http(s) are dropped.
+///
+/// `feeds` skips per-post comment feeds unless `exclude_comment_feeds` is
+/// false. `content_snippet` is only extracted with `include_summary`.
+/// `max_per_platform` caps social links per platform (None: no cap), keeping
+/// header/footer links and shorter paths first.
+#[pyfunction]
+#[pyo3(signature = (
+    html,
+    max_bytes=Some(crate::html::DEFAULT_MAX_BYTES),
+    base_url=None,
+    exclude_comment_feeds=true,
+    include_summary=false,
+    max_per_platform=Some(3),
+))]
+pub fn extract_html_metadata(
+    py: Python<'_>,
+    html: &Bound<'_, PyAny>,
+    max_bytes: Option<usize>,
+    base_url: Option<&str>,
+    exclude_comment_feeds: bool,
+    include_summary: bool,
+    max_per_platform: Option<usize>,
+) -> PyResult<PyObject> {
+    let full = crate::encoding::read_html(html)?.text;
+    let options = MetadataOptions { exclude_comment_feeds, include_summary, max_per_platform };
+    Ok(metadata_dict(py, &full, max_bytes, base_url, options)?.into())
+}
+
+/// `extract_html_metadata` for a raw response body.
+///
+/// The charset is taken from a BOM, then `content_type_header`'s `charset=`,
+/// then `<meta charset>`, falling back to a byte-level guess; undecodable
+/// bytes are replaced. The result adds a `detected_encoding` key.
+#[pyfunction]
+#[pyo3(signature = (
+    body,
+    content_type_header=None,
+    max_bytes=Some(crate::html::DEFAULT_MAX_BYTES),
+    base_url=None,
+    exclude_comment_feeds=true,
+    include_summary=false,
+    max_per_platform=Some(3),
+))]
+// Python keyword arguments; every one past `body` has a default
+#[allow(clippy::too_many_arguments)]
+pub fn extract_html_metadata_bytes(
+    py: Python<'_>,
+    body: &[u8],
+    content_type_header: Option<&str>,
+    max_bytes: Option<usize>,
+    base_url: Option<&str>,
+    exclude_comment_feeds: bool,
+    include_summary: bool,
+    max_per_platform: Option<usize>,
+) -> PyResult<PyObject> {
+    let input = crate::encoding::decode_html_bytes(py, body, content_type_header)?;
+    let options = MetadataOptions { exclude_comment_feeds, include_summary, max_per_platform };
+    let dict = metadata_dict(py, &input.text, max_bytes, base_url, options)?;
+    dict.set_item("detected_encoding", input.encoding)?;
     Ok(dict.into())
 }
+
+fn metadata_dict<'py>(
+    py: Python<'py>,
+    full: &str,
+    max_bytes: Option<usize>,
+    base_url: Option<&str>,
+    options: MetadataOptions,
+) -> PyResult<Bound<'py, PyDict>> {
+    crate::html::check_size(full)?;
+    HtmlMetadata::extract(full, max_bytes, base_url, options).into_dict(py)
+}
+
+/// Optional behaviour of `extract_html_metadata` beyond truncation and base URL.
+#[derive(Debug, Clone, Copy)]
+struct MetadataOptions {
+    exclude_comment_feeds: bool,
+    include_summary: bool,
+    max_per_platform: Option<usize>,
+}
+
+/// Everything `extract_html_metadata` reports, gathered without touching
+/// Python so batches can run off the GIL.
+#[derive(Debug, Default)]
+struct HtmlMetadata {
+    title: Option<String>,
+    title_source: Option<&'static str>,
+    raw_title: Option<String>,
+    meta_description: Option<String>,
+    meta_description_source: Option<&'static str>,
+    social_links: Vec<String>,
+    social_profiles: HashMap<String, Vec<String>>,
+    social_links_truncated: usize,
+    abn: Option<String>,
+    open_graph: Vec<(&'static str, Option<String>)>,
+    twitter_card: Vec<(&'static str, Option<String>)>,
+    twitter_handle: Option<String>,
+    best_favicon: Option<String>,
+    favicons: Vec<Favicon>,
+    generators: Vec<String>,
+    meta_keywords: Option<Vec<String>>,
+    theme_color: Option<String>,
+    published_at: Option<String>,
+    modified_at: Option<String>,
+    date_source: Option<&'static str>,
+    date_raw: Option<String>,
+    structured: StructuredData,
+    lang: Option<LanguageTag>,
+    lang_raw: Option<String>,
+    dir: Option<String>,
+    feeds: Vec<Feed>,
+    has_blog: bool,
+    pwa: PwaInfo,
+    viewport: Option<Viewport>,
+    content_snippet: Option<String>,
+    brand_colors: Vec<BrandColor>,
+    truncated: bool,
+}
+
+impl HtmlMetadata {
+    fn extract(
+        full: &str,
+        max_bytes: Option<usize>,
+        base_url: Option<&str>,
+        options: MetadataOptions,
+    ) -> Self {
+        let html = match max_bytes {
+            Some(max) => crate::html::truncate_at_tag(full, max),
+            None => full,
+        };
+        let mut meta = HtmlMetadata { truncated: html.len() < full.len(), ..Default::default() };
+
+        // An empty document yields None / empty values for every key
+        let document = Html::parse_document(html);
+        let base = crate::html::parse_base_url(base_url);
+
+        meta.raw_title = document_title(&document).map(|el| el.text().collect());
+
+        meta.meta_description = document
+            .select(&META_DESC_SEL)
+            .next()
+            .and_then(|el| el.value().attr("content").map(|s| s.to_string()))
+            .filter(|s| !s.trim().is_empty());
+
+        // Social links
+        let domains = social_domains();
+
+        let json_ld = crate::html::json_ld_objects(&document);
+        meta.structured = structured_data(&json_ld, base.as_ref());
+
+        // Anchors first, then JSON-LD sameAs (declared profiles, ranked like
+        // site chrome), through the same normalization
+        let anchor_hrefs = document
+            .select(&LINK_SEL)
+            .filter_map(|el| el.value().attr("href").map(|href| (href, in_site_chrome(&el))));
+        let same_as_hrefs = meta.structured.same_as.iter().map(|href| (href.as_str(), true));
+        let mut candidates: Vec<SocialCandidate> = Vec::new();
+        let mut seen_social: HashMap<String, usize> = HashMap::new();
+        for (href, in_chrome) in anchor_hrefs.chain(same_as_hrefs) {
+            let Some(link) = social_link_for_href(href, base.as_ref(), &domains) else { continue };
+            // One link per platform handle; unparseable links dedupe on the URL
+            let key = parse_social_profile(&link)
+                .map(|(platform, handle)| format!("{platform}:{}", handle.to_lowercase()))
+                .unwrap_or_else(|| link.clone());
+            match seen_social.get(&key) {
+                Some(&i) => candidates[i].in_chrome |= in_chrome,
+                None => {
+                    seen_social.insert(key, candidates.len());
+                    let platform = social_link_platform(&link, &domains);
+                    candidates.push(SocialCandidate { link, platform, in_chrome });
+                }
+            }
+        }
+
+        let kept = cap_social_links(&candidates, options.max_per_platform);
+        meta.social_links_truncated = candidates.len() - kept.len();
+        for candidate in kept {
+            if let Some(platform) = &candidate.platform {
+                let links = meta.social_profiles.entry(platform.clone()).or_default();
+                links.push(candidate.link.clone());
+            }
+            meta.social_links.push(candidate.link.clone());
+        }
+
+        meta.abn = crate::html::best_abn(&document).or_else(|| {
+            meta.truncated.then(|| {
+                let tail = crate::html::tail_of(full, crate::html::TAIL_SCAN_BYTES);
+                crate::html::best_abn(&Html::parse_fragment(tail))
+            })?
+        });
+
+        let tags = meta_tags(&document);
+
+        // <title> → og:title → twitter:title → first <h1>, skipping blank ones
+        let h1 = document
+            .select(&H1_SEL)
+            .next()
+            .map(|el| el.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" "));
+        let title_candidates = [
+            (meta.raw_title.as_deref().map(str::trim), "title"),
+            (first_meta(&tags, "og:title"), "og:title"),
+            (first_meta(&tags, "twitter:title"), "twitter:title"),
+            (h1.as_deref(), "h1"),
+        ];
+        if let Some((title, source)) = title_candidates
+            .into_iter()
+            .find_map(|(title, source)| title.filter(|t| !t.is_empty()).map(|t| (t, source)))
+        {
+            meta.title = Some(title.to_string());
+            meta.title_source = Some(source);
+        }
+
+        // meta description → og:description → twitter:description → first
+        // substantial visible paragraph (capped, as it's not written as a summary)
+        if meta.meta_description.is_some() {
+            meta.meta_description_source = Some("meta");
+        } else if let Some((description, source)) = [
+            (first_meta(&tags, "og:description"), "og:description"),
+            (first_meta(&tags, "twitter:description"), "twitter:description"),
+        ]
+        .into_iter()
+        .find_map(|(d, source)| d.map(|d| (d.to_string(), source)))
+        {
+            meta.meta_description = Some(description);
+            meta.meta_description_source = Some(source);
+        } else if let Some(paragraph) = first_paragraph(&document, DESCRIPTION_PARAGRAPH_MIN_CHARS)
+        {
+            meta.meta_description =
+                Some(truncate_at_word(&paragraph, DESCRIPTION_FALLBACK_MAX_CHARS));
+            meta.meta_description_source = Some("paragraph");
+        }
+
+        meta.open_graph = prefixed_meta(&tags, "og", OPEN_GRAPH_KEYS, base.as_ref());
+        meta.twitter_card = prefixed_meta(&tags, "twitter", TWITTER_CARD_KEYS, base.as_ref());
+        meta.twitter_handle = first_meta(&tags, "twitter:site").and_then(twitter_handle);
+
+        meta.favicons = collect_favicons(&document, base.as_ref());
+        meta.best_favicon = best_favicon(&meta.favicons, base.as_ref());
+
+        meta.generators = tags.get("generator").cloned().unwrap_or_default();
+        meta.meta_keywords = first_meta(&tags, "keywords").map(|k| {
+            k.split(',').map(str::trim).filter(|k| !k.is_empty()).map(String::from).collect()
+        });
+        meta.theme_color = first_meta(&tags, "theme-color").map(String::from);
+        meta.brand_colors = brand_colors(&document, &tags);
+        if let Some(declared) = tags.get("viewport") {
+            meta.viewport = declared.last().map(|content| Viewport {
+                multiple_declared: declared.len() > 1,
+                ..parse_viewport(content)
+            });
+        }
+
+        for modified in [false, true] {
+            let candidates = date_candidates(&tags, &json_ld, &document, modified);
+            let parsed = candidates.iter().find_map(|(raw, s)| parse_date(raw).map(|d| (d, *s)));
+            if let Some((_, source)) = parsed {
+                meta.date_source = meta.date_source.or(Some(source));
+            }
+            // The strongest source is kept raw even when a weaker one parsed
+            let strongest = candidates.first().map(|(raw, _)| raw);
+            if let Some(raw) = strongest.filter(|raw| parse_date(raw).is_none()) {
+                meta.date_raw = meta.date_raw.take().or_else(|| Some(raw.trim().to_string()));
+            }
+            let date = parsed.map(|(date, _)| date);
+            if modified {
+                meta.modified_at = date;
+            } else {
+                meta.published_at = date;
+            }
+        }
+
+        // <html lang>, then og:locale; the first malformed value is kept raw
+        let root = document.root_element().value();
+        let declared = [root.attr("lang"), first_meta(&tags, "og:locale")];
+        for raw in declared.into_iter().flatten().map(str::trim).filter(|l| !l.is_empty()) {
+            match parse_language_tag(raw) {
+                Some(tag) => {
+                    meta.lang = Some(tag);
+                    break;
+                }
+                None => meta.lang_raw = meta.lang_raw.take().or_else(|| Some(raw.to_string())),
+            }
+        }
+        meta.dir = root
+            .attr("dir")
+            .map(|d| d.trim().to_lowercase())
+            .filter(|d| d == "ltr" || d == "rtl");
+
+        if options.include_summary {
+            meta.content_snippet = first_paragraph(&document, SNIPPET_MIN_CHARS)
+                .map(|p| truncate_at_word(&p, SNIPPET_MAX_CHARS));
+        }
+
+        meta.feeds = collect_feeds(&document, base.as_ref(), options.exclude_comment_feeds);
+        meta.has_blog = meta
+            .feeds
+            .iter()
+            .any(|f| !f.href.to_lowercase().contains(COMMENT_FEED_MARKER))
+            || has_blog_nav_link(&document);
+
+        meta.pwa = PwaInfo {
+            manifest_url: meta.favicons.iter().find(|f| f.rel == "manifest").map(|f| f.url.clone()),
+            apple_mobile_web_app_capable: first_meta(&tags, "apple-mobile-web-app-capable")
+                .is_some_and(|v| v.eq_ignore_ascii_case("yes")),
+            service_worker_hint: has_service_worker_registration(&document),
+        };
+
+        meta
+    }
+
+    fn into_dict(self, py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        let meta_dict = |values: Vec<(&'static str, Option<String>)>| -> PyResult<_> {
+            let dict = PyDict::new(py);
+            for (key, value) in values {
+                dict.set_item(key, value)?;
+            }
+            Ok(dict)
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("title", self.title)?;
+        dict.set_item("title_source", self.title_source)?;
+        dict.set_item("raw_title", self.raw_title)?;
+        dict.set_item("meta_description", self.meta_description)?;
+        dict.set_item("meta_description_source", self.meta_description_source)?;
+        dict.set_item("social_links", PyList::new(py, &self.social_links)?)?;
+        dict.set_item("social_profiles", self.social_profiles)?;
+        dict.set_item("social_links_truncated", self.social_links_truncated)?;
+        dict.set_item("abn", self.abn)?;
+        dict.set_item("open_graph", meta_dict(self.open_graph)?)?;
+        dict.set_item("twitter_card", meta_dict(self.twitter_card)?)?;
+        dict.set_item("twitter_handle", self.twitter_handle)?;
+        dict.set_item("best_favicon", self.best_favicon)?;
+        dict.set_item("favicons", self.favicons)?;
+        dict.set_item("generator", self.generators.first())?;
+        dict.set_item("generator_parsed", self.generators.first().and_then(|g| parse_generator(g)))?;
+        dict.set_item("generators", self.generators)?;
+        dict.set_item("meta_keywords", self.meta_keywords)?;
+        dict.set_item("theme_color", self.theme_color)?;
+        dict.set_item("published_at", self.published_at)?;
+        dict.set_item("modified_at", self.modified_at)?;
+        dict.set_item("date_source", self.date_source)?;
+        dict.set_item("date_raw", self.date_raw)?;
+        dict.set_item("structured", self.structured)?;
+        dict.set_item("lang", self.lang)?;
+        dict.set_item("lang_raw", self.lang_raw)?;
+        dict.set_item("dir", self.dir)?;
+        dict.set_item("feeds", self.feeds)?;
+        dict.set_item("has_blog", self.has_blog)?;
+        dict.set_item("pwa", self.pwa)?;
+        dict.set_item("viewport", self.viewport)?;
+        dict.set_item("content_snippet", self.content_snippet)?;
+        dict.set_item("brand_colors", self.brand_colors)?;
+        dict.set_item("truncated", self.truncated)?;
+        Ok(dict)
+    }
+}
+
+/// `extract_html_metadata` over many `(key, html)` pages in parallel.
+///
+/// The GIL is released while pages are parsed on a rayon pool (`num_threads`
+/// workers, default: rayon's global pool). Returns a dict keyed by each
+/// page's key; a page that panics or exceeds the 50MB hard cap maps to
+/// `{"error": ...}` instead of failing the batch.
+#[pyfunction]
+#[pyo3(signature = (
+    pages,
+    max_bytes=Some(crate::html::DEFAULT_MAX_BYTES),
+    num_threads=None,
+    exclude_comment_feeds=true,
+    include_summary=false,
+    max_per_platform=Some(3),
+))]
+pub fn extract_html_metadata_batch<'py>(
+    py: Python<'py>,
+    pages: Vec<(String, String)>,
+    max_bytes: Option<usize>,
+    num_threads: Option<usize>,
+    exclude_comment_feeds: bool,
+    include_summary: bool,
+    max_per_platform: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let options = MetadataOptions { exclude_comment_feeds, include_summary, max_per_platform };
+    let run = || -> Vec<Result<HtmlMetadata, String>> {
+        pages
+            .par_iter()
+            .map(|(_, html)| {
+                if let Some(e) = crate::html::size_error(html) {
+                    return Err(e);
+                }
+                std::panic::catch_unwind(|| HtmlMetadata::extract(html, max_bytes, None, options))
+                    .map_err(|payload| crate::html::panic_message(payload.as_ref()))
+            })
+            .collect()
+    };
+
+    let results = py.allow_threads(|| match num_threads {
+        Some(n) => rayon::ThreadPoolBuilder::new()
+            .num_threads(n.max(1))
+            .build()
+            .map(|pool| pool.install(run))
+            .map_err(|e| e.to_string()),
+        None => Ok(run()),
+    });
+    let results = results.map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+
+    let out = PyDict::new(py);
+    for ((key, _), result) in pages.iter().zip(results) {
+        match result {
+            Ok(meta) => out.set_item(key, meta.into_dict(py)?)?,
+            Err(message) => {
+                let dict = PyDict::new(py);
+                dict.set_item("error", message)?;
+                out.set_item(key, dict)?;
+            }
+        }
+    }
+    Ok(out)
+}
@@ -1,6 +1,13 @@
+use numpy::PyArray1;
+use pyo3::exceptions::{PyImportError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDate, PyDict, PyList};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::{LazyLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use xxhash_rust::xxh3::xxh3_64;
 
 // ---------------------------------------------------------------------------
 // Helpers for extracting values from the Python dict
@@ -12,42 +19,429 @@ fn extract_opt_string(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str
         .flatten()
 }
 
-fn extract_opt_f64(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> Option<f64> {
-    map.get(key)
-        .and_then(|obj| obj.extract::<Option<f64>>(py).ok())
-        .flatten()
+// A prospect (or its signals) as passed from Python
+pub(crate) type Fields = HashMap<String, PyObject>;
+
+/// A prospect's fields by name. Dicts are taken as they are; any other object
+/// (dataclass, Pydantic model, ...) is read attribute by attribute for `keys`,
+/// with each of `nested` (e.g. `signals`) read the same way into a dict.
+/// Dates on objects become ISO strings, as the Python exporters write them.
+pub(crate) fn prospect_fields(
+    obj: &Bound<'_, PyAny>,
+    keys: &[&str],
+    nested: &[(&str, &[&str])],
+) -> PyResult<Fields> {
+    if let Ok(dict) = obj.downcast::<PyDict>() {
+        return dict.extract();
+    }
+    let py = obj.py();
+    let mut fields = Fields::new();
+    for key in keys {
+        // Missing attributes, like missing keys, count as absent
+        let Ok(value) = obj.getattr(*key) else { continue };
+        let value = if value.is_instance_of::<PyDate>() {
+            value.call_method0("isoformat")?
+        } else {
+            value
+        };
+        fields.insert(key.to_string(), value.unbind());
+    }
+    for (key, nested_keys) in nested {
+        let Ok(value) = obj.getattr(*key) else { continue };
+        let value = if value.is_none() || value.is_instance_of::<PyDict>() {
+            value.unbind()
+        } else {
+            prospect_fields(&value, nested_keys, &[])?.into_pyobject(py)?.into_any().unbind()
+        };
+        fields.insert(key.to_string(), value);
+    }
+    Ok(fields)
 }
 
-fn extract_opt_i64(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> Option<i64> {
-    map.get(key)
-        .and_then(|obj| obj.extract::<Option<i64>>(py).ok())
-        .flatten()
+/// Fields that didn't come through as their expected type, by key
+/// (`"signals.load_time_ms"` for signal fields).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InputIssues {
+    /// Numeric strings read as numbers
+    coerced: Vec<String>,
+    /// Values of the wrong type, scored as if absent
+    ignored: Vec<String>,
 }
 
-fn extract_bool(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> bool {
-    map.get(key)
-        .and_then(|obj| obj.extract::<bool>(py).ok())
-        .unwrap_or(false)
+/// Reads prospect fields, telling absent keys (missing or None) apart from
+/// values of the wrong type. Strict readers raise ValueError on the latter;
+/// lenient ones read numeric strings as numbers and otherwise treat the field
+/// as absent, noting either in `issues`.
+struct FieldReader<'py> {
+    py: Python<'py>,
+    strict: bool,
+    /// Position in the batch, for error messages
+    index: Option<usize>,
+    issues: InputIssues,
 }
 
-/// Extract a Python tristate: None → None, True → Some(true), False → Some(false)
-fn extract_opt_bool(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> Option<bool> {
-    map.get(key)
-        .and_then(|obj| obj.extract::<Option<bool>>(py).ok())
-        .flatten()
+impl<'py> FieldReader<'py> {
+    fn new(py: Python<'py>, strict: bool, index: Option<usize>) -> Self {
+        FieldReader { py, strict, index, issues: InputIssues::default() }
+    }
+
+    /// Raise (strict) or record as ignored (lenient) a value of the wrong type.
+    fn mismatch(&mut self, path: String, obj: &PyObject, expected: &str) -> PyResult<()> {
+        if !self.strict {
+            self.issues.ignored.push(path);
+            return Ok(());
+        }
+        let found = obj.bind(self.py).get_type().name().map_or("?".to_string(), |n| n.to_string());
+        let prospect = self.index.map_or("prospect".to_string(), |i| format!("prospect {i}"));
+        Err(PyValueError::new_err(format!(
+            "{prospect}: {path:?} should be {expected}, got {found}"
+        )))
+    }
+
+    fn read<T: for<'a> FromPyObject<'a>>(
+        &mut self,
+        map: &Fields,
+        prefix: &str,
+        key: &str,
+        expected: &str,
+    ) -> PyResult<Option<T>> {
+        let Some(obj) = map.get(key).filter(|obj| !obj.is_none(self.py)) else { return Ok(None) };
+        match obj.extract::<T>(self.py) {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => {
+                self.mismatch(format!("{prefix}{key}"), obj, expected)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Like `read`, but a lenient reader parses numeric strings with `parse`.
+    fn read_number<T: for<'a> FromPyObject<'a>>(
+        &mut self,
+        map: &Fields,
+        prefix: &str,
+        key: &str,
+        expected: &str,
+        parse: fn(&str) -> Option<T>,
+    ) -> PyResult<Option<T>> {
+        let Some(obj) = map.get(key).filter(|obj| !obj.is_none(self.py)) else { return Ok(None) };
+        if let Ok(value) = obj.extract::<T>(self.py) {
+            return Ok(Some(value));
+        }
+        if !self.strict {
+            if let Some(value) = obj.extract::<String>(self.py).ok().and_then(|s| parse(s.trim())) {
+                self.issues.coerced.push(format!("{prefix}{key}"));
+                return Ok(Some(value));
+            }
+        }
+        self.mismatch(format!("{prefix}{key}"), obj, expected)?;
+        Ok(None)
+    }
+
+    fn string(&mut self, map: &Fields, prefix: &str, key: &str) -> PyResult<Option<String>> {
+        self.read(map, prefix, key, "str")
+    }
+
+    fn int(&mut self, map: &Fields, prefix: &str, key: &str) -> PyResult<Option<i64>> {
+        self.read_number(map, prefix, key, "int", |s| s.parse().ok())
+    }
+
+    fn float(&mut self, map: &Fields, prefix: &str, key: &str) -> PyResult<Option<f64>> {
+        let parse = |s: &str| s.parse::<f64>().ok().filter(|v| v.is_finite());
+        self.read_number(map, prefix, key, "float", parse)
+    }
+
+    fn bool(&mut self, map: &Fields, prefix: &str, key: &str) -> PyResult<Option<bool>> {
+        self.read(map, prefix, key, "bool")
+    }
+
+    fn list_len(&mut self, map: &Fields, prefix: &str, key: &str) -> PyResult<usize> {
+        Ok(self.read::<Vec<PyObject>>(map, prefix, key, "list")?.map_or(0, |v| v.len()))
+    }
 }
 
-fn extract_list_nonempty(py: Python<'_>, map: &HashMap<String, PyObject>, key: &str) -> bool {
-    map.get(key)
-        .and_then(|obj| obj.extract::<Vec<PyObject>>(py).ok())
-        .map(|v| !v.is_empty())
-        .unwrap_or(false)
+// ---------------------------------------------------------------------------
+// Scoring inputs
+// ---------------------------------------------------------------------------
+
+/// The `signals` fields the scorers read.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct SignalInputs {
+    is_parked: Option<bool>,
+    reachable: Option<bool>,
+    estimated_location_count: Option<i64>,
+    is_hiring: Option<bool>,
+    ab_testing_tools: Option<Vec<String>>,
+    digital_maturity: Option<i64>,
+    has_google_analytics: Option<bool>,
+    has_facebook_pixel: Option<bool>,
+    has_booking_system: Option<bool>,
+    email_count: usize,
+    cms: Option<String>,
+    load_time_ms: Option<i64>,
+    has_legacy_jquery: Option<bool>,
+    has_online_ordering: Option<bool>,
+    is_placeholder: Option<bool>,
+    legacy_score: Option<i64>,
+    has_responsive: Option<bool>,
+    has_legacy_framework: Option<bool>,
+    cms_version: Option<String>,
+    generator: Option<String>,
+    is_thin_content: Option<bool>,
+    word_count: Option<i64>,
+    copyright_years_stale: Option<i64>,
 }
 
-fn extract_signals(py: Python<'_>, map: &HashMap<String, PyObject>) -> Option<HashMap<String, PyObject>> {
-    map.get("signals")
-        .and_then(|obj| obj.extract::<Option<HashMap<String, PyObject>>>(py).ok())
-        .flatten()
+/// The prospect fields the scorers read, copied out of the Python dict in
+/// one pass so scoring itself can run without the GIL. Serialized, these are
+/// the canonical form `prospect_content_hash` hashes.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct ProspectInputs {
+    website: Option<String>,
+    phone: Option<String>,
+    email_count: usize,
+    found_in_maps: bool,
+    maps_position: Option<i64>,
+    rating: Option<f64>,
+    review_count: Option<i64>,
+    found_in_ads: bool,
+    found_in_organic: bool,
+    organic_position: Option<i64>,
+    signals: Option<SignalInputs>,
+}
+
+// Attributes read from non-dict prospects; keep in step with ProspectInputs::read
+const SCORED_FIELDS: &[&str] = &[
+    "website", "phone", "emails", "found_in_maps", "maps_position", "rating", "review_count",
+    "found_in_ads", "found_in_organic", "organic_position", "scraped_at",
+];
+const SCORED_SIGNAL_FIELDS: &[&str] = &[
+    "is_parked", "reachable", "estimated_location_count", "is_hiring", "ab_testing_tools",
+    "digital_maturity", "has_google_analytics", "has_facebook_pixel", "has_booking_system",
+    "tracking", "has_booking", "emails", "cms", "load_time_ms", "has_legacy_jquery",
+    "has_online_ordering", "is_placeholder", "legacy_score", "has_responsive",
+    "has_legacy_framework", "cms_version", "generator", "is_thin_content", "word_count",
+    "copyright_years_stale",
+];
+
+impl ProspectInputs {
+    /// Extract one prospect, raising on mistyped fields when `strict`.
+    pub(crate) fn extract(
+        py: Python<'_>,
+        prospect: &HashMap<String, PyObject>,
+        strict: bool,
+    ) -> PyResult<Self> {
+        Self::read(&mut FieldReader::new(py, strict, None), prospect)
+    }
+
+    /// Extract with `strict` validation; `index` names the prospect in errors.
+    pub(crate) fn extract_checked(
+        py: Python<'_>,
+        prospect: &HashMap<String, PyObject>,
+        strict: bool,
+        index: Option<usize>,
+    ) -> PyResult<(Self, InputIssues)> {
+        let mut reader = FieldReader::new(py, strict, index);
+        let inputs = Self::read(&mut reader, prospect)?;
+        Ok((inputs, reader.issues))
+    }
+
+    fn read(r: &mut FieldReader<'_>, prospect: &HashMap<String, PyObject>) -> PyResult<Self> {
+        let signals = match r.read::<HashMap<String, PyObject>>(prospect, "", "signals", "dict")? {
+            Some(s) => {
+                let p = "signals.";
+                // analyze_tech_stack reports tracking and booking under its own keys
+                let tracking = r.read::<HashMap<String, PyObject>>(&s, p, "tracking", "dict")?;
+                let tracked = |r: &mut FieldReader<'_>, key: &str| match &tracking {
+                    Some(tracking) => r.bool(tracking, "signals.tracking.", key),
+                    None => Ok(None),
+                };
+                let has_google_analytics = match r.bool(&s, p, "has_google_analytics")? {
+                    Some(v) => Some(v),
+                    None => tracked(r, "google_analytics")?,
+                };
+                let has_facebook_pixel = match r.bool(&s, p, "has_facebook_pixel")? {
+                    Some(v) => Some(v),
+                    None => tracked(r, "facebook_pixel")?,
+                };
+                let has_booking_system = match r.bool(&s, p, "has_booking_system")? {
+                    Some(v) => Some(v),
+                    None => r.bool(&s, p, "has_booking")?,
+                };
+                Some(SignalInputs {
+                    is_parked: r.bool(&s, p, "is_parked")?,
+                    reachable: r.bool(&s, p, "reachable")?,
+                    estimated_location_count: r.int(&s, p, "estimated_location_count")?,
+                    is_hiring: r.bool(&s, p, "is_hiring")?,
+                    ab_testing_tools: r.read(&s, p, "ab_testing_tools", "list[str]")?,
+                    digital_maturity: r.int(&s, p, "digital_maturity")?,
+                    has_google_analytics,
+                    has_facebook_pixel,
+                    has_booking_system,
+                    email_count: r.list_len(&s, p, "emails")?,
+                    cms: r.string(&s, p, "cms")?,
+                    load_time_ms: r.int(&s, p, "load_time_ms")?,
+                    has_legacy_jquery: r.bool(&s, p, "has_legacy_jquery")?,
+                    has_online_ordering: r.bool(&s, p, "has_online_ordering")?,
+                    is_placeholder: r.bool(&s, p, "is_placeholder")?,
+                    legacy_score: r.int(&s, p, "legacy_score")?,
+                    has_responsive: r.bool(&s, p, "has_responsive")?,
+                    has_legacy_framework: r.bool(&s, p, "has_legacy_framework")?,
+                    cms_version: r.string(&s, p, "cms_version")?,
+                    generator: r.string(&s, p, "generator")?,
+                    is_thin_content: r.bool(&s, p, "is_thin_content")?,
+                    word_count: r.int(&s, p, "word_count")?,
+                    copyright_years_stale: r.int(&s, p, "copyright_years_stale")?,
+                })
+            }
+            None => None,
+        };
+        Ok(ProspectInputs {
+            website: r.string(prospect, "", "website")?,
+            phone: r.string(prospect, "", "phone")?,
+            email_count: r.list_len(prospect, "", "emails")?,
+            found_in_maps: r.bool(prospect, "", "found_in_maps")?.unwrap_or(false),
+            maps_position: r.int(prospect, "", "maps_position")?,
+            rating: r.float(prospect, "", "rating")?,
+            review_count: r.int(prospect, "", "review_count")?,
+            found_in_ads: r.bool(prospect, "", "found_in_ads")?.unwrap_or(false),
+            found_in_organic: r.bool(prospect, "", "found_in_organic")?.unwrap_or(false),
+            organic_position: r.int(prospect, "", "organic_position")?,
+            signals,
+        })
+    }
+
+    /// A website counts only if it isn't a parked or for-sale domain.
+    fn has_usable_website(&self) -> bool {
+        self.website.is_some()
+            && self.signals.as_ref().is_none_or(|signals| signals.is_parked != Some(true))
+    }
+
+    /// A phone number or an email address (on the prospect or found on its
+    /// site) to reach the business on.
+    fn is_contactable(&self) -> bool {
+        let has_phone = self.phone.as_deref().is_some_and(|phone| !phone.trim().is_empty());
+        let site_emails = self.signals.as_ref().map_or(0, |signals| signals.email_count);
+        has_phone || self.email_count > 0 || site_emails > 0
+    }
+
+    /// `signals.reachable` explicitly false: the site is down or its domain
+    /// doesn't resolve. Missing or None means the check wasn't run.
+    fn is_unreachable(&self) -> bool {
+        self.signals.as_ref().is_some_and(|signals| signals.reachable == Some(false))
+    }
+
+    /// The website's domain when it is a directory listing (Yelp, Yellow
+    /// Pages, ...) rather than a site the business owns.
+    fn directory_listing(&self) -> Option<String> {
+        let website = self.website.as_deref()?;
+        let domain = crate::text::normalize_domain(website)?;
+        crate::text::is_directory_url(website, &domain).then_some(domain)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Score breakdowns
+// ---------------------------------------------------------------------------
+
+/// The input a score component was computed from, as Python sees it.
+#[derive(Debug, Clone, IntoPyObject)]
+pub enum ComponentInput {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+/// One line of a score breakdown: the points a component contributed
+/// (negative for penalties) and the cap it was held to, if any.
+#[derive(Debug, Clone, IntoPyObject)]
+pub struct ScoreComponent {
+    pub name: &'static str,
+    pub input: Option<ComponentInput>,
+    pub points: f64,
+    pub cap: Option<f64>,
+}
+
+/// Every component a scorer evaluated, in evaluation order. The score is the
+/// sum of `points`: penalties record only what they actually removed, and
+/// clamping to 0–100 is its own "clamp" component, so the parts always add up.
+#[derive(Debug, Clone, Default)]
+pub struct ScoreBreakdown {
+    pub components: Vec<ScoreComponent>,
+    // Caps configured by the profile (ScoringShape::caps)
+    caps: Vec<(&'static str, f64)>,
+}
+
+impl ScoreBreakdown {
+    /// An empty breakdown that holds components to the profile's caps.
+    fn shaped(shape: &ScoringShape) -> Self {
+        ScoreBreakdown { components: Vec::new(), caps: shape.caps.clone() }
+    }
+
+    fn configured_cap(&self, name: &str) -> Option<f64> {
+        self.caps.iter().find(|(capped, _)| *capped == name).map(|&(_, cap)| cap)
+    }
+
+    fn add(&mut self, name: &'static str, input: Option<ComponentInput>, points: f64) {
+        let cap = self.configured_cap(name);
+        let points = cap.map_or(points, |cap| points.clamp(-cap, cap));
+        self.components.push(ScoreComponent { name, input, points, cap });
+    }
+
+    /// A deduction of up to `cap` (or the profile's cap for `name`), limited
+    /// to what's left of the score.
+    fn deduct(&mut self, name: &'static str, input: Option<ComponentInput>, points: f64, cap: f64) {
+        let cap = self.configured_cap(name).unwrap_or(cap);
+        let points = -points.min(cap).min(self.subtotal()).max(0.0);
+        self.components.push(ScoreComponent { name, input, points, cap: Some(cap) });
+    }
+
+    fn subtotal(&self) -> f64 {
+        self.components.iter().map(|c| c.points).sum()
+    }
+
+    /// Close the breakdown with a "clamp" component when the subtotal falls
+    /// outside `low`–`high` (0–100 unless the profile says otherwise).
+    fn clamp(mut self, (low, high): (f64, f64)) -> Self {
+        let subtotal = self.subtotal();
+        let clamped = subtotal.clamp(low, high);
+        if clamped != subtotal {
+            let cap = if subtotal > high { high } else { low };
+            self.components.push(ScoreComponent {
+                name: "clamp",
+                input: Some(ComponentInput::Float(subtotal)),
+                points: clamped - subtotal,
+                cap: Some(cap),
+            });
+        }
+        self
+    }
+
+    pub fn score(&self) -> u32 {
+        self.subtotal().round().clamp(0.0, 100.0) as u32
+    }
+}
+
+impl<'py> IntoPyObject<'py> for ScoreBreakdown {
+    type Target = PyDict;
+    type Output = Bound<'py, PyDict>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("total", self.score())?;
+        dict.set_item("components", self.components)?;
+        Ok(dict)
+    }
+}
+
+fn text_input(value: Option<String>) -> Option<ComponentInput> {
+    value.map(ComponentInput::Text)
+}
+
+fn bool_input(value: bool) -> Option<ComponentInput> {
+    Some(ComponentInput::Bool(value))
 }
 
 // ---------------------------------------------------------------------------
@@ -62,49 +456,266 @@ const WEIGHT_GOOD_RATING: u32 = 10;
 const WEIGHT_REVIEW_COUNT: u32 = 10;
 const WEIGHT_ADS_PRESENCE: u32 = 10;
 const WEIGHT_ORGANIC_TOP10: u32 = 15;
+// Deducted in proportion to signals.digital_maturity (0–100): a well-built
+// site is a poor target for a rebuild pitch
+const WEIGHT_DIGITAL_MATURITY: u32 = 10;
+// A business that is hiring has budget to spend
+const WEIGHT_HIRING: u32 = 5;
+// A directory listing given as the website earns no website points and
+// costs these: the business has no site of its own to pitch against
+const WEIGHT_DIRECTORY_LISTING: u32 = 10;
+// Per live A/B testing tool (marketing maturity), up to WEIGHT_AB_TESTING_MAX
+const WEIGHT_AB_TESTING: u32 = 5;
+const WEIGHT_AB_TESTING_MAX: u32 = 10;
+// Chains and franchises are outside the ICP: this many locations zeroes fit
+const MAX_FIT_LOCATIONS: i64 = 5;
 
-fn fit_score_inner(py: Python<'_>, prospect: &HashMap<String, PyObject>) -> u32 {
-    let mut score: u32 = 0;
+/// Points per fit component. Keys of the `weights` dict accepted by
+/// `calculate_fit_score`; `digital_maturity` is deducted at full maturity,
+/// `ab_testing` per live tool and `directory_listing` when the website is a
+/// directory listing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FitWeights {
+    website: f64,
+    phone: f64,
+    email: f64,
+    maps_presence: f64,
+    good_rating: f64,
+    review_count: f64,
+    ads_presence: f64,
+    organic_top10: f64,
+    digital_maturity: f64,
+    hiring: f64,
+    ab_testing: f64,
+    directory_listing: f64,
+}
 
-    if extract_opt_string(py, prospect, "website").is_some() {
-        score += WEIGHT_WEBSITE;
-    }
-    if extract_opt_string(py, prospect, "phone").is_some() {
-        score += WEIGHT_PHONE;
+impl Default for FitWeights {
+    fn default() -> Self {
+        FitWeights {
+            website: WEIGHT_WEBSITE as f64,
+            phone: WEIGHT_PHONE as f64,
+            email: WEIGHT_EMAIL as f64,
+            maps_presence: WEIGHT_MAPS_PRESENCE as f64,
+            good_rating: WEIGHT_GOOD_RATING as f64,
+            review_count: WEIGHT_REVIEW_COUNT as f64,
+            ads_presence: WEIGHT_ADS_PRESENCE as f64,
+            organic_top10: WEIGHT_ORGANIC_TOP10 as f64,
+            digital_maturity: WEIGHT_DIGITAL_MATURITY as f64,
+            hiring: WEIGHT_HIRING as f64,
+            ab_testing: WEIGHT_AB_TESTING as f64,
+            directory_listing: WEIGHT_DIRECTORY_LISTING as f64,
+        }
     }
-    if extract_list_nonempty(py, prospect, "emails") {
-        score += WEIGHT_EMAIL;
+}
+
+impl FitWeights {
+    const NAMES: &'static [&'static str] = &[
+        "website", "phone", "email", "maps_presence", "good_rating", "review_count",
+        "ads_presence", "organic_top10", "digital_maturity", "hiring", "ab_testing",
+        "directory_listing",
+    ];
+
+    fn get(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "website" => self.website,
+            "phone" => self.phone,
+            "email" => self.email,
+            "maps_presence" => self.maps_presence,
+            "good_rating" => self.good_rating,
+            "review_count" => self.review_count,
+            "ads_presence" => self.ads_presence,
+            "organic_top10" => self.organic_top10,
+            "digital_maturity" => self.digital_maturity,
+            "hiring" => self.hiring,
+            "ab_testing" => self.ab_testing,
+            "directory_listing" => self.directory_listing,
+            _ => return None,
+        })
     }
-    if extract_bool(py, prospect, "found_in_maps") {
-        score += WEIGHT_MAPS_PRESENCE;
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut f64> {
+        Some(match name {
+            "website" => &mut self.website,
+            "phone" => &mut self.phone,
+            "email" => &mut self.email,
+            "maps_presence" => &mut self.maps_presence,
+            "good_rating" => &mut self.good_rating,
+            "review_count" => &mut self.review_count,
+            "ads_presence" => &mut self.ads_presence,
+            "organic_top10" => &mut self.organic_top10,
+            "digital_maturity" => &mut self.digital_maturity,
+            "hiring" => &mut self.hiring,
+            "ab_testing" => &mut self.ab_testing,
+            "directory_listing" => &mut self.directory_listing,
+            _ => return None,
+        })
     }
-    if let Some(rating) = extract_opt_f64(py, prospect, "rating") {
-        if rating >= 4.0 {
-            score += WEIGHT_GOOD_RATING;
+
+    /// These weights overridden by `overrides`. Unknown names, negative or
+    /// non-finite weights, and an all-zero set raise ValueError.
+    pub(crate) fn with_overrides(self, overrides: Option<&HashMap<String, f64>>) -> PyResult<Self> {
+        let mut weights = self;
+        let Some(overrides) = overrides else { return Ok(weights) };
+        for (name, value) in overrides {
+            let Some(slot) = weights.get_mut(name) else {
+                return Err(PyValueError::new_err(format!(
+                    "unknown fit weight {name:?}; valid components: {}",
+                    FitWeights::NAMES.join(", ")
+                )));
+            };
+            if !value.is_finite() || *value < 0.0 {
+                return Err(PyValueError::new_err(format!(
+                    "fit weight {name:?} must be a non-negative number, got {value}"
+                )));
+            }
+            *slot = *value;
         }
-    }
-    if let Some(rc) = extract_opt_i64(py, prospect, "review_count") {
-        if rc >= 10 {
-            score += WEIGHT_REVIEW_COUNT;
+        if FitWeights::NAMES.iter().all(|name| weights.get_mut(name).is_some_and(|w| *w == 0.0)) {
+            return Err(PyValueError::new_err("at least one fit weight must be non-zero"));
         }
+        Ok(weights)
     }
-    if extract_bool(py, prospect, "found_in_ads") {
-        score += WEIGHT_ADS_PRESENCE;
+}
+
+fn fit_breakdown(prospect: &ProspectInputs, profile: &ScoringProfile) -> ScoreBreakdown {
+    let weights = &profile.fit;
+    let thresholds = &profile.thresholds;
+    let shape = &profile.shape;
+    let mut b = ScoreBreakdown::shaped(shape);
+    let points = |hit: bool, weight: f64| if hit { weight } else { 0.0 };
+
+    // A Yelp or Yellow Pages page isn't an owned website
+    let directory = prospect.directory_listing();
+    let has_website = prospect.has_usable_website() && directory.is_none();
+    b.add("website", bool_input(has_website), points(has_website, weights.website));
+
+    let phone = prospect.phone.clone();
+    let has_phone = phone.is_some();
+    b.add("phone", text_input(phone), points(has_phone, weights.phone));
+
+    let emails = prospect.email_count;
+    b.add("email", Some(ComponentInput::Int(emails as i64)), points(emails > 0, weights.email));
+
+    let in_maps = prospect.found_in_maps;
+    b.add("maps_presence", bool_input(in_maps), points(in_maps, weights.maps_presence));
+
+    let rating = prospect.rating;
+    let good_rating = rating.is_some_and(|r| r >= thresholds.good_rating);
+    b.add(
+        "good_rating",
+        rating.map(ComponentInput::Float),
+        shape.points("good_rating", rating, good_rating, weights.good_rating),
+    );
+
+    let reviews = prospect.review_count;
+    let many_reviews = reviews.is_some_and(|rc| rc as f64 >= thresholds.min_reviews);
+    let review_value = reviews.map(|rc| rc as f64);
+    let review_points =
+        shape.points("review_count", review_value, many_reviews, weights.review_count);
+    b.add("review_count", reviews.map(ComponentInput::Int), review_points);
+
+    let in_ads = prospect.found_in_ads;
+    b.add("ads_presence", bool_input(in_ads), points(in_ads, weights.ads_presence));
+
+    let organic = prospect.organic_position.filter(|_| prospect.found_in_organic);
+    b.add(
+        "organic_top10",
+        organic.map(ComponentInput::Int),
+        points(organic.is_some_and(|pos| pos <= 10), weights.organic_top10),
+    );
+
+    if let Some(domain) = directory {
+        let penalty = weights.directory_listing;
+        b.deduct("directory_listing", text_input(Some(domain)), penalty, penalty);
     }
-    if extract_bool(py, prospect, "found_in_organic") {
-        if let Some(pos) = extract_opt_i64(py, prospect, "organic_position") {
-            if pos <= 10 {
-                score += WEIGHT_ORGANIC_TOP10;
-            }
+
+    if let Some(signals) = &prospect.signals {
+        // Only present when the caller ran detect_locations
+        if let Some(n) = signals.estimated_location_count.filter(|n| *n >= MAX_FIT_LOCATIONS) {
+            let subtotal = b.subtotal();
+            b.add("multi_location", Some(ComponentInput::Int(n)), -subtotal);
+            return b.clamp(shape.clamp);
+        }
+
+        let hiring = signals.is_hiring;
+        let hiring_points = points(hiring == Some(true), weights.hiring);
+        b.add("hiring", hiring.map(ComponentInput::Bool), hiring_points);
+
+        // A team running experiments already has marketing help; a dead
+        // Google Optimize tag left behind doesn't count
+        if let Some(tools) = &signals.ab_testing_tools {
+            let live = tools.iter().filter(|t| *t != crate::html::GOOGLE_OPTIMIZE).count();
+            b.deduct(
+                "ab_testing",
+                Some(ComponentInput::Int(live as i64)),
+                live as f64 * weights.ab_testing,
+                WEIGHT_AB_TESTING_MAX as f64,
+            );
+        }
+        if let Some(maturity) = signals.digital_maturity {
+            let scaled = maturity.clamp(0, 100) as f64 * weights.digital_maturity / 100.0;
+            b.deduct(
+                "digital_maturity",
+                Some(ComponentInput::Int(maturity)),
+                scaled.floor(),
+                weights.digital_maturity,
+            );
         }
     }
 
-    score.min(100)
+    b.clamp(shape.clamp)
 }
 
+/// Fit score (0–100). `profile` picks a preset (see `register_scoring_profile`;
+/// "default" when None) and `weights` then overrides the points of the named
+/// components (see `get_default_fit_weights`); missing keys keep the profile's.
+///
+/// Missing and None fields count as absent. Any other value of the wrong type
+/// raises ValueError when `strict`; otherwise numeric strings such as `"42"`
+/// are read as numbers and anything else counts as absent.
 #[pyfunction]
-pub fn calculate_fit_score(prospect: HashMap<String, PyObject>) -> u32 {
-    Python::with_gil(|py| fit_score_inner(py, &prospect))
+#[pyo3(signature = (prospect, weights=None, profile=None, strict=false))]
+pub fn calculate_fit_score(
+    prospect: HashMap<String, PyObject>,
+    weights: Option<HashMap<String, f64>>,
+    profile: Option<&str>,
+    strict: bool,
+) -> PyResult<u32> {
+    let profile = resolve_profile(profile, weights.as_ref())?;
+    let inputs = Python::with_gil(|py| ProspectInputs::extract(py, &prospect, strict))?;
+    Ok(fit_breakdown(&inputs, &profile).score())
+}
+
+/// `calculate_fit_score` with its working: `{"total": int, "components":
+/// [{name, input, points, cap}]}`.
+///
+/// Components are listed in evaluation order with the input value read from
+/// the prospect (None when absent) and the points contributed; penalties are
+/// negative and carry their cap. `points` always sum to `total`.
+#[pyfunction]
+#[pyo3(signature = (prospect, weights=None, profile=None, strict=false))]
+pub fn explain_fit_score(
+    py: Python<'_>,
+    prospect: HashMap<String, PyObject>,
+    weights: Option<HashMap<String, f64>>,
+    profile: Option<&str>,
+    strict: bool,
+) -> PyResult<ScoreBreakdown> {
+    let profile = resolve_profile(profile, weights.as_ref())?;
+    Ok(fit_breakdown(&ProspectInputs::extract(py, &prospect, strict)?, &profile))
+}
+
+/// The built-in fit weights by component name, for tweaking and passing back
+/// as `weights`.
+#[pyfunction]
+pub fn get_default_fit_weights(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let mut weights = FitWeights::default();
+    let dict = PyDict::new(py);
+    for name in FitWeights::NAMES {
+        dict.set_item(name, weights.get_mut(name).copied())?;
+    }
+    Ok(dict)
 }
 
 // ---------------------------------------------------------------------------
@@ -117,127 +728,4161 @@ const OPP_NO_BOOKING: i32 = 15;
 const OPP_NO_CONTACT: i32 = 10;
 const OPP_WEAK_CMS: i32 = 10;
 const OPP_SLOW_SITE: i32 = 10;
+const OPP_LEGACY_JQUERY: i32 = 5;
+const OPP_NO_ONLINE_ORDERING: i32 = 10;
+const OPP_PLACEHOLDER_PAGE: i32 = 40;
+// Scaled by signals.legacy_score (0–100)
+const OPP_LEGACY_MARKUP: i32 = 15;
 const OPP_RUNNING_ADS_PENALTY: i32 = -10;
 const OPP_GOOD_TRACKING_PENALTY: i32 = -10;
 const OPP_POOR_MAPS: i32 = 10;
 const OPP_POOR_ORGANIC: i32 = 20;
+// signals.has_responsive confirmed false
+const OPP_NOT_RESPONSIVE: i32 = 15;
+// signals.has_legacy_framework: AngularJS 1.x, React <16, Vue 1.x
+const OPP_LEGACY_FRAMEWORK: i32 = 10;
+// CMS major version below OUTDATED_CMS_MAJOR, from cms_version or generator
+const OPP_OUTDATED_CMS: i32 = 10;
+// signals.is_thin_content, or word_count below html::THIN_CONTENT_WORDS
+const OPP_THIN_CONTENT: i32 = 10;
+// Footer copyright at least ScoringThresholds::stale_copyright_years old
+const OPP_STALE_COPYRIGHT: i32 = 5;
+// Reputation::LowVolume: a review-generation pitch
+const OPP_LOW_VOLUME: i32 = 10;
+// Reputation::LowRating: a reputation-repair pitch
+const OPP_LOW_RATING: i32 = 10;
+
+// Oldest major version of each CMS still getting regular security releases
+const OUTDATED_CMS_MAJOR: &[(&str, u32)] = &[
+    ("wordpress", 6),
+    ("joomla", 4),
+    ("drupal", 10),
+    ("magento", 2),
+    ("prestashop", 8),
+    ("typo3", 12),
+    ("ghost", 5),
+];
+const OPP_NO_WEBSITE: i32 = 80;
+const OPP_NOT_ANALYZED: i32 = 50;
 
-fn opportunity_score_inner(py: Python<'_>, prospect: &HashMap<String, PyObject>) -> u32 {
-    // No website → huge opportunity
-    if extract_opt_string(py, prospect, "website").is_none() {
-        return 80;
+/// Points per opportunity component, penalties negative. Whole numbers so
+/// `legacy_markup` scales with integer division like opportunity.py.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct OpportunityWeights {
+    no_analytics: i32,
+    no_pixel: i32,
+    no_booking: i32,
+    no_contact: i32,
+    weak_cms: i32,
+    slow_site: i32,
+    legacy_jquery: i32,
+    no_online_ordering: i32,
+    placeholder_page: i32,
+    legacy_markup: i32,
+    running_ads_penalty: i32,
+    good_tracking_penalty: i32,
+    poor_maps: i32,
+    poor_organic: i32,
+    not_responsive: i32,
+    legacy_framework: i32,
+    outdated_cms: i32,
+    thin_content: i32,
+    stale_copyright: i32,
+    low_volume: i32,
+    low_rating: i32,
+    no_website: i32,
+    not_analyzed: i32,
+}
+
+impl Default for OpportunityWeights {
+    fn default() -> Self {
+        OpportunityWeights {
+            no_analytics: OPP_NO_ANALYTICS,
+            no_pixel: OPP_NO_PIXEL,
+            no_booking: OPP_NO_BOOKING,
+            no_contact: OPP_NO_CONTACT,
+            weak_cms: OPP_WEAK_CMS,
+            slow_site: OPP_SLOW_SITE,
+            legacy_jquery: OPP_LEGACY_JQUERY,
+            no_online_ordering: OPP_NO_ONLINE_ORDERING,
+            placeholder_page: OPP_PLACEHOLDER_PAGE,
+            legacy_markup: OPP_LEGACY_MARKUP,
+            running_ads_penalty: OPP_RUNNING_ADS_PENALTY,
+            good_tracking_penalty: OPP_GOOD_TRACKING_PENALTY,
+            poor_maps: OPP_POOR_MAPS,
+            poor_organic: OPP_POOR_ORGANIC,
+            not_responsive: OPP_NOT_RESPONSIVE,
+            legacy_framework: OPP_LEGACY_FRAMEWORK,
+            outdated_cms: OPP_OUTDATED_CMS,
+            thin_content: OPP_THIN_CONTENT,
+            stale_copyright: OPP_STALE_COPYRIGHT,
+            low_volume: OPP_LOW_VOLUME,
+            low_rating: OPP_LOW_RATING,
+            no_website: OPP_NO_WEBSITE,
+            not_analyzed: OPP_NOT_ANALYZED,
+        }
+    }
+}
+
+impl OpportunityWeights {
+    const NAMES: &'static [&'static str] = &[
+        "no_analytics", "no_pixel", "no_booking", "no_contact", "weak_cms", "slow_site",
+        "legacy_jquery", "no_online_ordering", "placeholder_page", "legacy_markup",
+        "running_ads_penalty", "good_tracking_penalty", "poor_maps", "poor_organic",
+        "not_responsive", "legacy_framework", "outdated_cms", "thin_content", "stale_copyright",
+        "low_volume", "low_rating", "no_website", "not_analyzed",
+    ];
+
+    fn get(&self, name: &str) -> Option<i32> {
+        Some(match name {
+            "no_analytics" => self.no_analytics,
+            "no_pixel" => self.no_pixel,
+            "no_booking" => self.no_booking,
+            "no_contact" => self.no_contact,
+            "weak_cms" => self.weak_cms,
+            "slow_site" => self.slow_site,
+            "legacy_jquery" => self.legacy_jquery,
+            "no_online_ordering" => self.no_online_ordering,
+            "placeholder_page" => self.placeholder_page,
+            "legacy_markup" => self.legacy_markup,
+            "running_ads_penalty" => self.running_ads_penalty,
+            "good_tracking_penalty" => self.good_tracking_penalty,
+            "poor_maps" => self.poor_maps,
+            "poor_organic" => self.poor_organic,
+            "not_responsive" => self.not_responsive,
+            "legacy_framework" => self.legacy_framework,
+            "outdated_cms" => self.outdated_cms,
+            "thin_content" => self.thin_content,
+            "stale_copyright" => self.stale_copyright,
+            "low_volume" => self.low_volume,
+            "low_rating" => self.low_rating,
+            "no_website" => self.no_website,
+            "not_analyzed" => self.not_analyzed,
+            _ => return None,
+        })
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut i32> {
+        Some(match name {
+            "no_analytics" => &mut self.no_analytics,
+            "no_pixel" => &mut self.no_pixel,
+            "no_booking" => &mut self.no_booking,
+            "no_contact" => &mut self.no_contact,
+            "weak_cms" => &mut self.weak_cms,
+            "slow_site" => &mut self.slow_site,
+            "legacy_jquery" => &mut self.legacy_jquery,
+            "no_online_ordering" => &mut self.no_online_ordering,
+            "placeholder_page" => &mut self.placeholder_page,
+            "legacy_markup" => &mut self.legacy_markup,
+            "running_ads_penalty" => &mut self.running_ads_penalty,
+            "good_tracking_penalty" => &mut self.good_tracking_penalty,
+            "poor_maps" => &mut self.poor_maps,
+            "poor_organic" => &mut self.poor_organic,
+            "not_responsive" => &mut self.not_responsive,
+            "legacy_framework" => &mut self.legacy_framework,
+            "outdated_cms" => &mut self.outdated_cms,
+            "thin_content" => &mut self.thin_content,
+            "stale_copyright" => &mut self.stale_copyright,
+            "low_volume" => &mut self.low_volume,
+            "low_rating" => &mut self.low_rating,
+            "no_website" => &mut self.no_website,
+            "not_analyzed" => &mut self.not_analyzed,
+            _ => return None,
+        })
+    }
+}
+
+/// The CMS name and version, from `cms` + `cms_version` or else a generator
+/// meta such as "WordPress 5.8.1".
+fn cms_release(signals: &SignalInputs) -> Option<(String, String)> {
+    if let (Some(cms), Some(version)) = (&signals.cms, &signals.cms_version) {
+        return Some((cms.clone(), version.clone()));
+    }
+    let generator = crate::metadata::parse_generator(signals.generator.as_deref()?)?;
+    Some((generator.name, generator.version))
+}
+
+fn is_outdated_cms(name: &str, version: &str) -> bool {
+    let name = name.to_lowercase();
+    let Some(major) = version.trim().split('.').next().and_then(|m| m.parse::<u32>().ok()) else {
+        return false;
+    };
+    OUTDATED_CMS_MAJOR
+        .iter()
+        .find(|(cms, _)| name.split_whitespace().next() == Some(cms))
+        .is_some_and(|(_, supported)| major < *supported)
+}
+
+fn opportunity_breakdown(prospect: &ProspectInputs, profile: &ScoringProfile) -> ScoreBreakdown {
+    let weights = &profile.opportunity;
+    let shape = &profile.shape;
+    let mut b = ScoreBreakdown::shaped(shape);
+    let points = |hit: bool, weight: i32| if hit { weight as f64 } else { 0.0 };
+
+    // No website (or only a parked domain) → huge opportunity
+    if !prospect.has_usable_website() {
+        b.add("no_website", bool_input(false), weights.no_website as f64);
+        return b.clamp(shape.clamp);
     }
 
-    let signals = match extract_signals(py, prospect) {
-        Some(s) => s,
-        None => return 50, // can't analyse
+    let Some(signals) = &prospect.signals else {
+        // Can't analyse
+        b.add("not_analyzed", None, weights.not_analyzed as f64);
+        return b.clamp(shape.clamp);
     };
 
-    let mut score: i32 = 0;
+    let analytics = signals.has_google_analytics;
+    let pixel = signals.has_facebook_pixel;
 
     // Missing GA (confirmed false) → +15
-    if extract_opt_bool(py, &signals, "has_google_analytics") == Some(false) {
-        score += OPP_NO_ANALYTICS;
-    }
+    b.add(
+        "no_analytics",
+        analytics.map(ComponentInput::Bool),
+        points(analytics == Some(false), weights.no_analytics),
+    );
 
     // Missing FB pixel (confirmed false) → +10
-    if extract_opt_bool(py, &signals, "has_facebook_pixel") == Some(false) {
-        score += OPP_NO_PIXEL;
-    }
+    let no_pixel = points(pixel == Some(false), weights.no_pixel);
+    b.add("no_pixel", pixel.map(ComponentInput::Bool), no_pixel);
 
     // No booking (confirmed false) → +15
-    if extract_opt_bool(py, &signals, "has_booking_system") == Some(false) {
-        score += OPP_NO_BOOKING;
-    }
+    let booking = signals.has_booking_system;
+    b.add(
+        "no_booking",
+        booking.map(ComponentInput::Bool),
+        points(booking == Some(false), weights.no_booking),
+    );
 
     // No contact emails → +10
-    if !extract_list_nonempty(py, &signals, "emails") {
-        score += OPP_NO_CONTACT;
-    }
+    let emails = signals.email_count;
+    let no_contact = points(emails == 0, weights.no_contact);
+    b.add("no_contact", Some(ComponentInput::Int(emails as i64)), no_contact);
 
     // Weak CMS → +10
     let weak_cms = ["Wix", "Weebly", "GoDaddy Website Builder"];
-    if let Some(cms) = extract_opt_string(py, &signals, "cms") {
-        if weak_cms.contains(&cms.as_str()) {
-            score += OPP_WEAK_CMS;
-        }
-    }
+    let cms = signals.cms.clone();
+    let is_weak = cms.as_deref().is_some_and(|cms| weak_cms.contains(&cms));
+    b.add("weak_cms", text_input(cms), points(is_weak, weights.weak_cms));
 
-    // Slow site (>3000ms) → +10
-    if let Some(load_time) = extract_opt_i64(py, &signals, "load_time_ms") {
-        if load_time > 3000 {
-            score += OPP_SLOW_SITE;
-        }
+    // Slow site (>3000ms by default) → +10
+    let load_time = signals.load_time_ms;
+    let slow = load_time.is_some_and(|ms| ms as f64 > profile.thresholds.slow_site_ms);
+    let load_ms = load_time.map(|ms| ms as f64);
+    let slow_points = shape.points("slow_site", load_ms, slow, weights.slow_site as f64);
+    b.add("slow_site", load_time.map(ComponentInput::Int), slow_points);
+
+    // jQuery < 3 (usually a 2010s theme nobody maintains) → +5
+    let legacy_jquery = signals.has_legacy_jquery;
+    b.add(
+        "legacy_jquery",
+        legacy_jquery.map(ComponentInput::Bool),
+        points(legacy_jquery == Some(true), weights.legacy_jquery),
+    );
+
+    // No first-party online ordering (confirmed false; hospitality) → +10
+    let ordering = signals.has_online_ordering;
+    b.add(
+        "no_online_ordering",
+        ordering.map(ComponentInput::Bool),
+        points(ordering == Some(false), weights.no_online_ordering),
+    );
+
+    // Coming-soon / under-construction page: they know they need a site → +40
+    let placeholder = signals.is_placeholder;
+    b.add(
+        "placeholder_page",
+        placeholder.map(ComponentInput::Bool),
+        points(placeholder == Some(true), weights.placeholder_page),
+    );
+
+    // Table layouts, <font> tags, Flash → up to +15
+    if let Some(legacy) = signals.legacy_score {
+        let scaled = legacy.clamp(0, 100) as i32 * weights.legacy_markup / 100;
+        b.add("legacy_markup", Some(ComponentInput::Int(legacy)), scaled as f64);
     }
 
+    // Not mobile-friendly (confirmed false) → +15
+    let responsive = signals.has_responsive;
+    b.add(
+        "not_responsive",
+        responsive.map(ComponentInput::Bool),
+        points(responsive == Some(false), weights.not_responsive),
+    );
+
+    // AngularJS 1.x / React <16 / Vue 1.x → +10
+    let legacy_framework = signals.has_legacy_framework;
+    b.add(
+        "legacy_framework",
+        legacy_framework.map(ComponentInput::Bool),
+        points(legacy_framework == Some(true), weights.legacy_framework),
+    );
+
+    // CMS a major version or more behind → +10
+    let cms_release = cms_release(signals);
+    let outdated = cms_release.as_ref().is_some_and(|(cms, version)| is_outdated_cms(cms, version));
+    b.add(
+        "outdated_cms",
+        text_input(cms_release.map(|(name, version)| format!("{name} {version}"))),
+        points(outdated, weights.outdated_cms),
+    );
+
+    // Little to read (content_metrics) → +10
+    let word_count = signals.word_count;
+    let thin = signals
+        .is_thin_content
+        .or(word_count.map(|words| words < crate::html::THIN_CONTENT_WORDS as i64));
+    b.add(
+        "thin_content",
+        word_count.map(ComponentInput::Int).or(thin.map(ComponentInput::Bool)),
+        points(thin == Some(true), weights.thin_content),
+    );
+
+    // Footer copyright not updated in years (detect_copyright_year) → +5.
+    // Only the staleness is read: a bare year would make the score depend
+    // on when it was computed.
+    let years_stale = signals.copyright_years_stale;
+    let stale = years_stale.is_some_and(|y| y as f64 >= profile.thresholds.stale_copyright_years);
+    b.add(
+        "stale_copyright",
+        years_stale.map(ComponentInput::Int),
+        shape.points(
+            "stale_copyright",
+            years_stale.map(|y| y as f64),
+            stale,
+            weights.stale_copyright as f64,
+        ),
+    );
+
     // Penalty: already running ads
-    if extract_bool(py, prospect, "found_in_ads") {
-        score += OPP_RUNNING_ADS_PENALTY;
-    }
+    let in_ads = prospect.found_in_ads;
+    b.add("running_ads_penalty", bool_input(in_ads), points(in_ads, weights.running_ads_penalty));
 
     // Penalty: has both GA AND FB pixel (both confirmed true)
-    if extract_opt_bool(py, &signals, "has_google_analytics") == Some(true)
-        && extract_opt_bool(py, &signals, "has_facebook_pixel") == Some(true)
-    {
-        score += OPP_GOOD_TRACKING_PENALTY;
-    }
+    let good_tracking = analytics == Some(true) && pixel == Some(true);
+    b.add(
+        "good_tracking_penalty",
+        bool_input(good_tracking),
+        points(good_tracking, weights.good_tracking_penalty),
+    );
 
     // Poor Maps ranking (found in maps but position > 1)
-    if extract_bool(py, prospect, "found_in_maps") {
-        if let Some(pos) = extract_opt_i64(py, prospect, "maps_position") {
-            if pos > 1 {
-                score += OPP_POOR_MAPS;
-            }
-        }
-    }
+    let maps_position = prospect.maps_position.filter(|_| prospect.found_in_maps);
+    b.add(
+        "poor_maps",
+        maps_position.map(ComponentInput::Int),
+        points(maps_position.is_some_and(|pos| pos > 1), weights.poor_maps),
+    );
 
     // Poor or no organic ranking
-    if !extract_bool(py, prospect, "found_in_organic") {
-        score += OPP_POOR_ORGANIC;
-    } else if let Some(pos) = extract_opt_i64(py, prospect, "organic_position") {
-        if pos > 5 {
-            score += OPP_POOR_ORGANIC;
+    let in_organic = prospect.found_in_organic;
+    let organic_position = prospect.organic_position;
+    let poor_organic = !in_organic || organic_position.is_some_and(|pos| pos > 5);
+    b.add(
+        "poor_organic",
+        if in_organic { organic_position.map(ComponentInput::Int) } else { bool_input(false) },
+        points(poor_organic, weights.poor_organic),
+    );
+
+    // Few reviews, or a poor rating (reputation_signal) → +10 each
+    let thresholds = &profile.thresholds;
+    let reputation = Reputation::classify(prospect.rating, prospect.review_count, thresholds);
+    let reputation_input = text_input(Some(reputation.as_str().to_string()));
+    b.add(
+        "low_volume",
+        reputation_input.clone(),
+        points(reputation == Reputation::LowVolume, weights.low_volume),
+    );
+    b.add(
+        "low_rating",
+        reputation_input,
+        points(reputation == Reputation::LowRating, weights.low_rating),
+    );
+
+    b.clamp(shape.clamp)
+}
+
+/// Opportunity score (0–100) under `profile` ("default" when None); `strict`
+/// as for `calculate_fit_score`.
+#[pyfunction]
+#[pyo3(signature = (prospect, profile=None, strict=false))]
+pub fn calculate_opportunity_score(
+    prospect: HashMap<String, PyObject>,
+    profile: Option<&str>,
+    strict: bool,
+) -> PyResult<u32> {
+    let profile = resolve_profile(profile, None)?;
+    let inputs = Python::with_gil(|py| ProspectInputs::extract(py, &prospect, strict))?;
+    Ok(opportunity_breakdown(&inputs, &profile).score())
+}
+
+/// `calculate_opportunity_score` with its working: `{"total": int,
+/// "components": [{name, input, points, cap}]}`, as for `explain_fit_score`.
+#[pyfunction]
+#[pyo3(signature = (prospect, profile=None, strict=false))]
+pub fn explain_opportunity_score(
+    py: Python<'_>,
+    prospect: HashMap<String, PyObject>,
+    profile: Option<&str>,
+    strict: bool,
+) -> PyResult<ScoreBreakdown> {
+    let profile = resolve_profile(profile, None)?;
+    Ok(opportunity_breakdown(&ProspectInputs::extract(py, &prospect, strict)?, &profile))
+}
+
+/// The built-in opportunity points by component name, penalties negative;
+/// the names used in `explain_opportunity_score` and in a profile's
+/// `"opportunity"` weights.
+///
+/// Tech-stack components read these `signals` keys, so an
+/// `analyze_tech_stack` report merged with `content_metrics` and
+/// `detect_copyright_year` results can be passed as-is:
+///   - `not_responsive`: `has_responsive`
+///   - `legacy_framework`: `has_legacy_framework`
+///   - `outdated_cms`: `cms` + `cms_version`, or `generator`
+///   - `thin_content`: `is_thin_content`, or `word_count`
+///   - `stale_copyright`: `copyright_years_stale`
+///
+/// `low_volume` and `low_rating` read `rating` and `review_count` as
+/// `reputation_signal` does, with its classification as their input.
+///
+/// `tracking` and `has_booking` stand in for `has_google_analytics` /
+/// `has_facebook_pixel` and `has_booking_system` when those are absent.
+#[pyfunction]
+pub fn get_default_opportunity_weights(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let mut weights = OpportunityWeights::default();
+    let dict = PyDict::new(py);
+    for name in OpportunityWeights::NAMES {
+        dict.set_item(name, weights.get_mut(name).copied())?;
+    }
+    Ok(dict)
+}
+
+// ---------------------------------------------------------------------------
+// Reputation signal
+// ---------------------------------------------------------------------------
+
+/// What a prospect's Google reviews say about the pitch to make.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reputation {
+    /// No reviews and no rating.
+    NoPresence,
+    /// Rated below `good_rating`, however many reviews: reputation repair.
+    LowRating,
+    /// Well rated (or unrated) but under `min_reviews`: review generation.
+    LowVolume,
+    Strong,
+}
+
+impl Reputation {
+    /// A rating of exactly `good_rating` or a count of exactly `min_reviews`
+    /// passes, as in fit scoring.
+    fn classify(
+        rating: Option<f64>,
+        review_count: Option<i64>,
+        thresholds: &ScoringThresholds,
+    ) -> Self {
+        let reviews = review_count.unwrap_or(0).max(0);
+        if reviews == 0 && rating.is_none() {
+            Reputation::NoPresence
+        } else if rating.is_some_and(|r| r < thresholds.good_rating) {
+            Reputation::LowRating
+        } else if (reviews as f64) < thresholds.min_reviews {
+            Reputation::LowVolume
+        } else {
+            Reputation::Strong
         }
     }
 
-    score.clamp(0, 100) as u32
+    fn as_str(self) -> &'static str {
+        match self {
+            Reputation::NoPresence => "no_presence",
+            Reputation::LowRating => "low_rating",
+            Reputation::LowVolume => "low_volume",
+            Reputation::Strong => "strong",
+        }
+    }
 }
 
+/// Classify a Google rating and review count: "no_presence" (neither),
+/// "low_rating" (below `good_rating`, 4.0 by default), "low_volume" (fewer
+/// than `min_reviews`, 10 by default) or "strong". A poor rating wins over
+/// a low count; exactly 4.0★ or exactly 10 reviews passes.
+///
+/// Thresholds come from `profile` ("default" when None), then the keyword
+/// overrides. Returns `{"classification", "rating", "review_count",
+/// "good_rating", "min_reviews"}`; the same classification drives the
+/// `low_volume` / `low_rating` opportunity components.
 #[pyfunction]
-pub fn calculate_opportunity_score(prospect: HashMap<String, PyObject>) -> u32 {
-    Python::with_gil(|py| opportunity_score_inner(py, &prospect))
+#[pyo3(signature = (rating, review_count, profile=None, good_rating=None, min_reviews=None))]
+pub fn reputation_signal<'py>(
+    py: Python<'py>,
+    rating: Option<f64>,
+    review_count: Option<i64>,
+    profile: Option<&str>,
+    good_rating: Option<f64>,
+    min_reviews: Option<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut thresholds = resolve_profile(profile, None)?.thresholds;
+    thresholds.good_rating = good_rating.unwrap_or(thresholds.good_rating);
+    thresholds.min_reviews = min_reviews.unwrap_or(thresholds.min_reviews);
+    let dict = PyDict::new(py);
+    let classification = Reputation::classify(rating, review_count, &thresholds);
+    dict.set_item("classification", classification.as_str())?;
+    dict.set_item("rating", rating)?;
+    dict.set_item("review_count", review_count)?;
+    dict.set_item("good_rating", thresholds.good_rating)?;
+    dict.set_item("min_reviews", thresholds.min_reviews)?;
+    Ok(dict)
 }
 
 // ---------------------------------------------------------------------------
-// Batch scoring with Rayon
+// Opportunity notes  (prospect/scoring/notes.py)
 // ---------------------------------------------------------------------------
 
+// Phrase per triggered opportunity component, in breakdown order; "{input}"
+// is replaced with the component's input. `poor_organic` uses
+// "not_in_organic" when the prospect doesn't rank at all.
+const NOTE_TEMPLATES: &[(&str, &str)] = &[
+    ("no_website", "No website found - needs web presence"),
+    ("not_analyzed", "Website not analyzed - technical details unknown"),
+    ("no_analytics", "no Google Analytics"),
+    ("no_pixel", "no Facebook Pixel"),
+    ("no_booking", "no online booking"),
+    ("no_contact", "no visible contact email"),
+    ("weak_cms", "using {input} (limited platform)"),
+    ("slow_site", "slow site ({input}ms load time)"),
+    ("legacy_jquery", "outdated jQuery"),
+    ("no_online_ordering", "no online ordering"),
+    ("placeholder_page", "placeholder page instead of a site"),
+    ("legacy_markup", "legacy markup (score {input})"),
+    ("not_responsive", "not mobile-friendly"),
+    ("legacy_framework", "legacy JavaScript framework"),
+    ("outdated_cms", "outdated CMS ({input})"),
+    ("thin_content", "thin content"),
+    ("stale_copyright", "copyright {input} years out of date"),
+    ("low_volume", "few Google reviews"),
+    ("low_rating", "low Google rating"),
+    ("unreachable", "site unreachable"),
+    ("running_ads_penalty", "already running ads"),
+    ("good_tracking_penalty", "has good tracking setup"),
+    ("poor_maps", "#{input} in local pack (not #1)"),
+    ("poor_organic", "ranking #{input} in organic (room to improve)"),
+    ("not_in_organic", "not ranking in organic search"),
+];
+
+/// Note phrases by template key: the defaults with `overrides` applied. An
+/// empty override drops that phrase; unknown keys raise ValueError.
+fn note_templates(
+    overrides: Option<&HashMap<String, String>>,
+) -> PyResult<HashMap<&'static str, String>> {
+    let mut templates: HashMap<&'static str, String> =
+        NOTE_TEMPLATES.iter().map(|(key, phrase)| (*key, phrase.to_string())).collect();
+    for (key, phrase) in overrides.into_iter().flatten() {
+        let Some(slot) = templates.get_mut(key.as_str()) else {
+            let keys: Vec<&str> = NOTE_TEMPLATES.iter().map(|(key, _)| *key).collect();
+            return Err(PyValueError::new_err(format!(
+                "unknown note template {key:?}; valid templates: {}",
+                keys.join(", ")
+            )));
+        };
+        *slot = phrase.clone();
+    }
+    Ok(templates)
+}
+
+fn input_text(input: &Option<ComponentInput>) -> String {
+    match input {
+        Some(ComponentInput::Bool(value)) => value.to_string(),
+        Some(ComponentInput::Int(value)) => value.to_string(),
+        Some(ComponentInput::Float(value)) => value.to_string(),
+        Some(ComponentInput::Text(value)) => value.clone(),
+        None => String::new(),
+    }
+}
+
+/// "; "-joined phrases for every opportunity component that moved the score.
+fn opportunity_notes(
+    breakdown: &ScoreBreakdown,
+    templates: &HashMap<&'static str, String>,
+) -> String {
+    let mut notes: Vec<String> = Vec::new();
+    for component in &breakdown.components {
+        if component.points == 0.0 || component.name == "clamp" {
+            continue;
+        }
+        let key = match (component.name, &component.input) {
+            ("poor_organic", Some(ComponentInput::Bool(_))) => "not_in_organic",
+            (name, _) => name,
+        };
+        let Some(template) = templates.get(key).filter(|t| !t.is_empty()) else { continue };
+        notes.push(template.replace("{input}", &input_text(&component.input)));
+    }
+    notes.join("; ")
+}
+
+/// Plain-English opportunity notes for one prospect (dict or object), built
+/// from the components `explain_opportunity_score` reports under `profile`:
+/// one phrase per component that added or removed points, in breakdown
+/// order, joined with "; ". Empty when nothing triggered.
+///
+/// `templates` overrides phrases by component name (as listed by
+/// `get_default_opportunity_weights`, plus "not_in_organic" for prospects
+/// missing from organic results); "{input}" in a phrase is replaced with the
+/// component's input and an empty phrase drops it.
 #[pyfunction]
-pub fn score_prospects_batch(prospects: Vec<HashMap<String, PyObject>>) -> Vec<(u32, u32)> {
-    if prospects.len() <= 10 {
-        // Sequential for small batches
-        Python::with_gil(|py| {
-            prospects
-                .iter()
-                .map(|p| (fit_score_inner(py, p), opportunity_score_inner(py, p)))
-                .collect()
+#[pyo3(signature = (prospect, templates=None, profile=None))]
+pub fn generate_opportunity_notes(
+    py: Python<'_>,
+    prospect: &Bound<'_, PyAny>,
+    templates: Option<HashMap<String, String>>,
+    profile: Option<&str>,
+) -> PyResult<String> {
+    let templates = note_templates(templates.as_ref())?;
+    let profile = resolve_profile(profile, None)?;
+    let fields = prospect_fields(prospect, SCORED_FIELDS, &[("signals", SCORED_SIGNAL_FIELDS)])?;
+    let inputs = ProspectInputs::extract(py, &fields, false)?;
+    Ok(opportunity_notes(&opportunity_breakdown(&inputs, &profile), &templates))
+}
+
+// ---------------------------------------------------------------------------
+// Scoring profiles
+// ---------------------------------------------------------------------------
+
+/// Cut-offs the scorers compare against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ScoringThresholds {
+    /// Minimum Google rating for `good_rating`
+    good_rating: f64,
+    /// Minimum review count for `review_count`
+    min_reviews: f64,
+    /// Load time above which `slow_site` applies
+    slow_site_ms: f64,
+    /// Copyright age in years from which `stale_copyright` applies
+    stale_copyright_years: f64,
+}
+
+impl Default for ScoringThresholds {
+    fn default() -> Self {
+        ScoringThresholds {
+            good_rating: 4.0,
+            min_reviews: 10.0,
+            slow_site_ms: 3000.0,
+            stale_copyright_years: 2.0,
+        }
+    }
+}
+
+impl ScoringThresholds {
+    const NAMES: &'static [&'static str] =
+        &["good_rating", "min_reviews", "slow_site_ms", "stale_copyright_years"];
+
+    fn get(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "good_rating" => self.good_rating,
+            "min_reviews" => self.min_reviews,
+            "slow_site_ms" => self.slow_site_ms,
+            "stale_copyright_years" => self.stale_copyright_years,
+            _ => return None,
         })
-    } else {
-        // Parallel via Rayon for larger batches
-        Python::with_gil(|py| {
-            let results: Vec<(u32, u32)> = prospects
-                .par_iter()
-                .map(|p| {
-                    Python::with_gil(|py_inner| {
-                        (
-                            fit_score_inner(py_inner, p),
-                            opportunity_score_inner(py_inner, p),
-                        )
-                    })
-                })
-                .collect();
-            let _ = py; // keep outer GIL reference alive
-            results
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut f64> {
+        Some(match name {
+            "good_rating" => &mut self.good_rating,
+            "min_reviews" => &mut self.min_reviews,
+            "slow_site_ms" => &mut self.slow_site_ms,
+            "stale_copyright_years" => &mut self.stale_copyright_years,
+            _ => return None,
         })
     }
 }
+
+// Components whose points can follow a curve instead of their threshold
+const CURVED_COMPONENTS: &[&str] = &["review_count", "good_rating", "slow_site", "stale_copyright"];
+
+/// How much of a component's weight a value earns, in place of the
+/// all-or-nothing threshold.
+#[derive(Debug, Clone, PartialEq)]
+enum Curve {
+    /// In proportion to the value, full weight from `cap` up.
+    LinearCapped { cap: f64 },
+    /// `log_base(1 + value)`, full weight from `base - 1` up.
+    Log { base: f64 },
+    /// The fraction of the highest `(at, fraction)` breakpoint the value
+    /// reaches; nothing below the first.
+    Step { breakpoints: Vec<(f64, f64)> },
+}
+
+impl Curve {
+    fn parse(name: &str, spec: &serde_json::Value) -> PyResult<Self> {
+        let invalid = |reason: &str| PyValueError::new_err(format!("curve {name:?}: {reason}"));
+        let spec = spec.as_object().ok_or_else(|| invalid("must be an object"))?;
+        let kind = spec.get("curve").and_then(serde_json::Value::as_str);
+        if let Some(param) = kind.and_then(Curve::param) {
+            if let Some(key) = spec.keys().find(|key| *key != "curve" && *key != param) {
+                return Err(invalid(&format!("unknown key {key:?}; expected \"curve\", {param:?}")));
+            }
+        }
+        let number = |key: &str| {
+            let value = spec.get(key).and_then(serde_json::Value::as_f64);
+            let value = value.filter(|v| v.is_finite());
+            value.ok_or_else(|| invalid(&format!("needs a number {key:?}")))
+        };
+        match kind {
+            Some("linear_capped") => {
+                let cap = number("cap")?;
+                if cap <= 0.0 {
+                    return Err(invalid("\"cap\" must be positive"));
+                }
+                Ok(Curve::LinearCapped { cap })
+            }
+            Some("log") => {
+                let base = number("base")?;
+                if base <= 1.0 {
+                    return Err(invalid("\"base\" must be greater than 1"));
+                }
+                Ok(Curve::Log { base })
+            }
+            Some("step") => {
+                let pairs = spec
+                    .get("breakpoints")
+                    .and_then(serde_json::Value::as_array)
+                    .filter(|pairs| !pairs.is_empty())
+                    .ok_or_else(|| invalid("needs a non-empty \"breakpoints\" list"))?;
+                let mut breakpoints = Vec::with_capacity(pairs.len());
+                for pair in pairs {
+                    let numbers: Option<Vec<f64>> = pair
+                        .as_array()
+                        .map(|pair| pair.iter().map(serde_json::Value::as_f64).collect())
+                        .unwrap_or(None);
+                    match numbers.as_deref() {
+                        Some(&[at, fraction])
+                            if at.is_finite() && (0.0..=1.0).contains(&fraction) =>
+                        {
+                            breakpoints.push((at, fraction))
+                        }
+                        _ => {
+                            return Err(invalid(&format!(
+                                "breakpoint {pair} must be [value, fraction] with fraction 0 to 1"
+                            )))
+                        }
+                    }
+                }
+                breakpoints.sort_by(|a, b| a.0.total_cmp(&b.0));
+                Ok(Curve::Step { breakpoints })
+            }
+            Some(other) => Err(invalid(&format!(
+                "unknown curve {other:?}; expected \"linear_capped\", \"log\" or \"step\""
+            ))),
+            None => Err(invalid("needs a \"curve\" name")),
+        }
+    }
+
+    /// The parameter key each curve takes besides `"curve"`.
+    fn param(kind: &str) -> Option<&'static str> {
+        match kind {
+            "linear_capped" => Some("cap"),
+            "log" => Some("base"),
+            "step" => Some("breakpoints"),
+            _ => None,
+        }
+    }
+
+    /// As written in a profile definition.
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Curve::LinearCapped { cap } => {
+                serde_json::json!({"curve": "linear_capped", "cap": cap})
+            }
+            Curve::Log { base } => serde_json::json!({"curve": "log", "base": base}),
+            Curve::Step { breakpoints } => {
+                let pairs: Vec<[f64; 2]> = breakpoints.iter().map(|&(at, f)| [at, f]).collect();
+                serde_json::json!({"curve": "step", "breakpoints": pairs})
+            }
+        }
+    }
+
+    /// Share of the weight (0–1) earned by `value`.
+    fn fraction(&self, value: f64) -> f64 {
+        let value = value.max(0.0);
+        match self {
+            Curve::LinearCapped { cap } => (value / cap).min(1.0),
+            Curve::Log { base } => (value.ln_1p() / base.ln()).min(1.0),
+            Curve::Step { breakpoints } => breakpoints
+                .iter()
+                .rev()
+                .find(|(at, _)| value >= *at)
+                .map_or(0.0, |&(_, fraction)| fraction),
+        }
+    }
+}
+
+/// Caps, curves and the clamp range: how points are bounded and shaped,
+/// as opposed to how many each component is worth.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScoringShape {
+    /// Range each score is clamped to
+    clamp: (f64, f64),
+    /// Most points a component may add or remove, by name
+    caps: Vec<(&'static str, f64)>,
+    /// Curves replacing thresholds, by component name (CURVED_COMPONENTS)
+    curves: Vec<(&'static str, Curve)>,
+}
+
+impl Default for ScoringShape {
+    fn default() -> Self {
+        ScoringShape { clamp: (0.0, 100.0), caps: Vec::new(), curves: Vec::new() }
+    }
+}
+
+impl ScoringShape {
+    /// Points for a thresholded component: `weight` when `hit`, or the
+    /// configured curve's share of it for `value`.
+    fn points(&self, name: &str, value: Option<f64>, hit: bool, weight: f64) -> f64 {
+        match self.curves.iter().find(|(curved, _)| *curved == name) {
+            Some((_, curve)) => value.map_or(0.0, |v| weight * curve.fraction(v)),
+            None if hit => weight,
+            None => 0.0,
+        }
+    }
+}
+
+/// Everything a campaign can tune about scoring.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ScoringProfile {
+    fit: FitWeights,
+    opportunity: OpportunityWeights,
+    thresholds: ScoringThresholds,
+    shape: ScoringShape,
+}
+
+const DEFAULT_PROFILE: &str = "default";
+
+fn builtin_profiles() -> Vec<(&'static str, ScoringProfile)> {
+    let default = ScoringProfile::default();
+
+    // Trades win work by phone and from the map pack; quote forms stand in
+    // for booking, and nobody orders a plumber online
+    let mut trades = default.clone();
+    trades.fit.phone = 20.0;
+    trades.fit.maps_presence = 20.0;
+    trades.opportunity.no_booking = 5;
+    trades.opportunity.no_online_ordering = 0;
+    trades.opportunity.poor_maps = 15;
+
+    // Restaurants and cafes live on reviews, reservations and ordering, and
+    // collect reviews far faster than other businesses
+    let mut hospitality = default.clone();
+    hospitality.fit.good_rating = 15.0;
+    hospitality.fit.review_count = 15.0;
+    hospitality.fit.email = 5.0;
+    hospitality.opportunity.no_online_ordering = 20;
+    hospitality.thresholds.min_reviews = 50.0;
+
+    // Practices all have booking systems, so that signal is noise; ad
+    // platforms restrict health targeting, which blunts the pixel pitch
+    let mut medical = default.clone();
+    medical.fit.phone = 20.0;
+    medical.fit.good_rating = 15.0;
+    medical.opportunity.no_booking = 0;
+    medical.opportunity.no_pixel = 5;
+    medical.opportunity.no_online_ordering = 0;
+
+    // Shops sell online and retarget: ordering and the pixel matter most
+    let mut retail = default.clone();
+    retail.fit.ads_presence = 15.0;
+    retail.opportunity.no_online_ordering = 20;
+    retail.opportunity.no_pixel = 15;
+    retail.opportunity.no_booking = 0;
+
+    vec![
+        (DEFAULT_PROFILE, default),
+        ("trades", trades),
+        ("hospitality", hospitality),
+        ("medical", medical),
+        ("retail", retail),
+    ]
+}
+
+// Built-in presets plus any added via register_scoring_profile
+static SCORING_PROFILES: LazyLock<RwLock<HashMap<String, ScoringProfile>>> = LazyLock::new(|| {
+    let profiles = builtin_profiles().into_iter().map(|(name, p)| (name.to_string(), p));
+    RwLock::new(profiles.collect())
+});
+
+fn registered_profile_names(profiles: &HashMap<String, ScoringProfile>) -> String {
+    let mut names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    names.join(", ")
+}
+
+fn lookup_profile(name: &str) -> PyResult<ScoringProfile> {
+    let profiles = SCORING_PROFILES.read().unwrap_or_else(|e| e.into_inner());
+    profiles.get(name).cloned().ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "unknown scoring profile {name:?}; registered profiles: {}",
+            registered_profile_names(&profiles)
+        ))
+    })
+}
+
+/// The named profile ("default" when None) with `weights` applied to its fit
+/// weights.
+fn resolve_profile(
+    name: Option<&str>,
+    weights: Option<&HashMap<String, f64>>,
+) -> PyResult<ScoringProfile> {
+    let mut profile = lookup_profile(name.unwrap_or(DEFAULT_PROFILE))?;
+    profile.fit = profile.fit.with_overrides(weights)?;
+    Ok(profile)
+}
+
+/// The `{name: number}` object under `key` in a profile definition.
+fn json_number_map(
+    definition: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> PyResult<HashMap<String, f64>> {
+    let Some(value) = definition.get(key) else { return Ok(HashMap::new()) };
+    let object = value.as_object().ok_or_else(|| {
+        PyValueError::new_err(format!("profile {key:?} must be an object of numbers"))
+    })?;
+    object
+        .iter()
+        .map(|(name, value)| {
+            let number = value.as_f64().ok_or_else(|| {
+                PyValueError::new_err(format!("profile {key}.{name} must be a number, got {value}"))
+            })?;
+            Ok((name.clone(), number))
+        })
+        .collect()
+}
+
+fn parse_profile(weights_json: &str) -> PyResult<ScoringProfile> {
+    let value: serde_json::Value = serde_json::from_str(weights_json)
+        .map_err(|e| PyValueError::new_err(format!("invalid profile JSON: {e}")))?;
+    let definition = value
+        .as_object()
+        .ok_or_else(|| PyValueError::new_err("profile JSON must be an object"))?;
+    const KEYS: &[&str] =
+        &["extends", "fit", "opportunity", "thresholds", "caps", "clamp", "curves"];
+    for key in definition.keys() {
+        if !KEYS.contains(&key.as_str()) {
+            return Err(PyValueError::new_err(format!(
+                "unknown profile key {key:?}; expected {}",
+                KEYS.join(", ")
+            )));
+        }
+    }
+
+    let base = match definition.get("extends") {
+        None => DEFAULT_PROFILE,
+        Some(serde_json::Value::String(base)) => base.as_str(),
+        Some(other) => {
+            return Err(PyValueError::new_err(format!(
+                "profile \"extends\" must be a profile name, got {other}"
+            )))
+        }
+    };
+    build_profile(base, definition)
+}
+
+/// `base` with a definition's `"fit"`, `"opportunity"`, `"thresholds"`,
+/// `"caps"`, `"clamp"` and `"curves"` applied.
+fn build_profile(
+    base: &str,
+    definition: &serde_json::Map<String, serde_json::Value>,
+) -> PyResult<ScoringProfile> {
+    let mut profile = lookup_profile(base)?;
+
+    profile.fit = profile.fit.with_overrides(Some(&json_number_map(definition, "fit")?))?;
+
+    for (name, value) in json_number_map(definition, "opportunity")? {
+        let Some(slot) = profile.opportunity.get_mut(&name) else {
+            return Err(PyValueError::new_err(format!(
+                "unknown opportunity weight {name:?}; valid components: {}",
+                OpportunityWeights::NAMES.join(", ")
+            )));
+        };
+        if value.fract() != 0.0 || value.abs() > 100.0 {
+            return Err(PyValueError::new_err(format!(
+                "opportunity weight {name:?} must be a whole number from -100 to 100, got {value}"
+            )));
+        }
+        *slot = value as i32;
+    }
+
+    for (name, value) in json_number_map(definition, "thresholds")? {
+        let Some(slot) = profile.thresholds.get_mut(&name) else {
+            return Err(PyValueError::new_err(format!(
+                "unknown threshold {name:?}; valid thresholds: {}",
+                ScoringThresholds::NAMES.join(", ")
+            )));
+        };
+        if !value.is_finite() || value < 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "threshold {name:?} must be a non-negative number, got {value}"
+            )));
+        }
+        *slot = value;
+    }
+
+    parse_shape(definition, &mut profile.shape)?;
+    Ok(profile)
+}
+
+/// Apply a profile definition's `"caps"`, `"clamp"` and `"curves"` on top of
+/// the base profile's.
+fn parse_shape(
+    definition: &serde_json::Map<String, serde_json::Value>,
+    shape: &mut ScoringShape,
+) -> PyResult<()> {
+    for (name, cap) in json_number_map(definition, "caps")? {
+        let components = FitWeights::NAMES.iter().chain(OpportunityWeights::NAMES);
+        let Some(component) = components.copied().find(|c| *c == name) else {
+            return Err(PyValueError::new_err(format!(
+                "unknown component {name:?} in caps; valid components: {}, {}",
+                FitWeights::NAMES.join(", "),
+                OpportunityWeights::NAMES.join(", ")
+            )));
+        };
+        if !cap.is_finite() || cap < 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "cap for {name:?} must be a non-negative number, got {cap}"
+            )));
+        }
+        shape.caps.retain(|(capped, _)| *capped != component);
+        shape.caps.push((component, cap));
+    }
+
+    if let Some(clamp) = definition.get("clamp") {
+        let range: Option<Vec<f64>> = clamp
+            .as_array()
+            .map(|bounds| bounds.iter().map(serde_json::Value::as_f64).collect())
+            .unwrap_or(None);
+        match range.as_deref() {
+            Some(&[low, high]) if 0.0 <= low && low < high && high <= 100.0 => {
+                shape.clamp = (low, high)
+            }
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "profile \"clamp\" must be [low, high] with 0 <= low < high <= 100, got {clamp}"
+                )))
+            }
+        }
+    }
+
+    if let Some(curves) = definition.get("curves") {
+        let curves = curves.as_object().ok_or_else(|| {
+            PyValueError::new_err("profile \"curves\" must be an object of curves")
+        })?;
+        for (name, spec) in curves {
+            let Some(component) = CURVED_COMPONENTS.iter().copied().find(|c| *c == name) else {
+                return Err(PyValueError::new_err(format!(
+                    "component {name:?} can't take a curve; curved components: {}",
+                    CURVED_COMPONENTS.join(", ")
+                )));
+            };
+            shape.curves.retain(|(curved, _)| *curved != component);
+            // null goes back to the threshold
+            if !spec.is_null() {
+                shape.curves.push((component, Curve::parse(name, spec)?));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Define (or redefine) a scoring profile usable as `profile=name`.
+///
+/// `weights_json` is an object with optional keys: `"extends"` (the profile
+/// to start from, "default" if absent), `"fit"` (fit weights as for
+/// `weights`), `"opportunity"` (opportunity points by component, penalties
+/// negative; see `get_default_opportunity_weights`) and `"thresholds"`
+/// (`good_rating`, `min_reviews`, `slow_site_ms`, `stale_copyright_years`).
+///
+/// Three more keys shape the points rather than weigh them:
+///   - `"caps"`: most points any component (fit or opportunity, by name)
+///     may add or remove, e.g. `{"ab_testing": 15}`; built-in caps are
+///     `ab_testing` 10 and `digital_maturity` its weight
+///   - `"clamp"`: `[low, high]` range for both scores, `[0, 100]` by default
+///   - `"curves"`: per numeric component (`review_count`, `good_rating`,
+///     `slow_site`, `stale_copyright`), a curve earning a share of the
+///     weight by value in place of its threshold:
+///     `{"curve": "linear_capped", "cap": 200}` (full weight from 200),
+///     `{"curve": "log", "base": 101}` (`log_base(1 + value)`, full from
+///     100), or `{"curve": "step", "breakpoints": [[10, 0.5], [100, 1]]}`
+///     (the share of the highest breakpoint reached); null restores the
+///     threshold
+///
+/// Keys missing from the definition keep the `"extends"` profile's values.
+/// Built-in profiles can't be replaced.
+#[pyfunction]
+pub fn register_scoring_profile(name: &str, weights_json: &str) -> PyResult<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(PyValueError::new_err("profile name must not be empty"));
+    }
+    if builtin_profiles().iter().any(|(builtin, _)| *builtin == name) {
+        return Err(PyValueError::new_err(format!(
+            "{name:?} is a built-in profile; register it under another name"
+        )));
+    }
+    let profile = parse_profile(weights_json)?;
+    let mut profiles = SCORING_PROFILES.write().unwrap_or_else(|e| e.into_inner());
+    profiles.insert(name.to_string(), profile);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Priority and batch normalization
+// ---------------------------------------------------------------------------
+
+// Settings.fit_weight / opportunity_weight in prospect/config.py
+const PRIORITY_FIT_WEIGHT: f64 = 0.4;
+const PRIORITY_OPPORTUNITY_WEIGHT: f64 = 0.6;
+
+/// How fit and opportunity combine into a priority score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PriorityFormula {
+    /// `fit * fit_weight + opportunity * opportunity_weight`
+    WeightedSum { fit: f64, opportunity: f64 },
+    /// `sqrt(fit * opportunity)`: a zero on either side zeroes priority
+    GeometricMean,
+    /// `opportunity` when `fit >= threshold`, else 0
+    MinGated { threshold: f64 },
+}
+
+impl Default for PriorityFormula {
+    fn default() -> Self {
+        PriorityFormula::WeightedSum {
+            fit: PRIORITY_FIT_WEIGHT,
+            opportunity: PRIORITY_OPPORTUNITY_WEIGHT,
+        }
+    }
+}
+
+impl PriorityFormula {
+    /// The parameters formula `kind` takes, or None for an unknown kind.
+    fn param_names(kind: &str) -> Option<&'static [&'static str]> {
+        match kind {
+            "weighted_sum" => Some(&["fit", "opportunity"]),
+            "geometric_mean" => Some(&[]),
+            "min_gated" => Some(&["threshold"]),
+            _ => None,
+        }
+    }
+
+    fn parse(kind: &str, params: Option<&HashMap<String, f64>>) -> PyResult<Self> {
+        let empty = HashMap::new();
+        let params = params.unwrap_or(&empty);
+        let Some(allowed) = PriorityFormula::param_names(kind) else {
+            return Err(PyValueError::new_err(format!(
+                "unknown priority formula {kind:?}; expected weighted_sum, geometric_mean or \
+                 min_gated"
+            )));
+        };
+        for (name, value) in params {
+            if !allowed.contains(&name.as_str()) {
+                return Err(PyValueError::new_err(format!(
+                    "{kind} takes no parameter {name:?}; valid parameters: {}",
+                    if allowed.is_empty() { "none".to_string() } else { allowed.join(", ") }
+                )));
+            }
+            if !value.is_finite() || *value < 0.0 {
+                return Err(PyValueError::new_err(format!(
+                    "priority parameter {name:?} must be a non-negative number, got {value}"
+                )));
+            }
+        }
+        Ok(match kind {
+            "weighted_sum" => {
+                let fit = params.get("fit").copied().unwrap_or(PRIORITY_FIT_WEIGHT);
+                let opportunity =
+                    params.get("opportunity").copied().unwrap_or(PRIORITY_OPPORTUNITY_WEIGHT);
+                if fit == 0.0 && opportunity == 0.0 {
+                    return Err(PyValueError::new_err("weighted_sum weights can't both be zero"));
+                }
+                PriorityFormula::WeightedSum { fit, opportunity }
+            }
+            "geometric_mean" => PriorityFormula::GeometricMean,
+            _ => {
+                let threshold = params.get("threshold").copied().ok_or_else(|| {
+                    PyValueError::new_err("min_gated needs a \"threshold\" parameter")
+                })?;
+                PriorityFormula::MinGated { threshold }
+            }
+        })
+    }
+
+    fn apply(self, fit: u32, opportunity: u32) -> f64 {
+        let (fit, opportunity) = (fit as f64, opportunity as f64);
+        match self {
+            PriorityFormula::WeightedSum { fit: fit_weight, opportunity: opportunity_weight } => {
+                fit * fit_weight + opportunity * opportunity_weight
+            }
+            PriorityFormula::GeometricMean => (fit * opportunity).sqrt(),
+            PriorityFormula::MinGated { threshold } => {
+                if fit >= threshold { opportunity } else { 0.0 }
+            }
+        }
+    }
+
+    fn kind(self) -> &'static str {
+        match self {
+            PriorityFormula::WeightedSum { .. } => "weighted_sum",
+            PriorityFormula::GeometricMean => "geometric_mean",
+            PriorityFormula::MinGated { .. } => "min_gated",
+        }
+    }
+
+    fn params(self) -> Vec<(&'static str, f64)> {
+        match self {
+            PriorityFormula::WeightedSum { fit, opportunity } => {
+                vec![("fit", fit), ("opportunity", opportunity)]
+            }
+            PriorityFormula::GeometricMean => Vec::new(),
+            PriorityFormula::MinGated { threshold } => vec![("threshold", threshold)],
+        }
+    }
+
+    /// `{"kind": ..., "params": {...}}`, as reported with batch results.
+    fn to_dict(self, py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        let params = PyDict::new(py);
+        for (name, value) in self.params() {
+            params.set_item(name, value)?;
+        }
+        let dict = PyDict::new(py);
+        dict.set_item("kind", self.kind())?;
+        dict.set_item("params", params)?;
+        Ok(dict)
+    }
+}
+
+// The formula score_prospects_batch applies; set via set_priority_formula
+static PRIORITY_FORMULA: LazyLock<RwLock<PriorityFormula>> =
+    LazyLock::new(|| RwLock::new(PriorityFormula::default()));
+
+fn current_priority_formula() -> PriorityFormula {
+    *PRIORITY_FORMULA.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Change how `score_prospects_batch` computes priority:
+///   - "weighted_sum": `fit * params["fit"] + opportunity * params["opportunity"]`
+///     (missing weights default to 0.4 and 0.6, the built-in formula)
+///   - "geometric_mean": `sqrt(fit * opportunity)`, no params
+///   - "min_gated": opportunity when `fit >= params["threshold"]`, else 0
+#[pyfunction]
+#[pyo3(signature = (kind, params=None))]
+pub fn set_priority_formula(kind: &str, params: Option<HashMap<String, f64>>) -> PyResult<()> {
+    let formula = PriorityFormula::parse(kind, params.as_ref())?;
+    *PRIORITY_FORMULA.write().unwrap_or_else(|e| e.into_inner()) = formula;
+    Ok(())
+}
+
+/// The priority formula in effect, as `{"kind": str, "params": {name: float}}`.
+#[pyfunction]
+pub fn get_priority_formula(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    current_priority_formula().to_dict(py)
+}
+
+/// Priority for one fit/opportunity pair under `formula` and `params` (as for
+/// `set_priority_formula`), or the formula in effect when `formula` is None.
+#[pyfunction]
+#[pyo3(signature = (fit, opportunity, formula=None, params=None))]
+pub fn calculate_priority(
+    fit: u32,
+    opportunity: u32,
+    formula: Option<&str>,
+    params: Option<HashMap<String, f64>>,
+) -> PyResult<f64> {
+    let formula = match formula {
+        Some(kind) => PriorityFormula::parse(kind, params.as_ref())?,
+        None if params.is_some() => {
+            return Err(PyValueError::new_err("params given without a formula"));
+        }
+        None => current_priority_formula(),
+    };
+    Ok(formula.apply(fit, opportunity))
+}
+
+/// How `rank_prospects` places a score within its batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RankMethod {
+    /// 0–100 by average rank, so tied scores share a percentile.
+    Percentile,
+    /// Standard deviations from the batch mean.
+    ZScore,
+}
+
+impl RankMethod {
+    fn parse(method: &str) -> PyResult<Self> {
+        match method {
+            "percentile" => Ok(RankMethod::Percentile),
+            "zscore" => Ok(RankMethod::ZScore),
+            other => Err(PyValueError::new_err(format!(
+                "unknown rank method {other:?}; expected \"percentile\" or \"zscore\""
+            ))),
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            RankMethod::Percentile => "percentile",
+            RankMethod::ZScore => "zscore",
+        }
+    }
+}
+
+fn rank_scores(scores: &[f64], method: RankMethod) -> Vec<f64> {
+    let n = scores.len();
+    match method {
+        RankMethod::Percentile => {
+            if n == 1 {
+                return vec![50.0];
+            }
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+            let mut ranks = vec![0.0; n];
+            let mut start = 0;
+            while start < n {
+                let mut end = start;
+                while end + 1 < n && scores[order[end + 1]] == scores[order[start]] {
+                    end += 1;
+                }
+                // Ties share the average of the 0-based ranks they span
+                let average = (start + end) as f64 / 2.0;
+                for &i in &order[start..=end] {
+                    ranks[i] = average / (n - 1) as f64 * 100.0;
+                }
+                start = end + 1;
+            }
+            ranks
+        }
+        RankMethod::ZScore => {
+            let (mean, std) = mean_std(scores);
+            scores.iter().map(|s| if std > 0.0 { (s - mean) / std } else { 0.0 }).collect()
+        }
+    }
+}
+
+/// Place each score within the batch: `method="percentile"` gives 0–100 with
+/// tied scores sharing their average rank (a batch of one is 50.0);
+/// `"zscore"` gives standard deviations from the mean (0.0 when all equal).
+#[pyfunction]
+#[pyo3(signature = (scores, method="percentile"))]
+pub fn rank_prospects(scores: Vec<f64>, method: &str) -> PyResult<Vec<f64>> {
+    Ok(rank_scores(&scores, RankMethod::parse(method)?))
+}
+
+// Letters handed out by `grade_prospects`, best first
+const GRADE_LETTERS: &[&str] = &["A", "B", "C", "D", "E", "F"];
+
+/// Cut points for grading: `thresholds` sorted best first, or the batch's
+/// upper, middle and lower quartiles when not given.
+fn grade_cut_points(priorities: &[f64], thresholds: Option<Vec<f64>>) -> PyResult<Vec<f64>> {
+    let mut cuts = match thresholds {
+        Some(cuts) => {
+            if cuts.is_empty() || cuts.len() >= GRADE_LETTERS.len() {
+                return Err(PyValueError::new_err(format!(
+                    "expected 1 to {} grade thresholds, got {}",
+                    GRADE_LETTERS.len() - 1,
+                    cuts.len()
+                )));
+            }
+            if let Some(bad) = cuts.iter().find(|c| !c.is_finite()) {
+                return Err(PyValueError::new_err(format!("grade threshold {bad} is not finite")));
+            }
+            cuts
+        }
+        None => {
+            let mut sorted = priorities.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            [0.75, 0.5, 0.25].iter().map(|&q| quantile(&sorted, q)).collect()
+        }
+    };
+    cuts.sort_by(|a, b| b.total_cmp(a));
+    Ok(cuts)
+}
+
+/// Linearly interpolated quantile of an ascending, non-empty slice.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = (sorted.len() - 1) as f64 * q;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+fn grade_scores(priorities: &[f64], thresholds: Option<Vec<f64>>) -> PyResult<Vec<String>> {
+    if priorities.is_empty() {
+        return Ok(Vec::new());
+    }
+    let cuts = grade_cut_points(priorities, thresholds)?;
+    Ok(priorities
+        .iter()
+        .map(|&p| {
+            // A score sitting exactly on a cut point takes the higher grade
+            let tier = cuts.iter().position(|&cut| p >= cut).unwrap_or(cuts.len());
+            GRADE_LETTERS[tier].to_string()
+        })
+        .collect())
+}
+
+/// Letter grade per priority score. Without `thresholds` the batch is split
+/// at its quartiles into A/B/C/D; with them, `[80, 60, 40]` means A at 80 and
+/// above, B from 60, C from 40, D below (one more grade than cut points, in
+/// any order). A score equal to a cut point gets the higher grade.
+#[pyfunction]
+#[pyo3(signature = (priorities, thresholds=None))]
+pub fn grade_prospects(
+    priorities: Vec<f64>,
+    thresholds: Option<Vec<f64>>,
+) -> PyResult<Vec<String>> {
+    grade_scores(&priorities, thresholds)
+}
+
+/// The ranking key `top_prospects` reads from one prospect.
+struct RankKey {
+    value: f64,
+    fit: f64,
+    review_count: i64,
+    name: Option<String>,
+}
+
+impl RankKey {
+    fn read(py: Python<'_>, fields: &Fields, by: &str) -> Self {
+        let number = |key: &str| {
+            fields
+                .get(key)
+                .and_then(|obj| obj.extract::<f64>(py).ok())
+                .filter(|v| !v.is_nan())
+                // -0.0 and 0.0 must tie
+                .map_or(f64::NEG_INFINITY, |v| v + 0.0)
+        };
+        RankKey {
+            value: number(by),
+            fit: number("fit_score"),
+            review_count: fields
+                .get("review_count")
+                .and_then(|obj| obj.extract::<i64>(py).ok())
+                .unwrap_or(-1),
+            name: extract_opt_string(py, fields, "name"),
+        }
+    }
+
+    /// Best first: higher value, higher fit, more reviews, then name in
+    /// byte order (unnamed last).
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .value
+            .total_cmp(&self.value)
+            .then(other.fit.total_cmp(&self.fit))
+            .then(other.review_count.cmp(&self.review_count))
+            .then_with(|| match (&self.name, &other.name) {
+                (Some(a), Some(b)) => a.as_bytes().cmp(b.as_bytes()),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            })
+    }
+}
+
+/// Indices of the `n` best prospects (dicts or objects), best first, by the
+/// numeric field `by`. Selection is partial, so cost grows with `n` rather
+/// than a full sort.
+///
+/// Ties break on higher `fit_score`, then higher `review_count`, then `name`
+/// ascending (compared byte-wise, so independent of locale), then original
+/// index, making the order the same on every run and platform. Missing or
+/// non-numeric values rank below any number; `n` at or beyond the length
+/// returns every index, ranked.
+#[pyfunction]
+#[pyo3(signature = (prospects, n, by="priority_score"))]
+pub fn top_prospects(
+    py: Python<'_>,
+    prospects: Vec<Bound<'_, PyAny>>,
+    n: usize,
+    by: &str,
+) -> PyResult<Vec<usize>> {
+    let keys = [by, "fit_score", "review_count", "name"];
+    let mut ranked = Vec::with_capacity(prospects.len());
+    for (i, prospect) in prospects.iter().enumerate() {
+        ranked.push((RankKey::read(py, &prospect_fields(prospect, &keys, &[])?, by), i));
+    }
+    let compare =
+        |a: &(RankKey, usize), b: &(RankKey, usize)| a.0.compare(&b.0).then(a.1.cmp(&b.1));
+    let n = n.min(ranked.len());
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if n < ranked.len() {
+        ranked.select_nth_unstable_by(n - 1, compare);
+        ranked.truncate(n);
+    }
+    ranked.sort_unstable_by(compare);
+    Ok(ranked.into_iter().map(|(_, i)| i).collect())
+}
+
+// ---------------------------------------------------------------------------
+// Freshness decay
+// ---------------------------------------------------------------------------
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Unix seconds for an ISO 8601 timestamp as `datetime.isoformat()` writes
+/// it: `YYYY-MM-DD`, optionally followed by `T` (or a space) and
+/// `HH:MM[:SS[.ffffff]]`, then `Z` or a `±HH:MM` offset. Naive times are
+/// taken as UTC.
+fn parse_iso_timestamp(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let number = |s: &str| s.bytes().all(|b| b.is_ascii_digit()).then(|| s.parse::<i64>().ok())?;
+
+    let (date, rest) = value.split_at_checked(10)?;
+    let mut parts = date.split('-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day) = (number(year)?, number(month)?, number(day)?);
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut seconds = days_from_civil(year, month, day) as f64 * SECONDS_PER_DAY;
+    if rest.is_empty() {
+        return Some(seconds);
+    }
+
+    let rest = rest.strip_prefix(['T', 't', ' '])?;
+    let (time, offset) = match rest.find(['Z', 'z', '+', '-']) {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    let (clock, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut fields = clock.split(':');
+    let hour = number(fields.next()?)?;
+    let minute = number(fields.next()?)?;
+    let second = fields.next().map_or(Some(0), number)?;
+    if fields.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    seconds += (hour * 3600 + minute * 60 + second) as f64;
+    if !fraction.is_empty() {
+        number(fraction)?;
+        seconds += format!("0.{fraction}").parse::<f64>().ok()?;
+    }
+
+    match offset {
+        "" | "Z" | "z" => {}
+        _ => {
+            let (sign, hhmm) = offset.split_at(1);
+            let (hours, minutes) = hhmm.split_once(':').unwrap_or(hhmm.split_at_checked(2)?);
+            let (hours, minutes) = (number(hours)?, number(minutes)?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let shift = (hours * 3600 + minutes * 60) as f64;
+            // 10:00+10:00 is 00:00 UTC
+            seconds += if sign == "+" { -shift } else { shift };
+        }
+    }
+    Some(seconds)
+}
+
+/// Share of a score left after `age_days`, halving every `half_life_days`.
+/// Timestamps in the future don't inflate the score.
+fn freshness_multiplier(age_days: f64, half_life_days: f64) -> f64 {
+    0.5_f64.powf(age_days.max(0.0) / half_life_days)
+}
+
+/// Decay settings for `score_prospects_batch`.
+#[derive(Debug, Clone, Copy)]
+struct FreshnessDecay {
+    half_life_days: f64,
+    /// Unix seconds ages are measured from
+    now: f64,
+}
+
+impl FreshnessDecay {
+    fn new(half_life_days: f64, now: Option<f64>) -> PyResult<Self> {
+        if !half_life_days.is_finite() || half_life_days <= 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "freshness half-life must be a positive number of days, got {half_life_days}"
+            )));
+        }
+        let now = match now {
+            Some(now) if now.is_finite() => now,
+            Some(now) => {
+                let message = format!("freshness_now must be finite, got {now}");
+                return Err(PyValueError::new_err(message));
+            }
+            None => SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |d| d.as_secs_f64()),
+        };
+        Ok(FreshnessDecay { half_life_days, now })
+    }
+
+    /// The multiplier for a prospect scraped at `scraped_at`, or None when it
+    /// is missing or unparseable.
+    fn multiplier(&self, scraped_at: Option<&str>) -> Option<f64> {
+        let scraped = parse_iso_timestamp(scraped_at?)?;
+        Some(freshness_multiplier((self.now - scraped) / SECONDS_PER_DAY, self.half_life_days))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scoring config documents
+// ---------------------------------------------------------------------------
+
+// Top-level keys of a document read by `load_scoring_config`
+const CONFIG_KEYS: &[&str] =
+    &["profile", "fit", "opportunity", "thresholds", "caps", "clamp", "curves", "priority"];
+
+/// A scoring config parsed once by `load_scoring_config`, for
+/// `score_prospects_batch(config=...)` and `dump_scoring_config`.
+#[pyclass(frozen, module = "_leadswarm_native")]
+pub struct ScoringConfig {
+    /// The profile the document starts from
+    base: String,
+    profile: ScoringProfile,
+    /// None leaves priority to `set_priority_formula`
+    formula: Option<PriorityFormula>,
+    /// Unknown keys skipped under `allow_unknown`, as dotted paths
+    ignored: Vec<String>,
+}
+
+#[pymethods]
+impl ScoringConfig {
+    /// The profile the config starts from.
+    #[getter]
+    fn profile(&self) -> &str {
+        &self.base
+    }
+
+    /// Unknown keys skipped because of `allow_unknown`, as dotted paths.
+    #[getter]
+    fn ignored_keys(&self) -> Vec<String> {
+        self.ignored.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        let priority = self.formula.map_or("global", PriorityFormula::kind);
+        format!("ScoringConfig(profile={:?}, priority={priority})", self.base)
+    }
+}
+
+impl ScoringConfig {
+    /// The config with every value spelled out, which loads back to the same
+    /// config.
+    fn to_document(&self) -> serde_json::Value {
+        let profile = &self.profile;
+        let mut fit = serde_json::Map::new();
+        for name in FitWeights::NAMES {
+            fit.insert(name.to_string(), profile.fit.get(name).into());
+        }
+        let mut opportunity = serde_json::Map::new();
+        for name in OpportunityWeights::NAMES {
+            opportunity.insert(name.to_string(), profile.opportunity.get(name).into());
+        }
+        let mut thresholds = serde_json::Map::new();
+        for name in ScoringThresholds::NAMES {
+            thresholds.insert(name.to_string(), profile.thresholds.get(name).into());
+        }
+        let shape = &profile.shape;
+        let caps: serde_json::Map<String, serde_json::Value> =
+            shape.caps.iter().map(|&(name, cap)| (name.to_string(), cap.into())).collect();
+        let curves: serde_json::Map<String, serde_json::Value> =
+            shape.curves.iter().map(|(name, curve)| (name.to_string(), curve.to_json())).collect();
+        let mut document = serde_json::json!({
+            "profile": self.base,
+            "fit": fit,
+            "opportunity": opportunity,
+            "thresholds": thresholds,
+            "caps": caps,
+            "clamp": [shape.clamp.0, shape.clamp.1],
+            "curves": curves,
+        });
+        if let Some(formula) = self.formula {
+            let params: serde_json::Map<String, serde_json::Value> =
+                formula.params().iter().map(|&(name, v)| (name.to_string(), v.into())).collect();
+            document["priority"] = serde_json::json!({"kind": formula.kind(), "params": params});
+        }
+        document
+    }
+}
+
+fn config_error(message: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(format!("scoring config: {message}"))
+}
+
+/// Reject keys of `object` outside `known` or, with `allow_unknown`, drop
+/// them into `ignored`. `path` is the object's dotted path in the document.
+fn check_config_keys(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    path: &str,
+    known: &[&str],
+    allow_unknown: bool,
+    ignored: &mut Vec<String>,
+) -> PyResult<()> {
+    let unknown: Vec<String> =
+        object.keys().filter(|key| !known.contains(&key.as_str())).cloned().collect();
+    for key in unknown {
+        let field = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+        if !allow_unknown {
+            return Err(config_error(format!(
+                "unknown key {field:?}; expected {}",
+                known.join(", ")
+            )));
+        }
+        object.remove(&key);
+        ignored.push(field);
+    }
+    Ok(())
+}
+
+/// Check every object in a config document for unknown keys.
+fn check_config_document(
+    document: &mut serde_json::Map<String, serde_json::Value>,
+    allow_unknown: bool,
+    ignored: &mut Vec<String>,
+) -> PyResult<()> {
+    check_config_keys(document, "", CONFIG_KEYS, allow_unknown, ignored)?;
+    let components: Vec<&str> =
+        FitWeights::NAMES.iter().chain(OpportunityWeights::NAMES).copied().collect();
+    let sections: [(&str, &[&str]); 5] = [
+        ("fit", FitWeights::NAMES),
+        ("opportunity", OpportunityWeights::NAMES),
+        ("thresholds", ScoringThresholds::NAMES),
+        ("caps", &components),
+        ("curves", CURVED_COMPONENTS),
+    ];
+    for (section, known) in sections {
+        if let Some(serde_json::Value::Object(object)) = document.get_mut(section) {
+            check_config_keys(object, section, known, allow_unknown, ignored)?;
+        }
+    }
+    if let Some(serde_json::Value::Object(curves)) = document.get_mut("curves") {
+        for (name, spec) in curves.iter_mut() {
+            let Some(spec) = spec.as_object_mut() else { continue };
+            let kind = spec.get("curve").and_then(serde_json::Value::as_str);
+            if let Some(param) = kind.and_then(Curve::param) {
+                let path = format!("curves.{name}");
+                check_config_keys(spec, &path, &["curve", param], allow_unknown, ignored)?;
+            }
+        }
+    }
+    if let Some(serde_json::Value::Object(priority)) = document.get_mut("priority") {
+        check_config_keys(priority, "priority", &["kind", "params"], allow_unknown, ignored)?;
+        let kind = priority.get("kind").and_then(serde_json::Value::as_str);
+        let names = kind.and_then(PriorityFormula::param_names);
+        if let (Some(names), Some(serde_json::Value::Object(params))) =
+            (names, priority.get_mut("params"))
+        {
+            check_config_keys(params, "priority.params", names, allow_unknown, ignored)?;
+        }
+    }
+    Ok(())
+}
+
+/// The formula a document's `"priority"` object describes.
+fn config_formula(priority: &serde_json::Value) -> PyResult<PriorityFormula> {
+    let priority = priority.as_object().ok_or_else(|| {
+        config_error("\"priority\" must be an object with \"kind\" and \"params\"")
+    })?;
+    let kind = match priority.get("kind") {
+        Some(serde_json::Value::String(kind)) => kind.as_str(),
+        other => {
+            let found = other.map_or("nothing".to_string(), ToString::to_string);
+            return Err(config_error(format!("priority.kind must be a formula name, got {found}")));
+        }
+    };
+    let mut params = HashMap::new();
+    match priority.get("params") {
+        None => {}
+        Some(serde_json::Value::Object(object)) => {
+            for (name, value) in object {
+                let number = value.as_f64().ok_or_else(|| {
+                    config_error(format!("priority.params.{name} must be a number, got {value}"))
+                })?;
+                params.insert(name.clone(), number);
+            }
+        }
+        Some(other) => {
+            return Err(config_error(format!(
+                "priority.params must be an object of numbers, got {other}"
+            )))
+        }
+    }
+    PriorityFormula::parse(kind, Some(&params))
+}
+
+/// Parse a scoring config document once, for reuse across batches.
+///
+/// `format` is "json" or "toml". The document takes the keys of a
+/// `register_scoring_profile` definition, with `"profile"` (the profile to
+/// start from, "default" if absent) in place of `"extends"`, plus
+/// `"priority"`: `{"kind": ..., "params": {...}}` as for
+/// `set_priority_formula`. Without `"priority"`, batches scored with the
+/// config use the formula in effect.
+///
+/// Syntax errors give the line and column, other errors the offending key
+/// (e.g. `fit.phone`). Unknown keys raise ValueError unless `allow_unknown`,
+/// which skips them and lists them in the config's `ignored_keys`.
+#[pyfunction]
+#[pyo3(signature = (text, format, allow_unknown=false))]
+pub fn load_scoring_config(
+    py: Python<'_>,
+    text: &str,
+    format: &str,
+    allow_unknown: bool,
+) -> PyResult<ScoringConfig> {
+    let document: serde_json::Value = match format {
+        "json" => serde_json::from_str(text)
+            .map_err(|e| config_error(format!("invalid JSON: {e}")))?,
+        "toml" => toml::from_str(text).map_err(|e| config_error(format!("invalid TOML: {e}")))?,
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "unknown config format {other:?}; expected json or toml"
+            )))
+        }
+    };
+    let serde_json::Value::Object(mut document) = document else {
+        return Err(config_error("the document must be an object"));
+    };
+    let mut ignored = Vec::new();
+    check_config_document(&mut document, allow_unknown, &mut ignored)?;
+
+    let base = match document.get("profile") {
+        None => DEFAULT_PROFILE,
+        Some(serde_json::Value::String(base)) => base.as_str(),
+        Some(other) => {
+            return Err(config_error(format!("\"profile\" must be a profile name, got {other}")))
+        }
+    };
+    let profile = build_profile(base, &document).map_err(|e| config_error(e.value(py)))?;
+    let formula = document.get("priority").map(config_formula).transpose()?;
+    Ok(ScoringConfig { base: base.to_string(), profile, formula, ignored })
+}
+
+/// `config` as a document in `format` ("json" or "toml") with every weight,
+/// threshold, cap, curve and the clamp written out, so loading it gives the
+/// same config back.
+#[pyfunction]
+#[pyo3(signature = (config, format="json"))]
+pub fn dump_scoring_config(config: PyRef<'_, ScoringConfig>, format: &str) -> PyResult<String> {
+    let document = config.to_document();
+    match format {
+        "json" => serde_json::to_string_pretty(&document)
+            .map_err(|e| PyValueError::new_err(format!("can't write scoring config: {e}"))),
+        "toml" => toml::to_string_pretty(&document)
+            .map_err(|e| PyValueError::new_err(format!("can't write scoring config: {e}"))),
+        other => Err(PyValueError::new_err(format!(
+            "unknown config format {other:?}; expected json or toml"
+        ))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rule expressions
+// ---------------------------------------------------------------------------
+
+// Largest adjustment a rule may make to either score
+const MAX_RULE_POINTS: f64 = 100.0;
+
+/// A field a rule reads: a prospect field falling back to the signal of the
+/// same name, or only the signal when written `signals.<name>`.
+#[derive(Debug, Clone, PartialEq)]
+struct RuleField {
+    name: String,
+    signal_only: bool,
+}
+
+/// A field's value as rules see it.
+#[derive(Debug, Clone, PartialEq)]
+enum RuleValue {
+    /// Absent, None, or an empty string or list
+    Missing,
+    Bool(bool),
+    Number(f64),
+    /// Lowercased, as comparisons ignore case
+    Text(String),
+    List(Vec<String>),
+}
+
+impl RuleValue {
+    fn read(obj: &Bound<'_, PyAny>) -> Self {
+        if obj.is_none() {
+            RuleValue::Missing
+        } else if let Ok(flag) = obj.downcast::<PyBool>() {
+            RuleValue::Bool(flag.is_true())
+        } else if let Ok(text) = obj.extract::<String>() {
+            match text.trim() {
+                "" => RuleValue::Missing,
+                text => RuleValue::Text(text.to_lowercase()),
+            }
+        } else if let Ok(number) = obj.extract::<f64>() {
+            RuleValue::Number(number)
+        } else if let Ok(items) = obj.extract::<Vec<Bound<'_, PyAny>>>() {
+            let items: Vec<String> =
+                items.iter().filter_map(|item| item.str().ok()).map(|s| s.to_string()).collect();
+            if items.is_empty() {
+                RuleValue::Missing
+            } else {
+                RuleValue::List(items.iter().map(|item| item.trim().to_lowercase()).collect())
+            }
+        } else {
+            obj.str().map_or(RuleValue::Missing, |s| RuleValue::Text(s.to_string().to_lowercase()))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A literal on the right of a comparison (text lowercased).
+#[derive(Debug, Clone, PartialEq)]
+enum RuleLiteral {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+/// A rule's condition, with fields as indices into `RuleSet::fields`.
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Missing(usize),
+    Compare(usize, Comparison, RuleLiteral),
+    Contains(usize, String),
+}
+
+impl Condition {
+    fn matches(&self, values: &[RuleValue]) -> bool {
+        match self {
+            Condition::And(a, b) => a.matches(values) && b.matches(values),
+            Condition::Or(a, b) => a.matches(values) || b.matches(values),
+            Condition::Not(inner) => !inner.matches(values),
+            Condition::Missing(field) => values[*field] == RuleValue::Missing,
+            Condition::Contains(field, needle) => match &values[*field] {
+                RuleValue::Text(text) => text.contains(needle.as_str()),
+                RuleValue::List(items) => items.iter().any(|item| item == needle),
+                _ => false,
+            },
+            Condition::Compare(field, op, literal) => {
+                let ordering = match (&values[*field], literal) {
+                    (RuleValue::Number(a), RuleLiteral::Number(b)) => a.partial_cmp(b),
+                    (RuleValue::Text(a), RuleLiteral::Text(b)) => Some(a.as_str().cmp(b.as_str())),
+                    (RuleValue::Bool(a), RuleLiteral::Bool(b)) => Some(a.cmp(b)),
+                    _ => None,
+                };
+                match (op, ordering) {
+                    (Comparison::Ne, ordering) => ordering != Some(std::cmp::Ordering::Equal),
+                    (_, None) => false,
+                    (Comparison::Eq, Some(ordering)) => ordering.is_eq(),
+                    (Comparison::Lt, Some(ordering)) => ordering.is_lt(),
+                    (Comparison::Le, Some(ordering)) => ordering.is_le(),
+                    (Comparison::Gt, Some(ordering)) => ordering.is_gt(),
+                    (Comparison::Ge, Some(ordering)) => ordering.is_ge(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Adjustment {
+    Fit(f64),
+    Opportunity(f64),
+    Exclude,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    name: String,
+    condition: Condition,
+    adjustment: Adjustment,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Number(f64),
+    Text(String),
+    Compare(Comparison),
+    Open,
+    Close,
+    Colon,
+    Arrow,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// 1-based, in characters
+    column: usize,
+}
+
+/// A compile error at a 1-based column of the rule.
+struct RuleError {
+    column: usize,
+    message: String,
+}
+
+fn rule_error(column: usize, message: impl Into<String>) -> RuleError {
+    RuleError { column, message: message.into() }
+}
+
+fn tokenize_rule(rule: &str) -> Result<Vec<Token>, RuleError> {
+    let chars: Vec<char> = rule.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        let next = chars.get(i + 1).copied();
+        let (kind, len) = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => (TokenKind::Open, 1),
+            ')' => (TokenKind::Close, 1),
+            ':' => (TokenKind::Colon, 1),
+            '=' if next == Some('>') => (TokenKind::Arrow, 2),
+            '=' if next == Some('=') => (TokenKind::Compare(Comparison::Eq), 2),
+            '!' if next == Some('=') => (TokenKind::Compare(Comparison::Ne), 2),
+            '<' if next == Some('=') => (TokenKind::Compare(Comparison::Le), 2),
+            '>' if next == Some('=') => (TokenKind::Compare(Comparison::Ge), 2),
+            '<' => (TokenKind::Compare(Comparison::Lt), 1),
+            '>' => (TokenKind::Compare(Comparison::Gt), 1),
+            '"' | '\'' => {
+                let Some(len) = chars[i + 1..].iter().position(|&q| q == c) else {
+                    return Err(rule_error(column, "unterminated string"));
+                };
+                let text: String = chars[i + 1..i + 1 + len].iter().collect();
+                (TokenKind::Text(text.to_lowercase()), len + 2)
+            }
+            c if c.is_ascii_digit()
+                || (matches!(c, '+' | '-' | '.') && next.is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let len = 1 + chars[i + 1..]
+                    .iter()
+                    .take_while(|n| n.is_ascii_digit() || **n == '.')
+                    .count();
+                let text: String = chars[i..i + len].iter().collect();
+                let number = text
+                    .parse::<f64>()
+                    .map_err(|_| rule_error(column, format!("invalid number {text:?}")))?;
+                (TokenKind::Number(number), len)
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|n| n.is_alphanumeric() || **n == '_' || **n == '.')
+                    .count();
+                (TokenKind::Word(chars[i..i + len].iter().collect()), len)
+            }
+            c => return Err(rule_error(column, format!("unexpected character {c:?}"))),
+        };
+        tokens.push(Token { kind, column });
+        i += len;
+    }
+    Ok(tokens)
+}
+
+// Words with a meaning of their own, so not usable as field names
+const RULE_KEYWORDS: &[&str] = &["and", "or", "not", "contains", "missing", "true", "false"];
+
+/// Recursive descent over one rule's tokens, collecting the fields it reads.
+struct RuleParser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Column just past the rule, for errors at the end
+    end: usize,
+    fields: &'a mut Vec<RuleField>,
+}
+
+impl RuleParser<'_> {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|t| &t.kind)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |t| t.column)
+    }
+
+    fn next(&mut self) -> Option<TokenKind> {
+        let token = self.tokens.get(self.pos).map(|t| t.kind.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn at_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(TokenKind::Word(w)) if w == word)
+    }
+
+    fn rule(&mut self, index: usize) -> Result<Rule, RuleError> {
+        let named = matches!(
+            (self.peek(), self.tokens.get(self.pos + 1).map(|t| &t.kind)),
+            (Some(TokenKind::Word(_)), Some(TokenKind::Colon))
+        );
+        let name = match (named, self.peek()) {
+            (true, Some(TokenKind::Word(name))) => {
+                let name = name.clone();
+                self.pos += 2;
+                name
+            }
+            _ => format!("rule_{index}"),
+        };
+        let condition = self.or()?;
+        if self.peek() != Some(&TokenKind::Arrow) {
+            return Err(rule_error(self.column(), "expected \"=>\" and an adjustment"));
+        }
+        self.pos += 1;
+        let adjustment = self.adjustment()?;
+        if self.peek().is_some() {
+            return Err(rule_error(self.column(), "unexpected text after the adjustment"));
+        }
+        Ok(Rule { name, condition, adjustment })
+    }
+
+    fn or(&mut self) -> Result<Condition, RuleError> {
+        let mut condition = self.and()?;
+        while self.at_word("or") {
+            self.pos += 1;
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, RuleError> {
+        let mut condition = self.unary()?;
+        while self.at_word("and") {
+            self.pos += 1;
+            condition = Condition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<Condition, RuleError> {
+        if self.at_word("not") {
+            self.pos += 1;
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+        if self.peek() == Some(&TokenKind::Open) {
+            self.pos += 1;
+            let condition = self.or()?;
+            if self.peek() != Some(&TokenKind::Close) {
+                return Err(rule_error(self.column(), "expected \")\""));
+            }
+            self.pos += 1;
+            return Ok(condition);
+        }
+        self.test()
+    }
+
+    /// `field missing`, `field contains "text"` or `field <op> literal`.
+    fn test(&mut self) -> Result<Condition, RuleError> {
+        let column = self.column();
+        let Some(TokenKind::Word(word)) = self.next() else {
+            return Err(rule_error(column, "expected a field name"));
+        };
+        if RULE_KEYWORDS.contains(&word.as_str()) {
+            return Err(rule_error(column, format!("expected a field name, got {word:?}")));
+        }
+        let field = self.field(&word, column)?;
+        let column = self.column();
+        match self.next() {
+            Some(TokenKind::Word(w)) if w == "missing" => Ok(Condition::Missing(field)),
+            Some(TokenKind::Word(w)) if w == "contains" => {
+                let column = self.column();
+                match self.next() {
+                    Some(TokenKind::Text(text)) => Ok(Condition::Contains(field, text)),
+                    _ => Err(rule_error(column, "expected a quoted string after \"contains\"")),
+                }
+            }
+            Some(TokenKind::Compare(op)) => {
+                let column = self.column();
+                let literal = match self.next() {
+                    Some(TokenKind::Number(number)) => RuleLiteral::Number(number),
+                    Some(TokenKind::Text(text)) => RuleLiteral::Text(text),
+                    Some(TokenKind::Word(w)) if w == "true" => RuleLiteral::Bool(true),
+                    Some(TokenKind::Word(w)) if w == "false" => RuleLiteral::Bool(false),
+                    _ => {
+                        return Err(rule_error(
+                            column,
+                            "expected a number, quoted string, true or false",
+                        ))
+                    }
+                };
+                if matches!(literal, RuleLiteral::Bool(_))
+                    && !matches!(op, Comparison::Eq | Comparison::Ne)
+                {
+                    return Err(rule_error(column, "true and false only compare with == and !="));
+                }
+                Ok(Condition::Compare(field, op, literal))
+            }
+            _ => Err(rule_error(
+                column,
+                format!("expected \"missing\", \"contains\" or a comparison after {word:?}"),
+            )),
+        }
+    }
+
+    /// The index of `word` in the fields, added if new.
+    fn field(&mut self, word: &str, column: usize) -> Result<usize, RuleError> {
+        let (name, signal_only) = match word.strip_prefix("signals.") {
+            Some(name) => (name, true),
+            None => (word, false),
+        };
+        if name.is_empty() || name.contains('.') || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(rule_error(column, format!("invalid field name {word:?}")));
+        }
+        let field = RuleField { name: name.to_string(), signal_only };
+        Ok(match self.fields.iter().position(|f| *f == field) {
+            Some(index) => index,
+            None => {
+                self.fields.push(field);
+                self.fields.len() - 1
+            }
+        })
+    }
+
+    /// `fit <points>`, `opportunity <points>` or `exclude`.
+    fn adjustment(&mut self) -> Result<Adjustment, RuleError> {
+        let column = self.column();
+        let score = match self.next() {
+            Some(TokenKind::Word(w)) if w == "exclude" => return Ok(Adjustment::Exclude),
+            Some(TokenKind::Word(w)) if w == "fit" || w == "opportunity" => w,
+            _ => return Err(rule_error(column, "expected \"fit\", \"opportunity\" or \"exclude\"")),
+        };
+        let column = self.column();
+        let points = match self.next() {
+            Some(TokenKind::Number(points)) if points.abs() <= MAX_RULE_POINTS => points,
+            _ => {
+                return Err(rule_error(
+                    column,
+                    format!("expected points from -100 to 100 after {score:?}"),
+                ))
+            }
+        };
+        Ok(if score == "fit" { Adjustment::Fit(points) } else { Adjustment::Opportunity(points) })
+    }
+}
+
+/// Compiled score adjustment rules, from `compile_rules`.
+#[pyclass(frozen, module = "_leadswarm_native")]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+    fields: Vec<RuleField>,
+}
+
+#[pymethods]
+impl RuleSet {
+    /// Rule names, in order.
+    #[getter]
+    fn names(&self) -> Vec<String> {
+        self.rules.iter().map(|rule| rule.name.clone()).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.rules.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RuleSet({:?})", self.names())
+    }
+}
+
+impl RuleSet {
+    fn excludes(&self) -> bool {
+        self.rules.iter().any(|rule| rule.adjustment == Adjustment::Exclude)
+    }
+
+    /// The prospect's values for `fields`, in order.
+    fn values(&self, py: Python<'_>, prospect: &Fields) -> Vec<RuleValue> {
+        let signals = prospect.get("signals").map(|s| s.bind(py));
+        let signals = signals.and_then(|s| s.downcast::<PyDict>().ok());
+        self.fields
+            .iter()
+            .map(|field| {
+                let own = prospect
+                    .get(&field.name)
+                    .filter(|_| !field.signal_only)
+                    .map_or(RuleValue::Missing, |v| RuleValue::read(v.bind(py)));
+                if own != RuleValue::Missing {
+                    return own;
+                }
+                let signal = signals.and_then(|s| s.get_item(&field.name).ok().flatten());
+                signal.map_or(RuleValue::Missing, |v| RuleValue::read(&v))
+            })
+            .collect()
+    }
+
+    /// Apply every matching rule to the breakdowns, each as a "rule"
+    /// component with the rule's name as input, then clamp again. Returns the
+    /// names matched and whether any said to exclude.
+    fn apply(
+        &self,
+        values: &[RuleValue],
+        fit: &mut ScoreBreakdown,
+        opportunity: &mut ScoreBreakdown,
+        clamp: (f64, f64),
+    ) -> (Vec<String>, bool) {
+        let mut matched = Vec::new();
+        let mut exclude = false;
+        for rule in self.rules.iter().filter(|rule| rule.condition.matches(values)) {
+            let input = text_input(Some(rule.name.clone()));
+            match rule.adjustment {
+                Adjustment::Fit(points) => fit.add("rule", input, points),
+                Adjustment::Opportunity(points) => opportunity.add("rule", input, points),
+                Adjustment::Exclude => exclude = true,
+            }
+            matched.push(rule.name.clone());
+        }
+        if !matched.is_empty() {
+            *fit = std::mem::take(fit).clamp(clamp);
+            *opportunity = std::mem::take(opportunity).clamp(clamp);
+        }
+        (matched, exclude)
+    }
+}
+
+/// Compile score adjustment rules for `score_prospects_batch(rules=...)`.
+///
+/// Each rule is `[name:] condition => adjustment`:
+///   - `name` (letters, digits, `_`) labels the rule in explanations;
+///     unnamed rules are `rule_<index>`
+///   - a condition is `field missing`, `field contains "text"` or
+///     `field <op> literal` with `op` one of `== != < <= > >=` and the
+///     literal a number, a quoted string, `true` or `false`; conditions
+///     combine with `not`, `and`, `or` (loosest) and parentheses
+///   - the adjustment is `fit <points>`, `opportunity <points>` (-100 to
+///     100, e.g. `+10`) or `exclude`
+///
+/// A field reads the prospect's field of that name, or its signal when the
+/// field is missing; `signals.<name>` reads only the signal. Missing means
+/// absent, None, or an empty string or list. Text compares ignoring case,
+/// `contains` looks for a substring in text or an element in a list, and
+/// comparisons with a missing value or one of another type are false, except
+/// `!=`, which is always the opposite of `==`.
+///
+///     dentist_booking: category contains "dentist"
+///         and has_booking_system == false => opportunity +10
+///
+/// Errors raise ValueError with the rule's index and the column at fault.
+#[pyfunction]
+pub fn compile_rules(rules: Vec<String>) -> PyResult<RuleSet> {
+    let mut fields = Vec::new();
+    let mut compiled = Vec::with_capacity(rules.len());
+    for (index, rule) in rules.iter().enumerate() {
+        let parsed = tokenize_rule(rule).and_then(|tokens| {
+            let end = rule.chars().count() + 1;
+            RuleParser { tokens, pos: 0, end, fields: &mut fields }.rule(index)
+        });
+        match parsed {
+            Ok(rule) => compiled.push(rule),
+            Err(RuleError { column, message }) => {
+                return Err(PyValueError::new_err(format!(
+                    "rule {index} at column {column}: {message}"
+                )))
+            }
+        }
+    }
+    Ok(RuleSet { rules: compiled, fields })
+}
+
+// ---------------------------------------------------------------------------
+// Batch scoring with Rayon
+// ---------------------------------------------------------------------------
+
+/// What `score_prospects_batch` does with prospects whose site is down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UnreachablePolicy {
+    /// Zero the fit score: nobody to reach.
+    PenalizeFit,
+    /// Raise opportunity to at least the profile's `no_website` points.
+    BoostOpportunity,
+    /// Score -1 and list the index under `"excluded"`.
+    Exclude,
+}
+
+impl UnreachablePolicy {
+    fn parse(policy: &str) -> PyResult<Self> {
+        match policy {
+            "penalize_fit" => Ok(UnreachablePolicy::PenalizeFit),
+            "boost_opportunity" => Ok(UnreachablePolicy::BoostOpportunity),
+            "exclude" => Ok(UnreachablePolicy::Exclude),
+            other => Err(PyValueError::new_err(format!(
+                "unknown unreachable policy {other:?}; expected \"penalize_fit\", \
+                 \"boost_opportunity\" or \"exclude\""
+            ))),
+        }
+    }
+
+    /// Add the policy's "unreachable" component to an unreachable prospect's
+    /// breakdowns; the breakdowns still sum to the scores.
+    fn apply(
+        self,
+        fit: &mut ScoreBreakdown,
+        opportunity: &mut ScoreBreakdown,
+        profile: &ScoringProfile,
+    ) {
+        match self {
+            UnreachablePolicy::PenalizeFit => {
+                let subtotal = fit.subtotal();
+                fit.add("unreachable", bool_input(false), -subtotal);
+            }
+            UnreachablePolicy::BoostOpportunity => {
+                let (low, high) = profile.shape.clamp;
+                let floor = (profile.opportunity.no_website as f64).clamp(low, high);
+                let boost = (floor - opportunity.subtotal()).max(0.0);
+                opportunity.add("unreachable", bool_input(false), boost);
+            }
+            UnreachablePolicy::Exclude => {}
+        }
+    }
+}
+
+/// `rank` over the values of the prospects not excluded, with None in the
+/// excluded slots.
+fn over_included<T>(
+    values: &[f64],
+    excluded: &[bool],
+    rank: impl FnOnce(&[f64]) -> PyResult<Vec<T>>,
+) -> PyResult<Vec<Option<T>>> {
+    let included: Vec<f64> =
+        values.iter().zip(excluded).filter(|(_, &out)| !out).map(|(&v, _)| v).collect();
+    let mut ranked = rank(&included)?.into_iter();
+    Ok(excluded.iter().map(|&out| if out { None } else { ranked.next() }).collect())
+}
+
+/// Batch scores as NumPy arrays: `(fit, opportunity, priority)`.
+fn numpy_scores(py: Python<'_>, scores: &[(i64, i64)], priorities: Vec<f64>) -> PyResult<PyObject> {
+    // rust-numpy panics when it can't load NumPy's C API; raise instead
+    py.import("numpy").map_err(|e| {
+        PyImportError::new_err(format!("as_numpy=True needs NumPy installed: {e}"))
+    })?;
+    let fit: Vec<i64> = scores.iter().map(|&(fit, _)| fit).collect();
+    let opportunity: Vec<i64> = scores.iter().map(|&(_, opportunity)| opportunity).collect();
+    let arrays = (
+        PyArray1::from_vec(py, fit),
+        PyArray1::from_vec(py, opportunity),
+        PyArray1::from_vec(py, priorities),
+    );
+    Ok(arrays.into_pyobject(py)?.into_any().unbind())
+}
+
+/// Fit and opportunity breakdowns per prospect, on the rayon pool with the
+/// GIL released.
+fn score_breakdowns(
+    py: Python<'_>,
+    inputs: &[ProspectInputs],
+    profile: &ScoringProfile,
+) -> Vec<(ScoreBreakdown, ScoreBreakdown)> {
+    let score =
+        |p: &ProspectInputs| (fit_breakdown(p, profile), opportunity_breakdown(p, profile));
+    if inputs.len() <= 10 {
+        // Not worth the thread hand-off for small batches
+        inputs.iter().map(score).collect()
+    } else {
+        py.allow_threads(|| inputs.par_iter().map(score).collect())
+    }
+}
+
+/// Fit and opportunity scores for many prospects as `(fit, opportunity)`
+/// pairs, in input order; `weights` and `profile` as for
+/// `calculate_fit_score`. Prospects may be dicts or objects with the same
+/// fields as attributes (dataclasses, Pydantic models), `signals` included.
+///
+/// The scored fields are copied out of the dicts first, then scoring runs on
+/// the rayon pool with the GIL released. Results are identical to calling
+/// `calculate_fit_score` / `calculate_opportunity_score` per prospect.
+///
+/// With any of the options below, returns a dict instead: `"scores"` (the
+/// pairs), `"priority"` (by the formula from `set_priority_formula`, 0.4·fit +
+/// 0.6·opportunity unless changed), `"priority_formula"` (that formula, as
+/// from `get_priority_formula`) and
+///   - `explain`: `"explanations"`, `[{"fit": ..., "opportunity": ...}]` with
+///     each breakdown as from `explain_fit_score`
+///   - `rank_method` ("percentile" or "zscore"): `"fit_percentile"`,
+///     `"opportunity_percentile"` and `"priority_percentile"` (or
+///     `"..._zscore"`), each as from `rank_prospects` over this batch
+///   - `grade` or `grade_thresholds`: `"grade"`, a letter per prospect from
+///     `grade_prospects` over the priorities
+///   - `freshness_half_life_days`: priority decays by half for every that
+///     many days since the prospect's `scraped_at` (ISO 8601), measured to
+///     `freshness_now` (Unix seconds, default now). `"priority"` is then the
+///     decayed value, with `"priority_raw"`, `"freshness"` (the multiplier)
+///     and `"freshness_unknown"` (True where `scraped_at` was missing or
+///     unparseable, so no decay was applied). Ranks and grades use the
+///     decayed priority.
+///   - `report_warnings`: `"warnings"`, `{"coerced": {key: count}, "ignored":
+///     {key: count}}` counting numeric strings read as numbers and
+///     wrongly-typed values scored as absent (signal keys as
+///     `"signals.<key>"`)
+///   - `with_notes`: `"notes"`, each as from `generate_opportunity_notes`
+///     with `notes_templates` as its `templates`
+///   - `with_confidence` or `confidence_weights`: `"confidence"`, each as
+///     from `calculate_confidence` with `confidence_weights` as its `weights`
+///   - `with_stats`: `"stats"`, `{"fit": ..., "opportunity": ...,
+///     "priority": ...}` each as from `score_statistics`, priority after any
+///     freshness decay
+///
+/// With `explain`, each entry also has the `"confidence"` breakdown, its
+/// components the points each field earned.
+///
+/// `unreachable_policy` treats prospects whose `signals.reachable` is
+/// exactly False (site down, DNS dead). A missing or None `reachable` means
+/// the check never ran and is scored as usual, as is any non-bool value.
+///   - "penalize_fit": fit drops to 0 (an `"unreachable"` fit component)
+///   - "boost_opportunity": opportunity rises to at least the profile's
+///     `no_website` points (an `"unreachable"` opportunity component)
+///   - "exclude": the prospect keeps its place but scores `(-1, -1)`, with
+///     priority -1.0. In the dict output `"excluded"` lists those indices,
+///     ranks, grades and stats are over the rest, and excluded entries get None.
+///
+/// `config` (from `load_scoring_config`) stands in for `profile`, with
+/// `weights` still applied on top, and its priority formula, if it has one,
+/// for the one from `set_priority_formula`.
+///
+/// `rules` (from `compile_rules`) adjust the scores after everything above,
+/// each match a "rule" component in the breakdowns; with `explain`, each
+/// entry also lists the names of the rules it matched under `"rules"`.
+/// Prospects a rule excludes are treated as for `unreachable_policy`
+/// "exclude".
+///
+/// `as_numpy` returns `(fit, opportunity, priority)` as NumPy arrays (int64,
+/// int64, float64) allocated in Rust, with the same values as the pairs and
+/// the dict output's `"priority"`. It can't be combined with the options
+/// above that return a dict, and raises ImportError when NumPy isn't
+/// installed.
+///
+/// `strict` raises ValueError naming the prospect's index, the key and the
+/// type found instead, as for `calculate_fit_score`.
+#[pyfunction]
+#[pyo3(signature = (
+    prospects, weights=None, explain=false, rank_method=None, grade=false, grade_thresholds=None,
+    profile=None, freshness_half_life_days=None, freshness_now=None, strict=false,
+    report_warnings=false, with_notes=false, notes_templates=None, unreachable_policy=None,
+    config=None, rules=None, as_numpy=false, with_confidence=false, confidence_weights=None,
+    with_stats=false
+))]
+// Each option is a keyword argument on the Python side
+#[allow(clippy::too_many_arguments)]
+pub fn score_prospects_batch(
+    py: Python<'_>,
+    prospects: Vec<Bound<'_, PyAny>>,
+    weights: Option<HashMap<String, f64>>,
+    explain: bool,
+    rank_method: Option<&str>,
+    grade: bool,
+    grade_thresholds: Option<Vec<f64>>,
+    profile: Option<&str>,
+    freshness_half_life_days: Option<f64>,
+    freshness_now: Option<f64>,
+    strict: bool,
+    report_warnings: bool,
+    with_notes: bool,
+    notes_templates: Option<HashMap<String, String>>,
+    unreachable_policy: Option<&str>,
+    config: Option<PyRef<'_, ScoringConfig>>,
+    rules: Option<PyRef<'_, RuleSet>>,
+    as_numpy: bool,
+    with_confidence: bool,
+    confidence_weights: Option<HashMap<String, f64>>,
+    with_stats: bool,
+) -> PyResult<PyObject> {
+    let with_confidence = with_confidence || confidence_weights.is_some();
+    let dict_output = explain
+        || rank_method.is_some()
+        || grade
+        || grade_thresholds.is_some()
+        || freshness_half_life_days.is_some()
+        || report_warnings
+        || with_notes
+        || with_confidence
+        || with_stats;
+    if as_numpy && dict_output {
+        return Err(PyValueError::new_err(
+            "as_numpy returns plain score arrays; it can't be combined with explain, \
+             rank_method, grade, grade_thresholds, freshness_half_life_days, report_warnings, \
+             with_notes, with_confidence, confidence_weights or with_stats",
+        ));
+    }
+    // Objects are read for the fields the rules use as well
+    let mut keys: Vec<&str> = SCORED_FIELDS.to_vec();
+    if with_confidence || explain {
+        keys.extend(CONFIDENCE_FIELDS);
+    }
+    let mut signal_keys: Vec<&str> = SCORED_SIGNAL_FIELDS.to_vec();
+    for field in rules.iter().flat_map(|rules| &rules.fields) {
+        if !field.signal_only {
+            keys.push(&field.name);
+        }
+        signal_keys.push(&field.name);
+    }
+    let nested: &[(&str, &[&str])] = &[("signals", &signal_keys)];
+    let prospects = prospects
+        .iter()
+        .map(|p| prospect_fields(p, &keys, nested))
+        .collect::<PyResult<Vec<Fields>>>()?;
+    let grade = grade || grade_thresholds.is_some();
+    let decay = freshness_half_life_days
+        .map(|half_life| FreshnessDecay::new(half_life, freshness_now))
+        .transpose()?;
+    let scraped_at: Vec<Option<String>> = match decay {
+        Some(_) => prospects.iter().map(|p| extract_opt_string(py, p, "scraped_at")).collect(),
+        None => Vec::new(),
+    };
+    let (profile, formula) = match &config {
+        Some(_) if profile.is_some() => {
+            return Err(PyValueError::new_err("pass either profile or config, not both"))
+        }
+        Some(config) => {
+            let mut profile = config.profile.clone();
+            profile.fit = profile.fit.with_overrides(weights.as_ref())?;
+            (profile, config.formula)
+        }
+        None => (resolve_profile(profile, weights.as_ref())?, None),
+    };
+    let rank_method = rank_method.map(RankMethod::parse).transpose()?;
+    let templates = with_notes.then(|| note_templates(notes_templates.as_ref())).transpose()?;
+    let unreachable_policy = unreachable_policy.map(UnreachablePolicy::parse).transpose()?;
+    let confidence_weights = ConfidenceWeights::with_overrides(confidence_weights.as_ref())?;
+    let mut inputs = Vec::with_capacity(prospects.len());
+    let mut coerced: BTreeMap<String, usize> = BTreeMap::new();
+    let mut ignored: BTreeMap<String, usize> = BTreeMap::new();
+    for (i, prospect) in prospects.iter().enumerate() {
+        let (prospect_inputs, issues) =
+            ProspectInputs::extract_checked(py, prospect, strict, Some(i))?;
+        for key in issues.coerced {
+            *coerced.entry(key).or_default() += 1;
+        }
+        for key in issues.ignored {
+            *ignored.entry(key).or_default() += 1;
+        }
+        inputs.push(prospect_inputs);
+    }
+    let mut breakdowns = score_breakdowns(py, &inputs, &profile);
+    let mut excluded = vec![false; inputs.len()];
+    if let Some(policy) = unreachable_policy {
+        for (i, (fit, opportunity)) in breakdowns.iter_mut().enumerate() {
+            if inputs[i].is_unreachable() {
+                policy.apply(fit, opportunity, &profile);
+                excluded[i] = policy == UnreachablePolicy::Exclude;
+            }
+        }
+    }
+    let mut matched_rules: Vec<Vec<String>> = Vec::new();
+    if let Some(rules) = &rules {
+        for (i, (fit, opportunity)) in breakdowns.iter_mut().enumerate() {
+            let values = rules.values(py, &prospects[i]);
+            let (matched, exclude) = rules.apply(&values, fit, opportunity, profile.shape.clamp);
+            excluded[i] |= exclude;
+            matched_rules.push(matched);
+        }
+    }
+
+    let scores: Vec<(u32, u32)> =
+        breakdowns.iter().map(|(fit, opportunity)| (fit.score(), opportunity.score())).collect();
+    // Excluded prospects keep their slot with a -1 sentinel
+    let sentinel = |i: usize, value: f64| if excluded[i] { -1.0 } else { value };
+    let reported: Vec<(i64, i64)> = scores
+        .iter()
+        .enumerate()
+        .map(|(i, &(fit, opp))| if excluded[i] { (-1, -1) } else { (fit as i64, opp as i64) })
+        .collect();
+    let formula = formula.unwrap_or_else(current_priority_formula);
+    if as_numpy {
+        let priorities: Vec<f64> = scores
+            .iter()
+            .enumerate()
+            .map(|(i, &(fit, opp))| sentinel(i, formula.apply(fit, opp)))
+            .collect();
+        return numpy_scores(py, &reported, priorities);
+    }
+    if !dict_output {
+        return Ok(reported.into_pyobject(py)?.into_any().unbind());
+    }
+
+    let mut priorities: Vec<f64> =
+        scores.iter().map(|&(fit, opp)| formula.apply(fit, opp)).collect();
+    let out = PyDict::new(py);
+    out.set_item("priority_formula", formula.to_dict(py)?)?;
+    let excludes = rules.as_ref().is_some_and(|rules| rules.excludes());
+    if unreachable_policy == Some(UnreachablePolicy::Exclude) || excludes {
+        let indices: Vec<usize> = (0..excluded.len()).filter(|&i| excluded[i]).collect();
+        out.set_item("excluded", indices)?;
+    }
+    if let Some(decay) = decay {
+        let multipliers: Vec<Option<f64>> =
+            scraped_at.iter().map(|at| decay.multiplier(at.as_deref())).collect();
+        let raw: Vec<f64> = priorities.iter().enumerate().map(|(i, &p)| sentinel(i, p)).collect();
+        out.set_item("priority_raw", raw)?;
+        for (priority, multiplier) in priorities.iter_mut().zip(&multipliers) {
+            *priority *= multiplier.unwrap_or(1.0);
+        }
+        let applied: Vec<f64> = multipliers.iter().map(|m| m.unwrap_or(1.0)).collect();
+        let unknown: Vec<bool> = multipliers.iter().map(Option::is_none).collect();
+        out.set_item("freshness", applied)?;
+        out.set_item("freshness_unknown", unknown)?;
+    }
+    if let Some(templates) = &templates {
+        let notes: Vec<String> = breakdowns
+            .iter()
+            .map(|(_, opportunity)| opportunity_notes(opportunity, templates))
+            .collect();
+        out.set_item("notes", notes)?;
+    }
+    let confidence: Vec<ScoreBreakdown> = if with_confidence || explain {
+        prospects
+            .iter()
+            .map(|p| confidence_breakdown(&Coverage::read(py, p), &confidence_weights))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if with_confidence {
+        let totals: Vec<u32> = confidence.iter().map(ScoreBreakdown::score).collect();
+        out.set_item("confidence", totals)?;
+    }
+    if explain {
+        let explanations = PyList::empty(py);
+        let rows = breakdowns.into_iter().zip(confidence);
+        for (i, ((fit, opportunity), confidence)) in rows.enumerate() {
+            let entry = PyDict::new(py);
+            entry.set_item("fit", fit)?;
+            entry.set_item("opportunity", opportunity)?;
+            entry.set_item("confidence", confidence)?;
+            if let Some(matched) = matched_rules.get(i) {
+                entry.set_item("rules", matched)?;
+            }
+            explanations.append(entry)?;
+        }
+        out.set_item("explanations", explanations)?;
+    }
+    if let Some(method) = rank_method {
+        let fits: Vec<f64> = scores.iter().map(|&(fit, _)| fit as f64).collect();
+        let opportunities: Vec<f64> = scores.iter().map(|&(_, opp)| opp as f64).collect();
+        let suffix = method.suffix();
+        let columns = [("fit", &fits), ("opportunity", &opportunities), ("priority", &priorities)];
+        for (name, values) in columns {
+            let ranks = over_included(values, &excluded, |v| Ok(rank_scores(v, method)))?;
+            out.set_item(format!("{name}_{suffix}"), ranks)?;
+        }
+    }
+    if grade {
+        let grades = over_included(&priorities, &excluded, |v| grade_scores(v, grade_thresholds))?;
+        out.set_item("grade", grades)?;
+    }
+    if with_stats {
+        let stats = PyDict::new(py);
+        let fits: Vec<f64> = scores.iter().map(|&(fit, _)| fit as f64).collect();
+        let opportunities: Vec<f64> = scores.iter().map(|&(_, opp)| opp as f64).collect();
+        let columns = [("fit", &fits), ("opportunity", &opportunities), ("priority", &priorities)];
+        for (name, values) in columns {
+            let included: Vec<f64> =
+                values.iter().zip(&excluded).filter(|(_, &out)| !out).map(|(&v, _)| v).collect();
+            stats.set_item(name, score_stats(&included, DEFAULT_HISTOGRAM_BINS))?;
+        }
+        out.set_item("stats", stats)?;
+    }
+    if report_warnings {
+        let warnings = PyDict::new(py);
+        warnings.set_item("coerced", coerced)?;
+        warnings.set_item("ignored", ignored)?;
+        out.set_item("warnings", warnings)?;
+    }
+    let priorities: Vec<f64> =
+        priorities.iter().enumerate().map(|(i, &p)| sentinel(i, p)).collect();
+    out.set_item("scores", reported)?;
+    out.set_item("priority", priorities)?;
+    Ok(out.into_any().unbind())
+}
+
+// ---------------------------------------------------------------------------
+// Weighting comparison
+// ---------------------------------------------------------------------------
+
+// Movers reported by `compare_weightings` unless `top` says otherwise
+const DEFAULT_TOP_MOVERS: usize = 50;
+
+/// The profile one side of `compare_weightings` scores with: a registered
+/// profile name, fit weight overrides on `base`, or `base` itself for None.
+fn weighting_profile(
+    weights: Option<&Bound<'_, PyAny>>,
+    base: Option<&str>,
+) -> PyResult<ScoringProfile> {
+    match weights {
+        None => resolve_profile(base, None),
+        Some(w) if w.is_none() => resolve_profile(base, None),
+        Some(w) => match w.extract::<String>() {
+            Ok(name) => lookup_profile(&name),
+            Err(_) => resolve_profile(base, Some(&w.extract::<HashMap<String, f64>>()?)),
+        },
+    }
+}
+
+/// 0-based position of each priority when sorted best first, ties in input
+/// order.
+fn priority_positions(priorities: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..priorities.len()).collect();
+    order.sort_by(|&a, &b| priorities[b].total_cmp(&priorities[a]).then(a.cmp(&b)));
+    let mut positions = vec![0; priorities.len()];
+    for (position, &i) in order.iter().enumerate() {
+        positions[i] = position;
+    }
+    positions
+}
+
+/// Spearman's rank correlation, as Pearson's over average ranks. 1.0 when
+/// the rankings are identical (including empty and all-tied batches), None
+/// when only one side is all tied.
+fn spearman(a: &[f64], b: &[f64]) -> Option<f64> {
+    let ranks_a = rank_scores(a, RankMethod::Percentile);
+    let ranks_b = rank_scores(b, RankMethod::Percentile);
+    if ranks_a == ranks_b {
+        return Some(1.0);
+    }
+    let n = a.len() as f64;
+    let mean_a = ranks_a.iter().sum::<f64>() / n;
+    let mean_b = ranks_b.iter().sum::<f64>() / n;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in ranks_a.iter().zip(&ranks_b) {
+        cov += (x - mean_a) * (y - mean_b);
+        var_a += (x - mean_a).powi(2);
+        var_b += (y - mean_b).powi(2);
+    }
+    if var_a == 0.0 || var_b == 0.0 {
+        return None;
+    }
+    Some((cov / (var_a * var_b).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// How the ranking of a batch changes between two weightings, for trying
+/// weights offline before rolling them out. `weights_a` and `weights_b` are
+/// each a registered profile name, fit weight overrides on `profile` (as for
+/// `score_prospects_batch`), or None for `profile` as is. Both sides score
+/// in parallel and take priority from the current formula.
+///
+/// Returns a dict with
+///   - `"count"`: prospects compared
+///   - `"spearman"`: rank correlation of the two priority orderings, 1.0 for
+///     identical rankings, None when only one side ranks everything equal
+///   - `"grade_changes"`: prospects whose grade differs, each side graded as
+///     by `grade_prospects` with `grade_thresholds`
+///   - `"movers"`: up to `top` (default 50) prospects whose position moved,
+///     largest move first (then by index), each `{"index", "rank_a",
+///     "rank_b", "movement", "scores_a", "scores_b", "priority_a",
+///     "priority_b"}`; ranks count from 1 with ties in input order,
+///     `movement` is `rank_a - rank_b` (positive moved up under b) and the
+///     scores are `(fit, opportunity)` pairs
+#[pyfunction]
+#[pyo3(signature = (
+    prospects, weights_a=None, weights_b=None, profile=None, grade_thresholds=None,
+    top=DEFAULT_TOP_MOVERS
+))]
+pub fn compare_weightings<'py>(
+    py: Python<'py>,
+    prospects: Vec<Bound<'py, PyAny>>,
+    weights_a: Option<Bound<'py, PyAny>>,
+    weights_b: Option<Bound<'py, PyAny>>,
+    profile: Option<&str>,
+    grade_thresholds: Option<Vec<f64>>,
+    top: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let profile_a = weighting_profile(weights_a.as_ref(), profile)?;
+    let profile_b = weighting_profile(weights_b.as_ref(), profile)?;
+    let nested: &[(&str, &[&str])] = &[("signals", SCORED_SIGNAL_FIELDS)];
+    let inputs = prospects
+        .iter()
+        .map(|p| ProspectInputs::extract(py, &prospect_fields(p, SCORED_FIELDS, nested)?, false))
+        .collect::<PyResult<Vec<_>>>()?;
+    let formula = current_priority_formula();
+    let scored = |profile: &ScoringProfile| -> (Vec<(u32, u32)>, Vec<f64>) {
+        let scores: Vec<(u32, u32)> = score_breakdowns(py, &inputs, profile)
+            .iter()
+            .map(|(fit, opportunity)| (fit.score(), opportunity.score()))
+            .collect();
+        let priorities = scores.iter().map(|&(fit, opp)| formula.apply(fit, opp)).collect();
+        (scores, priorities)
+    };
+    let (scores_a, priorities_a) = scored(&profile_a);
+    let (scores_b, priorities_b) = scored(&profile_b);
+
+    let grades_a = grade_scores(&priorities_a, grade_thresholds.clone())?;
+    let grades_b = grade_scores(&priorities_b, grade_thresholds)?;
+    let grade_changes = grades_a.iter().zip(&grades_b).filter(|(a, b)| a != b).count();
+
+    let positions_a = priority_positions(&priorities_a);
+    let positions_b = priority_positions(&priorities_b);
+    let mut moved: Vec<(usize, i64)> = positions_a
+        .iter()
+        .zip(&positions_b)
+        .map(|(&a, &b)| a as i64 - b as i64)
+        .enumerate()
+        .filter(|&(_, movement)| movement != 0)
+        .collect();
+    moved.sort_by(|a, b| b.1.abs().cmp(&a.1.abs()).then(a.0.cmp(&b.0)));
+    moved.truncate(top);
+    let movers = PyList::empty(py);
+    for (i, movement) in moved {
+        let mover = PyDict::new(py);
+        mover.set_item("index", i)?;
+        mover.set_item("rank_a", positions_a[i] + 1)?;
+        mover.set_item("rank_b", positions_b[i] + 1)?;
+        mover.set_item("movement", movement)?;
+        mover.set_item("scores_a", scores_a[i])?;
+        mover.set_item("scores_b", scores_b[i])?;
+        mover.set_item("priority_a", priorities_a[i])?;
+        mover.set_item("priority_b", priorities_b[i])?;
+        movers.append(mover)?;
+    }
+
+    let out = PyDict::new(py);
+    out.set_item("count", inputs.len())?;
+    out.set_item("spearman", spearman(&priorities_a, &priorities_b))?;
+    out.set_item("grade_changes", grade_changes)?;
+    out.set_item("movers", movers)?;
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Score deltas between runs
+// ---------------------------------------------------------------------------
+
+// Fields `score_deltas` compares, then signal flags (reported as "signals.<key>")
+const DELTA_FIELDS: &[&str] =
+    &["website", "found_in_ads", "found_in_maps", "rating", "review_count"];
+const DELTA_SIGNAL_FLAGS: &[&str] = &[
+    "reachable", "is_parked", "is_placeholder", "has_google_analytics", "has_facebook_pixel",
+    "has_google_ads", "has_booking_system", "has_responsive", "has_legacy_jquery",
+    "has_legacy_framework", "has_online_ordering", "is_hiring", "is_thin_content",
+];
+// Read from non-dict prospects besides the scored fields, so entries export whole
+const DELTA_CARRIED_FIELDS: &[&str] = &[
+    "name", "domain", "address", "category", "ad_position", "source", "opportunity_notes",
+    "fit_score", "opportunity_score", "priority_score",
+];
+const DELTA_CARRIED_SIGNAL_FIELDS: &[&str] = &["title", "meta_description", "social_links"];
+
+/// The join key for one prospect: a normalized domain for "domain" (falling
+/// back to the website's) and "website", a normalized number for "phone",
+/// and any other field trimmed and lowercased.
+fn delta_join_key(py: Python<'_>, fields: &Fields, key: &str) -> Option<String> {
+    let text = |k: &str| extract_opt_string(py, fields, k);
+    let joined = match key {
+        "domain" => text("domain")
+            .and_then(|d| crate::text::normalize_domain(&d))
+            .or_else(|| text("website").and_then(|w| crate::text::normalize_domain(&w))),
+        "website" => text("website").and_then(|w| crate::text::normalize_domain(&w)),
+        "phone" => text("phone").map(|p| crate::text::normalize_phone(&p)),
+        other => fields
+            .get(other)
+            .map(|obj| obj.bind(py))
+            .filter(|obj| !obj.is_none())
+            .and_then(|obj| obj.str().ok())
+            .map(|s| s.to_string_lossy().trim().to_lowercase()),
+    };
+    joined.filter(|k| !k.is_empty())
+}
+
+/// One side's scores for `score_deltas`.
+#[derive(Debug, Clone, Copy)]
+struct RunScores {
+    fit: u32,
+    opportunity: u32,
+    priority: f64,
+}
+
+impl RunScores {
+    /// The prospect's stored `fit_score` / `opportunity_score` /
+    /// `priority_score`, or freshly computed when `rescore` or the stored
+    /// fit or opportunity score is missing.
+    fn read(
+        py: Python<'_>,
+        fields: &Fields,
+        rescore: bool,
+        profile: &ScoringProfile,
+        formula: &PriorityFormula,
+    ) -> PyResult<Self> {
+        let stored = |key: &str| fields.get(key).and_then(|obj| obj.extract::<u32>(py).ok());
+        let (fit, opportunity) = match (stored("fit_score"), stored("opportunity_score")) {
+            (Some(fit), Some(opportunity)) if !rescore => (fit, opportunity),
+            _ => {
+                let inputs = ProspectInputs::extract(py, fields, false)?;
+                let fit = fit_breakdown(&inputs, profile).score();
+                (fit, opportunity_breakdown(&inputs, profile).score())
+            }
+        };
+        let priority = fields
+            .get("priority_score")
+            .filter(|_| !rescore)
+            .and_then(|obj| obj.extract::<f64>(py).ok())
+            .unwrap_or_else(|| formula.apply(fit, opportunity));
+        Ok(RunScores { fit, opportunity, priority })
+    }
+
+    fn to_dict<'py>(self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("fit", self.fit)?;
+        dict.set_item("opportunity", self.opportunity)?;
+        dict.set_item("priority", self.priority)?;
+        Ok(dict)
+    }
+}
+
+/// Which of `DELTA_FIELDS` and the signal flags differ between two runs of
+/// a prospect; missing and None count as the same.
+fn changed_fields(py: Python<'_>, previous: &Fields, current: &Fields) -> PyResult<Vec<String>> {
+    let differs = |a: Option<&PyObject>, b: Option<&PyObject>| -> PyResult<bool> {
+        let a = a.map(|obj| obj.bind(py)).filter(|obj| !obj.is_none());
+        let b = b.map(|obj| obj.bind(py)).filter(|obj| !obj.is_none());
+        match (a, b) {
+            (Some(a), Some(b)) => Ok(!a.eq(b)?),
+            (None, None) => Ok(false),
+            _ => Ok(true),
+        }
+    };
+    let mut changed = Vec::new();
+    for key in DELTA_FIELDS {
+        if differs(previous.get(*key), current.get(*key))? {
+            changed.push(key.to_string());
+        }
+    }
+    let signals = |fields: &Fields| {
+        fields.get("signals").and_then(|obj| obj.extract::<Fields>(py).ok()).unwrap_or_default()
+    };
+    let (before, after) = (signals(previous), signals(current));
+    for key in DELTA_SIGNAL_FLAGS {
+        if differs(before.get(*key), after.get(*key))? {
+            changed.push(format!("signals.{key}"));
+        }
+    }
+    Ok(changed)
+}
+
+/// Fill in the `"delta"` dict of a prospect found in only one run.
+fn unmatched_delta(delta: &Bound<'_, PyDict>, status: &str) -> PyResult<()> {
+    delta.set_item("status", status)?;
+    for key in ["fit", "opportunity", "priority", "previous"] {
+        delta.set_item(key, delta.py().None())?;
+    }
+    delta.set_item("changed_fields", Vec::<String>::new())
+}
+
+/// A delta entry: the prospect's own fields with the scores used, plus a
+/// `"delta"` dict.
+fn delta_entry<'py>(
+    py: Python<'py>,
+    fields: &Fields,
+    scores: RunScores,
+    delta: Bound<'py, PyDict>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let entry = PyDict::new(py);
+    for (key, value) in fields {
+        entry.set_item(key, value.bind(py))?;
+    }
+    entry.set_item("fit_score", scores.fit)?;
+    entry.set_item("opportunity_score", scores.opportunity)?;
+    entry.set_item("priority_score", scores.priority)?;
+    entry.set_item("delta", delta)?;
+    Ok(entry)
+}
+
+/// Score changes between two runs over the same market, `previous` and
+/// `current` being lists of prospects (dicts or objects) joined on `key`:
+/// "domain" (normalized, falling back to the website's domain), "website",
+/// "phone" (normalized), or any other field, compared trimmed and
+/// lowercased. Only the first prospect per key on each side is joined; later
+/// ones come out as new or dropped.
+///
+/// Scores are read from `fit_score` / `opportunity_score` /
+/// `priority_score` where both fit and opportunity are set, and otherwise
+/// computed with `profile` and the current priority formula; `rescore`
+/// always computes them.
+///
+/// Returns one dict per prospect, current ones in order then dropped ones:
+/// the prospect's fields with the scores used, so the list goes straight to
+/// `serialize_prospects_json`, and under `"delta"`:
+///   - `"status"`: "matched", "new" (only in `current`, or with no key) or
+///     "dropped" (only in `previous`, scores as they were)
+///   - `"key"`: the normalized join key, or None
+///   - `"fit"`, `"opportunity"`, `"priority"`: current minus previous, None
+///     unless matched
+///   - `"changed_fields"`: which of website, found_in_ads, found_in_maps,
+///     rating, review_count and the signal flags (as `"signals.<key>"`,
+///     e.g. `"signals.reachable"`) differ
+///   - `"previous"`: `{"fit", "opportunity", "priority"}` when matched
+#[pyfunction]
+#[pyo3(signature = (previous, current, key="domain", rescore=false, profile=None))]
+pub fn score_deltas<'py>(
+    py: Python<'py>,
+    previous: Vec<Bound<'py, PyAny>>,
+    current: Vec<Bound<'py, PyAny>>,
+    key: &str,
+    rescore: bool,
+    profile: Option<&str>,
+) -> PyResult<Bound<'py, PyList>> {
+    let keys: Vec<&str> =
+        SCORED_FIELDS.iter().chain(DELTA_CARRIED_FIELDS).copied().chain([key]).collect();
+    let signal_keys: Vec<&str> = SCORED_SIGNAL_FIELDS
+        .iter()
+        .chain(DELTA_SIGNAL_FLAGS)
+        .chain(DELTA_CARRIED_SIGNAL_FIELDS)
+        .copied()
+        .collect();
+    let nested: &[(&str, &[&str])] = &[("signals", &signal_keys)];
+    let profile = resolve_profile(profile, None)?;
+    let formula = current_priority_formula();
+    let read_run = |prospects: &[Bound<'py, PyAny>]| -> PyResult<Vec<_>> {
+        prospects
+            .iter()
+            .map(|p| {
+                let fields = prospect_fields(p, &keys, nested)?;
+                let scores = RunScores::read(py, &fields, rescore, &profile, &formula)?;
+                Ok((delta_join_key(py, &fields, key), fields, scores))
+            })
+            .collect()
+    };
+    let previous = read_run(&previous)?;
+    let current = read_run(&current)?;
+
+    let mut previous_by_key: HashMap<&str, usize> = HashMap::new();
+    for (i, (joined, _, _)) in previous.iter().enumerate() {
+        if let Some(joined) = joined {
+            previous_by_key.entry(joined.as_str()).or_insert(i);
+        }
+    }
+    let mut matched = vec![false; previous.len()];
+    let mut seen: HashSet<&str> = HashSet::new();
+    let out = PyList::empty(py);
+    for (joined, fields, scores) in &current {
+        let delta = PyDict::new(py);
+        delta.set_item("key", joined)?;
+        let first = joined.as_deref().is_some_and(|k| seen.insert(k));
+        let found = joined
+            .as_deref()
+            .filter(|_| first)
+            .and_then(|k| previous_by_key.get(k).copied());
+        match found {
+            Some(i) => {
+                let (_, before_fields, before) = &previous[i];
+                matched[i] = true;
+                delta.set_item("status", "matched")?;
+                let opportunity = scores.opportunity as i64 - before.opportunity as i64;
+                delta.set_item("fit", scores.fit as i64 - before.fit as i64)?;
+                delta.set_item("opportunity", opportunity)?;
+                delta.set_item("priority", scores.priority - before.priority)?;
+                delta.set_item("changed_fields", changed_fields(py, before_fields, fields)?)?;
+                delta.set_item("previous", before.to_dict(py)?)?;
+            }
+            None => unmatched_delta(&delta, "new")?,
+        }
+        out.append(delta_entry(py, fields, *scores, delta)?)?;
+    }
+    for (i, (joined, fields, scores)) in previous.iter().enumerate() {
+        if matched[i] {
+            continue;
+        }
+        let delta = PyDict::new(py);
+        delta.set_item("key", joined)?;
+        unmatched_delta(&delta, "dropped")?;
+        out.append(delta_entry(py, fields, *scores, delta)?)?;
+    }
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Tier segmentation
+// ---------------------------------------------------------------------------
+
+// Default priority thresholds for the "call_now" and "email_sequence" tiers
+const TIER_CALL_NOW: f64 = 60.0;
+const TIER_EMAIL_SEQUENCE: f64 = 40.0;
+
+// Read from non-dict prospects besides the scored fields
+const SEGMENT_STORED_FIELDS: &[&str] = &["fit_score", "opportunity_score", "priority_score"];
+
+/// Outreach tier from `segment_prospects`, best first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tier {
+    CallNow,
+    EmailSequence,
+    Nurture,
+    /// No phone or email to reach the business on
+    Unreachable,
+}
+
+impl Tier {
+    const ALL: [Tier; 4] = [Tier::CallNow, Tier::EmailSequence, Tier::Nurture, Tier::Unreachable];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Tier::CallNow => "call_now",
+            Tier::EmailSequence => "email_sequence",
+            Tier::Nurture => "nurture",
+            Tier::Unreachable => "unreachable",
+        }
+    }
+}
+
+/// Priority cut-offs for the scored tiers, from `segment_prospects`'s
+/// `thresholds`.
+fn tier_thresholds(thresholds: Option<&HashMap<String, f64>>) -> PyResult<(f64, f64)> {
+    let (mut call_now, mut email_sequence) = (TIER_CALL_NOW, TIER_EMAIL_SEQUENCE);
+    for (name, &value) in thresholds.into_iter().flatten() {
+        let slot = match name.as_str() {
+            "call_now" => &mut call_now,
+            "email_sequence" => &mut email_sequence,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown tier {other:?}; thresholds apply to call_now and email_sequence"
+                )))
+            }
+        };
+        if !value.is_finite() {
+            return Err(PyValueError::new_err(format!(
+                "tier threshold {name:?} must be a number, got {value}"
+            )));
+        }
+        *slot = value;
+    }
+    if email_sequence > call_now {
+        return Err(PyValueError::new_err(format!(
+            "call_now threshold ({call_now}) must not be below email_sequence ({email_sequence})"
+        )));
+    }
+    Ok((call_now, email_sequence))
+}
+
+/// Bucket prospects (dicts or objects) into outreach tiers by priority in
+/// one pass: "call_now" from `thresholds["call_now"]` (default 60),
+/// "email_sequence" from `thresholds["email_sequence"]` (default 40), and
+/// "nurture" below that. A threshold exactly met takes the higher tier.
+///
+/// Priority is the stored `priority_score`, as for `score_deltas`: computed
+/// under `profile` and the formula from `set_priority_formula` when missing
+/// or when `rescore`. With `require_contact`, prospects with neither a phone
+/// number nor an email (`emails` or `signals.emails`) go to "unreachable"
+/// whatever their priority.
+///
+/// Returns `{"tiers": {tier: [index, ...]}, "counts": {tier: n, "total": n},
+/// "thresholds": {"call_now": ..., "email_sequence": ...}}`, every tier
+/// present even when empty. `write_tier` also sets each prospect's `"tier"`
+/// (an attribute on objects), which the exporters add as a column.
+#[pyfunction]
+#[pyo3(signature = (
+    prospects, thresholds=None, require_contact=true, write_tier=false, rescore=false,
+    profile=None
+))]
+pub fn segment_prospects<'py>(
+    py: Python<'py>,
+    prospects: Vec<Bound<'py, PyAny>>,
+    thresholds: Option<HashMap<String, f64>>,
+    require_contact: bool,
+    write_tier: bool,
+    rescore: bool,
+    profile: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let (call_now, email_sequence) = tier_thresholds(thresholds.as_ref())?;
+    let profile = resolve_profile(profile, None)?;
+    let formula = current_priority_formula();
+    let keys: Vec<&str> = SCORED_FIELDS.iter().chain(SEGMENT_STORED_FIELDS).copied().collect();
+    let nested: &[(&str, &[&str])] = &[("signals", SCORED_SIGNAL_FIELDS)];
+
+    let mut tiers: Vec<Vec<usize>> = vec![Vec::new(); Tier::ALL.len()];
+    for (i, prospect) in prospects.iter().enumerate() {
+        let fields = prospect_fields(prospect, &keys, nested)?;
+        let contactable = || -> PyResult<bool> {
+            Ok(ProspectInputs::extract(py, &fields, false)?.is_contactable())
+        };
+        let tier = if require_contact && !contactable()? {
+            Tier::Unreachable
+        } else {
+            let priority = RunScores::read(py, &fields, rescore, &profile, &formula)?.priority;
+            if priority >= call_now {
+                Tier::CallNow
+            } else if priority >= email_sequence {
+                Tier::EmailSequence
+            } else {
+                Tier::Nurture
+            }
+        };
+        tiers[tier as usize].push(i);
+        if write_tier {
+            match prospect.downcast::<PyDict>() {
+                Ok(dict) => dict.set_item("tier", tier.as_str())?,
+                Err(_) => prospect.setattr("tier", tier.as_str())?,
+            }
+        }
+    }
+
+    let by_tier = PyDict::new(py);
+    let counts = PyDict::new(py);
+    for (tier, indices) in Tier::ALL.iter().zip(tiers) {
+        counts.set_item(tier.as_str(), indices.len())?;
+        by_tier.set_item(tier.as_str(), indices)?;
+    }
+    counts.set_item("total", prospects.len())?;
+    let cut_offs = PyDict::new(py);
+    cut_offs.set_item("call_now", call_now)?;
+    cut_offs.set_item("email_sequence", email_sequence)?;
+    let out = PyDict::new(py);
+    out.set_item("tiers", by_tier)?;
+    out.set_item("counts", counts)?;
+    out.set_item("thresholds", cut_offs)?;
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Duplicate merging  (prospect/dedup.py merge_prospects)
+// ---------------------------------------------------------------------------
+
+// Name similarity (bigram Dice, 0–1) from which two prospects are one business
+const MERGE_NAME_THRESHOLD: f64 = 0.9;
+
+// Shorter phone numbers are fragments, not worth joining on
+const MERGE_MIN_PHONE_DIGITS: usize = 6;
+
+// Read from non-dict prospects (the Prospect dataclass fields)
+const MERGE_OBJECT_FIELDS: &[&str] = &[
+    "name", "website", "domain", "phone", "address", "found_in_ads", "ad_position",
+    "found_in_maps", "maps_position", "found_in_organic", "organic_position", "rating",
+    "review_count", "category", "emails", "fit_score", "opportunity_score", "priority_score",
+    "opportunity_notes", "source", "scraped_at",
+];
+// Kept from whichever member has the fullest value
+const MERGE_TEXT_FIELDS: &[&str] = &["name", "phone", "address", "category"];
+// Flags set when any member's is, each with the best (lowest) position
+const MERGE_PRESENCE_FIELDS: &[(&str, &str)] = &[
+    ("found_in_ads", "ad_position"),
+    ("found_in_maps", "maps_position"),
+    ("found_in_organic", "organic_position"),
+];
+
+/// Disjoint sets of prospect indices, each rooted at its lowest index, with
+/// the domains each set's members have.
+struct DuplicateSets {
+    parent: Vec<usize>,
+    // Indexed by root; emptied when the root joins another set
+    domains: Vec<BTreeSet<String>>,
+}
+
+impl DuplicateSets {
+    fn new(domains: &[Option<String>]) -> Self {
+        DuplicateSets {
+            parent: (0..domains.len()).collect(),
+            domains: domains.iter().map(|d| d.iter().cloned().collect()).collect(),
+        }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = i;
+        while self.parent[node] != root {
+            node = std::mem::replace(&mut self.parent[node], root);
+        }
+        root
+    }
+
+    fn join(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        let (root, child) = (a.min(b), a.max(b));
+        self.parent[child] = root;
+        let moved = std::mem::take(&mut self.domains[child]);
+        self.domains[root].extend(moved);
+    }
+
+    /// Whether joining the sets of `a` and `b` leaves at most one domain
+    /// among their members, as a name match requires.
+    fn one_domain(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        a == b || self.domains[a].union(&self.domains[b]).nth(1).is_none()
+    }
+
+    /// Each set's indices, ascending, in order of their lowest index.
+    fn groups(mut self) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut slot: HashMap<usize, usize> = HashMap::new();
+        for i in 0..self.parent.len() {
+            let root = self.find(i);
+            let index = *slot.entry(root).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(i);
+        }
+        groups
+    }
+}
+
+/// A prospect's normalized domain (from `domain`, else `website`) unless it
+/// is a directory listing, which many businesses share.
+fn merge_domain(py: Python<'_>, fields: &Fields) -> Option<String> {
+    let website = extract_opt_string(py, fields, "website");
+    let domain = extract_opt_string(py, fields, "domain")
+        .and_then(|d| crate::text::normalize_domain(&d))
+        .or_else(|| website.as_deref().and_then(crate::text::normalize_domain))?;
+    let listed = crate::text::is_directory_url(website.as_deref().unwrap_or(""), &domain);
+    (!listed).then_some(domain)
+}
+
+fn merge_phone(py: Python<'_>, fields: &Fields) -> Option<String> {
+    let phone = crate::text::normalize_phone(&extract_opt_string(py, fields, "phone")?);
+    let digits = phone.chars().filter(char::is_ascii_digit).count();
+    (digits >= MERGE_MIN_PHONE_DIGITS).then_some(phone)
+}
+
+/// Pairs `(i, j)`, `i < j`, of non-empty bigram lists at least `threshold`
+/// similar, ascending.
+///
+/// Only pairs sharing a bigram from the rarest few of either list are
+/// compared (prefix filtering): a list of n bigrams needs at least
+/// n·t/(2−t) of them shared to reach Dice t with any other, so a pair with
+/// none in common among each list's first n − that + 1 can't match.
+fn similar_name_pairs(bigrams: &[Vec<(char, char)>], threshold: f64) -> Vec<(usize, usize)> {
+    // Repeated bigrams are numbered so the lists become sets
+    let tokens: Vec<Vec<((char, char), usize)>> = bigrams
+        .iter()
+        .map(|list| {
+            list.iter()
+                .enumerate()
+                .map(|(k, &bigram)| (bigram, k - list.partition_point(|b| *b < bigram)))
+                .collect()
+        })
+        .collect();
+    let mut frequency: HashMap<((char, char), usize), usize> = HashMap::new();
+    for token in tokens.iter().flatten() {
+        *frequency.entry(*token).or_default() += 1;
+    }
+    let mut index: HashMap<((char, char), usize), Vec<usize>> = HashMap::new();
+    let mut pairs = Vec::new();
+    for (j, list) in tokens.iter().enumerate() {
+        if list.is_empty() {
+            continue;
+        }
+        let mut rarest = list.clone();
+        rarest.sort_by_key(|token| (frequency[token], *token));
+        let n = rarest.len() as f64;
+        // Held back by a hair so rounding never drops a true match
+        let needed = (n * threshold / (2.0 - threshold) - 1e-9).ceil().max(1.0) as usize;
+        let prefix = &rarest[..rarest.len() + 1 - needed.min(rarest.len())];
+        let mut candidates: BTreeSet<usize> = BTreeSet::new();
+        for token in prefix {
+            let seen = index.entry(*token).or_default();
+            candidates.extend(seen.iter().copied());
+            seen.push(j);
+        }
+        for i in candidates {
+            let (a, b) = (&bigrams[i], &bigrams[j]);
+            // Dice can't reach the threshold when the lengths differ this much
+            let (short, long) = (a.len().min(b.len()) as f64, a.len().max(b.len()) as f64);
+            if 2.0 * short / (short + long) < threshold {
+                continue;
+            }
+            if crate::text::bigram_similarity(a, b) >= threshold {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Groups of duplicate prospects: the same domain, the same phone number, or
+/// names at least `name_threshold` similar. A name match never leaves two
+/// domains in one group, so a prospect without a domain joins by name only a
+/// group whose members have none or its one domain (the first such match,
+/// by index).
+fn duplicate_groups(
+    py: Python<'_>,
+    prospects: &[Fields],
+    name_threshold: f64,
+) -> Vec<Vec<usize>> {
+    let domains: Vec<Option<String>> = prospects.iter().map(|p| merge_domain(py, p)).collect();
+    let mut sets = DuplicateSets::new(&domains);
+    let mut first: HashMap<(&str, String), usize> = HashMap::new();
+    for (i, prospect) in prospects.iter().enumerate() {
+        let keys = [("domain", domains[i].clone()), ("phone", merge_phone(py, prospect))];
+        for (kind, key) in keys {
+            let Some(key) = key else { continue };
+            let seen = *first.entry((kind, key)).or_insert(i);
+            sets.join(seen, i);
+        }
+    }
+
+    let bigrams: Vec<Vec<(char, char)>> = prospects
+        .iter()
+        .map(|p| extract_opt_string(py, p, "name").unwrap_or_default())
+        .map(|name| crate::text::name_bigrams(&name))
+        .collect();
+    for (i, j) in similar_name_pairs(&bigrams, name_threshold) {
+        if sets.one_domain(i, j) {
+            sets.join(i, j);
+        }
+    }
+    sets.groups()
+}
+
+/// The longest non-blank `key` among `members` (ties to the smallest), with
+/// the position of the member it came from.
+fn fullest_text(py: Python<'_>, members: &[&Fields], key: &str) -> Option<(usize, String)> {
+    members
+        .iter()
+        .enumerate()
+        .filter_map(|(i, fields)| Some((i, extract_opt_string(py, fields, key)?)))
+        .map(|(i, text)| (i, text.trim().to_string()))
+        .filter(|(_, text)| !text.is_empty())
+        .min_by(|(_, a), (_, b)| b.chars().count().cmp(&a.chars().count()).then_with(|| a.cmp(b)))
+}
+
+/// One prospect from a group of duplicates. Every choice depends only on
+/// the members' values, never their order.
+fn merge_group<'py>(py: Python<'py>, members: &[&Fields]) -> PyResult<Bound<'py, PyDict>> {
+    let text = |fields: &Fields, key: &str| extract_opt_string(py, fields, key).unwrap_or_default();
+    let populated = |fields: &Fields| {
+        fields.values().filter(|v| v.bind(py).is_truthy().unwrap_or(false)).count()
+    };
+    // The most complete record is the base for fields not merged below
+    let primary = members
+        .iter()
+        .min_by(|a, b| {
+            populated(b).cmp(&populated(a)).then_with(|| {
+                let key = |f: &Fields| ["name", "website", "phone", "address"].map(|k| text(f, k));
+                key(a).cmp(&key(b))
+            })
+        })
+        .expect("groups are never empty");
+    // Prospect fields in dataclass order, then any others sorted
+    let mut keys: Vec<&String> = primary.keys().collect();
+    let known = |key: &str| MERGE_OBJECT_FIELDS.iter().position(|k| *k == key);
+    keys.sort_by_key(|key| (known(key).unwrap_or(usize::MAX), key.as_str()));
+    let merged = PyDict::new(py);
+    for key in keys {
+        merged.set_item(key, &primary[key])?;
+    }
+
+    for key in MERGE_TEXT_FIELDS {
+        if let Some((_, value)) = fullest_text(py, members, key) {
+            merged.set_item(key, value)?;
+        }
+    }
+    // The domain and signals come from the same record as the website
+    if let Some((i, website)) = fullest_text(py, members, "website") {
+        let domain = extract_opt_string(py, members[i], "domain")
+            .or_else(|| crate::text::normalize_domain(&website));
+        merged.set_item("website", website)?;
+        merged.set_item("domain", domain)?;
+        if let Some(signals) = members[i].get("signals").filter(|s| !s.is_none(py)) {
+            merged.set_item("signals", signals)?;
+        }
+    }
+
+    let mut emails: BTreeMap<String, String> = BTreeMap::new();
+    for member in members {
+        for email in crate::export::extract_string_list(py, member, "emails") {
+            // Of spellings differing only in case, the smallest
+            let spelling = emails.entry(email.trim().to_lowercase()).or_default();
+            if spelling.is_empty() || email.trim() < spelling.as_str() {
+                *spelling = email.trim().to_string();
+            }
+        }
+    }
+    emails.retain(|key, _| !key.is_empty());
+    merged.set_item("emails", emails.into_values().collect::<Vec<String>>())?;
+
+    for (flag, position) in MERGE_PRESENCE_FIELDS {
+        let found = members.iter().any(|m| {
+            m.get(*flag).is_some_and(|v| v.bind(py).is_truthy().unwrap_or(false))
+        });
+        let best = members
+            .iter()
+            .filter_map(|m| m.get(*position).and_then(|v| v.extract::<i64>(py).ok()))
+            .min();
+        merged.set_item(flag, found)?;
+        merged.set_item(position, best)?;
+    }
+
+    let rating = members
+        .iter()
+        .filter_map(|m| m.get("rating").and_then(|v| v.extract::<f64>(py).ok()))
+        .filter(|r| r.is_finite())
+        .max_by(f64::total_cmp);
+    let review_count = members
+        .iter()
+        .filter_map(|m| m.get("review_count").and_then(|v| v.extract::<i64>(py).ok()))
+        .max();
+    merged.set_item("rating", rating)?;
+    merged.set_item("review_count", review_count)?;
+
+    let mut sources: BTreeSet<String> = BTreeSet::new();
+    for member in members {
+        let source = text(member, "source");
+        let distinct = source.split(',').map(str::trim).filter(|s| !s.is_empty());
+        sources.extend(distinct.map(String::from));
+    }
+    if !sources.is_empty() {
+        merged.set_item("source", sources.into_iter().collect::<Vec<_>>().join(", "))?;
+    }
+
+    // Most recent first; unparseable stamps lose to any parseable one
+    let mut latest: Option<(Option<f64>, String, &PyObject)> = None;
+    for member in members {
+        let Some(value) = member.get("scraped_at").filter(|v| !v.is_none(py)) else { continue };
+        let bound = value.bind(py);
+        let stamp = if bound.is_instance_of::<PyDate>() {
+            bound.call_method0("isoformat")?.extract::<String>()?
+        } else {
+            bound.str()?.to_string()
+        };
+        let candidate = (parse_iso_timestamp(&stamp), stamp, value);
+        let newer = latest.as_ref().is_none_or(|(at, stamp, _)| {
+            let order = match (candidate.0, at) {
+                (Some(a), Some(b)) => a.total_cmp(b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            };
+            order.then_with(|| candidate.1.cmp(stamp)).is_gt()
+        });
+        if newer {
+            latest = Some(candidate);
+        }
+    }
+    if let Some((_, _, value)) = latest {
+        merged.set_item("scraped_at", value)?;
+    }
+    Ok(merged)
+}
+
+// The merged prospects and the input indices behind each
+type MergedProspects<'py> = (Vec<Bound<'py, PyDict>>, Vec<Vec<usize>>);
+
+/// Merge duplicate prospects (dicts or objects) before scoring, so a business
+/// found several times is scored, ranked and contacted once.
+///
+/// Prospects are grouped when they share a normalized domain (directory
+/// listings aside) or phone number, or when their names (after
+/// `normalize_name`) are at least `name_threshold` similar by character
+/// bigrams, 1.0 meaning identical; a name match never joins two prospects
+/// with different domains, even through one that has none. Groups are
+/// transitive.
+///
+/// Each group becomes one dict, starting from its most complete record:
+/// emails are unioned, `found_in_*` set if any member's is (with the best
+/// position), website (with its domain and signals), name, phone, address
+/// and category taken from whichever member has the longest value, rating
+/// and review_count the highest, `source` the distinct sources sorted and
+/// joined with ", ", and `scraped_at` the most recent. The result doesn't
+/// depend on the order of prospects within a group.
+///
+/// Returns `(merged, groups)`: the merged prospects and, for each, the
+/// indices of the input prospects it came from, ascending; both in order of
+/// each group's first prospect.
+#[pyfunction]
+#[pyo3(signature = (prospects, name_threshold=MERGE_NAME_THRESHOLD))]
+pub fn merge_duplicate_prospects<'py>(
+    py: Python<'py>,
+    prospects: Vec<Bound<'py, PyAny>>,
+    name_threshold: f64,
+) -> PyResult<MergedProspects<'py>> {
+    if !(name_threshold > 0.0 && name_threshold <= 1.0) {
+        return Err(PyValueError::new_err(format!(
+            "name_threshold must be above 0 and at most 1, got {name_threshold}"
+        )));
+    }
+    let signal_keys: Vec<&str> =
+        SCORED_SIGNAL_FIELDS.iter().chain(DELTA_CARRIED_SIGNAL_FIELDS).copied().collect();
+    let nested: &[(&str, &[&str])] = &[("signals", &signal_keys)];
+    let prospects = prospects
+        .iter()
+        .map(|p| prospect_fields(p, MERGE_OBJECT_FIELDS, nested))
+        .collect::<PyResult<Vec<Fields>>>()?;
+
+    let groups = duplicate_groups(py, &prospects, name_threshold);
+    let merged = groups
+        .iter()
+        .map(|group| {
+            let members: Vec<&Fields> = group.iter().map(|&i| &prospects[i]).collect();
+            merge_group(py, &members)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok((merged, groups))
+}
+
+// ---------------------------------------------------------------------------
+// Incremental scoring
+// ---------------------------------------------------------------------------
+
+// Cached (fit, opportunity, priority) by content hash
+type ScoreCache = HashMap<String, (u32, u32, f64)>;
+
+/// What a cached score was computed under: the resolved profile and the
+/// priority formula.
+fn scoring_fingerprint(profile: &ScoringProfile, formula: &PriorityFormula) -> String {
+    format!("{profile:?}|{formula:?}")
+}
+
+/// xxh3 of the scoring inputs as the scorers read them and the
+/// `scoring_fingerprint`, so only changes that can move a score change the
+/// hash.
+fn content_hash(inputs: &ProspectInputs, fingerprint: &str) -> PyResult<String> {
+    let mut canonical = serde_json::to_vec(inputs)
+        .map_err(|e| PyValueError::new_err(format!("can't hash prospect: {e}")))?;
+    canonical.extend_from_slice(fingerprint.as_bytes());
+    Ok(format!("{:016x}", xxh3_64(&canonical)))
+}
+
+/// Hex xxh3 digest of what a prospect's (dict or object) scores depend on:
+/// the scored fields and signals, read as the scorers read them (numeric
+/// strings taken as numbers), together with `profile` and the formula from
+/// `set_priority_formula`. Notes, names, addresses, stored scores and other
+/// fields don't affect it.
+#[pyfunction]
+#[pyo3(signature = (prospect, profile=None))]
+pub fn prospect_content_hash(
+    py: Python<'_>,
+    prospect: &Bound<'_, PyAny>,
+    profile: Option<&str>,
+) -> PyResult<String> {
+    let profile = resolve_profile(profile, None)?;
+    let fingerprint = scoring_fingerprint(&profile, &current_priority_formula());
+    let fields = prospect_fields(prospect, SCORED_FIELDS, &[("signals", SCORED_SIGNAL_FIELDS)])?;
+    content_hash(&ProspectInputs::extract(py, &fields, false)?, &fingerprint)
+}
+
+/// Score a batch, reusing cached scores for prospects whose
+/// `prospect_content_hash` under `profile` is in `previous` (hash → `(fit,
+/// opportunity, priority)`, as returned under `"cache"` last time) and
+/// computing the rest under `profile` and the formula from
+/// `set_priority_formula`.
+///
+/// Returns `{"scores": [(fit, opportunity)], "priority": [float],
+/// "hashes": [str], "cache": {hash: (fit, opportunity, priority)}, "hits":
+/// int, "misses": int}`. `"cache"` holds this batch's prospects only, ready
+/// to pass as `previous` next run. The hashes cover the profile and formula,
+/// so after either changes nothing in an older cache matches and every
+/// prospect is rescored.
+#[pyfunction]
+#[pyo3(signature = (prospects, previous=None, profile=None))]
+pub fn score_prospects_incremental<'py>(
+    py: Python<'py>,
+    prospects: Vec<Bound<'py, PyAny>>,
+    previous: Option<ScoreCache>,
+    profile: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let profile = resolve_profile(profile, None)?;
+    let formula = current_priority_formula();
+    let fingerprint = scoring_fingerprint(&profile, &formula);
+    let previous = previous.unwrap_or_default();
+    let nested: &[(&str, &[&str])] = &[("signals", SCORED_SIGNAL_FIELDS)];
+
+    let mut hashes = Vec::with_capacity(prospects.len());
+    let mut cached: Vec<Option<(u32, u32, f64)>> = Vec::with_capacity(prospects.len());
+    let mut changed: Vec<ProspectInputs> = Vec::new();
+    for prospect in &prospects {
+        let fields = prospect_fields(prospect, SCORED_FIELDS, nested)?;
+        let inputs = ProspectInputs::extract(py, &fields, false)?;
+        let hash = content_hash(&inputs, &fingerprint)?;
+        let hit = previous.get(&hash).copied();
+        if hit.is_none() {
+            changed.push(inputs);
+        }
+        cached.push(hit);
+        hashes.push(hash);
+    }
+
+    let misses = changed.len();
+    let mut computed = score_breakdowns(py, &changed, &profile).into_iter();
+    let mut scores = Vec::with_capacity(prospects.len());
+    let mut priorities = Vec::with_capacity(prospects.len());
+    let mut cache = ScoreCache::with_capacity(prospects.len());
+    for (hash, hit) in hashes.iter().zip(cached) {
+        let entry = match hit {
+            Some(entry) => entry,
+            None => {
+                let (fit, opportunity) = computed.next().expect("one breakdown per miss");
+                let (fit, opportunity) = (fit.score(), opportunity.score());
+                (fit, opportunity, formula.apply(fit, opportunity))
+            }
+        };
+        scores.push((entry.0, entry.1));
+        priorities.push(entry.2);
+        cache.insert(hash.clone(), entry);
+    }
+
+    let out = PyDict::new(py);
+    out.set_item("scores", scores)?;
+    out.set_item("priority", priorities)?;
+    out.set_item("hashes", hashes)?;
+    out.set_item("cache", cache)?;
+    out.set_item("hits", prospects.len() - misses)?;
+    out.set_item("misses", misses)?;
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Confidence score
+// ---------------------------------------------------------------------------
+
+// Emails at which the emails component is fully earned
+const CONFIDENCE_FULL_EMAILS: usize = 3;
+
+// Read from non-dict prospects besides the scored fields
+const CONFIDENCE_FIELDS: &[&str] = &["address"];
+
+/// Points per field for `calculate_confidence`, before scaling to 0–100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ConfidenceWeights {
+    website: f64,
+    emails: f64,
+    phone: f64,
+    rating: f64,
+    review_count: f64,
+    signals: f64,
+    address: f64,
+}
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        ConfidenceWeights {
+            website: 20.0,
+            emails: 15.0,
+            phone: 15.0,
+            rating: 10.0,
+            review_count: 10.0,
+            signals: 20.0,
+            address: 10.0,
+        }
+    }
+}
+
+impl ConfidenceWeights {
+    const NAMES: &'static [&'static str] =
+        &["website", "emails", "phone", "rating", "review_count", "signals", "address"];
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut f64> {
+        Some(match name {
+            "website" => &mut self.website,
+            "emails" => &mut self.emails,
+            "phone" => &mut self.phone,
+            "rating" => &mut self.rating,
+            "review_count" => &mut self.review_count,
+            "signals" => &mut self.signals,
+            "address" => &mut self.address,
+            _ => return None,
+        })
+    }
+
+    /// The defaults overridden by `overrides`; unknown names, negative or
+    /// non-finite weights, and an all-zero set raise ValueError.
+    fn with_overrides(overrides: Option<&HashMap<String, f64>>) -> PyResult<Self> {
+        let mut weights = ConfidenceWeights::default();
+        for (name, &value) in overrides.into_iter().flatten() {
+            let Some(slot) = weights.get_mut(name) else {
+                return Err(PyValueError::new_err(format!(
+                    "unknown confidence field {name:?}; valid fields: {}",
+                    ConfidenceWeights::NAMES.join(", ")
+                )));
+            };
+            if !value.is_finite() || value < 0.0 {
+                return Err(PyValueError::new_err(format!(
+                    "confidence weight {name:?} must be a non-negative number, got {value}"
+                )));
+            }
+            *slot = value;
+        }
+        let total: f64 = ConfidenceWeights::NAMES
+            .iter()
+            .filter_map(|name| weights.get_mut(name).copied())
+            .sum();
+        if total == 0.0 {
+            return Err(PyValueError::new_err("at least one confidence weight must be non-zero"));
+        }
+        Ok(weights)
+    }
+}
+
+/// Which fields a prospect has, the only thing confidence looks at.
+#[derive(Debug, Clone, Copy, Default)]
+struct Coverage {
+    website: bool,
+    emails: usize,
+    phone: bool,
+    rating: bool,
+    review_count: bool,
+    signals: bool,
+    address: bool,
+}
+
+impl Coverage {
+    fn read(py: Python<'_>, fields: &Fields) -> Self {
+        let text = |key: &str| {
+            extract_opt_string(py, fields, key).is_some_and(|value| !value.trim().is_empty())
+        };
+        let number = |key: &str| {
+            fields.get(key).is_some_and(|v| !v.is_none(py) && v.extract::<f64>(py).is_ok())
+        };
+        let signals = fields.get("signals").and_then(|s| s.bind(py).downcast::<PyDict>().ok());
+        // Emails on the prospect and any found on its site, counted once
+        let mut emails: HashSet<String> = HashSet::new();
+        let mut add_emails = |map: &Fields| {
+            let listed = crate::export::extract_string_list(py, map, "emails");
+            emails.extend(listed.iter().map(|e| e.trim().to_lowercase()).filter(|e| !e.is_empty()));
+        };
+        add_emails(fields);
+        if let Some(site) = signals.and_then(|s| s.extract::<Fields>().ok()) {
+            add_emails(&site);
+        }
+        Coverage {
+            website: text("website"),
+            emails: emails.len(),
+            phone: text("phone"),
+            rating: number("rating"),
+            review_count: number("review_count"),
+            signals: signals.is_some_and(|s| !s.is_empty()),
+            address: text("address"),
+        }
+    }
+}
+
+/// Confidence components, each the share of its weight earned scaled so a
+/// fully covered prospect totals 100.
+fn confidence_breakdown(coverage: &Coverage, weights: &ConfidenceWeights) -> ScoreBreakdown {
+    let mut weights = *weights;
+    let total: f64 = ConfidenceWeights::NAMES
+        .iter()
+        .filter_map(|name| weights.get_mut(name).copied())
+        .sum();
+    let scale = 100.0 / total;
+    let mut b = ScoreBreakdown::default();
+    let mut add = |name: &'static str, input, earned: f64, weight: f64| {
+        b.add(name, input, earned * weight * scale);
+    };
+    add("website", bool_input(coverage.website), coverage.website as u8 as f64, weights.website);
+    let emails = coverage.emails.min(CONFIDENCE_FULL_EMAILS) as f64;
+    let email_share = emails / CONFIDENCE_FULL_EMAILS as f64;
+    add("emails", Some(ComponentInput::Int(coverage.emails as i64)), email_share, weights.emails);
+    add("phone", bool_input(coverage.phone), coverage.phone as u8 as f64, weights.phone);
+    add("rating", bool_input(coverage.rating), coverage.rating as u8 as f64, weights.rating);
+    let reviews = coverage.review_count;
+    add("review_count", bool_input(reviews), reviews as u8 as f64, weights.review_count);
+    add("signals", bool_input(coverage.signals), coverage.signals as u8 as f64, weights.signals);
+    add("address", bool_input(coverage.address), coverage.address as u8 as f64, weights.address);
+    b
+}
+
+/// How complete a prospect's (dict or object) data is, 0–100, independent of
+/// its fit and opportunity scores. Each field earns its weight when present:
+/// `website`, `phone`, `address` (non-blank), `rating`, `review_count`,
+/// `signals` (a non-empty dict, i.e. the site was analyzed) and `emails`
+/// (a third of the weight per distinct address, across `emails` and
+/// `signals.emails`, up to 3). The earned points are scaled by the total
+/// weight, so the score stays 0–100 whatever the weights.
+///
+/// `weights` overrides the default points by field name: website 20,
+/// emails 15, phone 15, rating 10, review_count 10, signals 20, address 10.
+#[pyfunction]
+#[pyo3(signature = (prospect, weights=None))]
+pub fn calculate_confidence(
+    py: Python<'_>,
+    prospect: &Bound<'_, PyAny>,
+    weights: Option<HashMap<String, f64>>,
+) -> PyResult<i64> {
+    let weights = ConfidenceWeights::with_overrides(weights.as_ref())?;
+    let keys: Vec<&str> = SCORED_FIELDS.iter().chain(CONFIDENCE_FIELDS).copied().collect();
+    let fields = prospect_fields(prospect, &keys, &[("signals", SCORED_SIGNAL_FIELDS)])?;
+    Ok(confidence_breakdown(&Coverage::read(py, &fields), &weights).score() as i64)
+}
+
+// ---------------------------------------------------------------------------
+// Score statistics
+// ---------------------------------------------------------------------------
+
+// Histogram bins used by `score_statistics` unless `bins` says otherwise
+const DEFAULT_HISTOGRAM_BINS: usize = 10;
+
+/// How `flag_outliers` decides a score is anomalous.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutlierMethod {
+    /// Beyond `k` interquartile ranges outside the quartiles.
+    Iqr,
+    /// More than `k` standard deviations from the mean.
+    ZScore,
+}
+
+impl OutlierMethod {
+    fn parse(method: &str) -> PyResult<Self> {
+        match method {
+            "iqr" => Ok(OutlierMethod::Iqr),
+            "zscore" => Ok(OutlierMethod::ZScore),
+            other => Err(PyValueError::new_err(format!(
+                "unknown outlier method {other:?}; expected \"iqr\" or \"zscore\""
+            ))),
+        }
+    }
+
+    /// Tukey's fences for IQR, three sigma for z-scores.
+    fn default_k(self) -> f64 {
+        match self {
+            OutlierMethod::Iqr => 1.5,
+            OutlierMethod::ZScore => 3.0,
+        }
+    }
+}
+
+/// Equal-width bins from the lowest to the highest score; `edges` has one
+/// more entry than `counts`.
+#[derive(Debug, Clone, Default, IntoPyObject)]
+struct Histogram {
+    edges: Vec<f64>,
+    counts: Vec<usize>,
+}
+
+/// The summary `score_statistics` returns; None and empty lists for no
+/// scores.
+#[derive(Debug, Clone, Default, IntoPyObject)]
+struct ScoreStatistics {
+    count: usize,
+    mean: Option<f64>,
+    median: Option<f64>,
+    std: Option<f64>,
+    min: Option<f64>,
+    max: Option<f64>,
+    deciles: Vec<f64>,
+    histogram: Histogram,
+}
+
+fn check_finite(scores: &[f64]) -> PyResult<()> {
+    match scores.iter().position(|s| !s.is_finite()) {
+        Some(i) => Err(PyValueError::new_err(format!(
+            "scores must be finite numbers, got {} at index {i}",
+            scores[i]
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Mean and population standard deviation, as for `rank_prospects` "zscore".
+fn mean_std(scores: &[f64]) -> (f64, f64) {
+    let n = scores.len() as f64;
+    let mean = scores.iter().sum::<f64>() / n;
+    let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+fn histogram(sorted: &[f64], bins: usize) -> Histogram {
+    let (mut low, mut high) = (sorted[0], sorted[sorted.len() - 1]);
+    if low == high {
+        // A single value sits in the middle of a unit-wide range, as NumPy does
+        low -= 0.5;
+        high += 0.5;
+    }
+    let width = (high - low) / bins as f64;
+    let edges = (0..=bins).map(|i| low + width * i as f64).collect();
+    let mut counts = vec![0; bins];
+    for &score in sorted {
+        // The last bin includes its right edge
+        let bin = (((score - low) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    Histogram { edges, counts }
+}
+
+fn score_stats(scores: &[f64], bins: usize) -> ScoreStatistics {
+    if scores.is_empty() {
+        return ScoreStatistics::default();
+    }
+    let mut sorted = scores.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let (mean, std) = mean_std(scores);
+    ScoreStatistics {
+        count: scores.len(),
+        mean: Some(mean),
+        median: Some(quantile(&sorted, 0.5)),
+        std: Some(std),
+        min: Some(sorted[0]),
+        max: Some(sorted[sorted.len() - 1]),
+        deciles: (1..10).map(|d| quantile(&sorted, d as f64 / 10.0)).collect(),
+        histogram: histogram(&sorted, bins),
+    }
+}
+
+/// A distribution summary for sanity-checking a scoring run: `count`,
+/// `mean`, `median`, `std` (population), `min`, `max`, `deciles` (the 10th
+/// to 90th percentiles, interpolated as NumPy does) and `histogram`,
+/// `{"edges": [...], "counts": [...]}` over `bins` (default 10) equal-width
+/// bins from min to max, the last including max. No scores gives a count of
+/// 0, None for the single values and empty lists.
+#[pyfunction]
+#[pyo3(signature = (scores, bins=DEFAULT_HISTOGRAM_BINS))]
+pub fn score_statistics(py: Python<'_>, scores: Vec<f64>, bins: usize) -> PyResult<PyObject> {
+    if bins == 0 {
+        return Err(PyValueError::new_err("bins must be at least 1"));
+    }
+    check_finite(&scores)?;
+    Ok(score_stats(&scores, bins).into_pyobject(py)?.into_any().unbind())
+}
+
+/// Indices (ascending) of anomalously high or low scores. `method="iqr"`
+/// flags scores more than `k` (default 1.5) interquartile ranges below the
+/// first or above the third quartile; `"zscore"` flags scores more than `k`
+/// (default 3.0) standard deviations from the mean. Nothing is flagged when
+/// every score is equal.
+#[pyfunction]
+#[pyo3(signature = (scores, method="iqr", k=None))]
+pub fn flag_outliers(scores: Vec<f64>, method: &str, k: Option<f64>) -> PyResult<Vec<usize>> {
+    let method = OutlierMethod::parse(method)?;
+    let k = k.unwrap_or_else(|| method.default_k());
+    if !k.is_finite() || k < 0.0 {
+        return Err(PyValueError::new_err(format!("k must be a non-negative number, got {k}")));
+    }
+    check_finite(&scores)?;
+    if scores.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (low, high) = match method {
+        OutlierMethod::Iqr => {
+            let mut sorted = scores.clone();
+            sorted.sort_by(f64::total_cmp);
+            let (q1, q3) = (quantile(&sorted, 0.25), quantile(&sorted, 0.75));
+            (q1 - k * (q3 - q1), q3 + k * (q3 - q1))
+        }
+        OutlierMethod::ZScore => {
+            let (mean, std) = mean_std(&scores);
+            (mean - k * std, mean + k * std)
+        }
+    };
+    Ok((0..scores.len()).filter(|&i| scores[i] < low || scores[i] > high).collect())
+}
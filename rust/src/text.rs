@@ -1,6 +1,6 @@
 use pyo3::prelude::*;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 use url::Url;
 
@@ -139,6 +139,23 @@ static GENERIC_EMAIL_PROVIDERS: &[&str] = &[
     "internode.on.net",
 ];
 
+// Public suffixes with two labels, so registrable domains keep three
+static MULTI_PART_SUFFIXES: &[&str] = &[
+    "com.au", "net.au", "org.au", "edu.au", "gov.au", "asn.au", "id.au",
+    "co.nz", "net.nz", "org.nz", "govt.nz",
+    "co.uk", "org.uk", "me.uk", "ltd.uk", "plc.uk", "gov.uk", "ac.uk",
+    "co.za", "com.sg", "com.my", "co.in", "com.br", "co.jp",
+];
+
+// IANA top-level domains, one per line ('#' lines are comments)
+static KNOWN_TLDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    include_str!("data/tlds.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .collect()
+});
+
 // ---------------------------------------------------------------------------
 // Lazy-compiled regexes
 // ---------------------------------------------------------------------------
@@ -231,13 +248,34 @@ pub fn normalize_domain(raw_url: &str) -> Option<String> {
     if !domain.contains('.') || domain.len() < 4 {
         return None;
     }
-    if domain.contains(|c: char| matches!(c, ' ' | '<' | '>' | '"' | '\'' | ';')) {
+    if domain.contains([' ', '<', '>', '"', '\'', ';']) {
         return None;
     }
 
     Some(domain)
 }
 
+/// Registrable part of a host name ("shop.joes.com.au" → "joes.com.au").
+pub(crate) fn registrable_domain(domain: &str) -> String {
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() <= 2 {
+        return domain;
+    }
+    let last_two = labels[labels.len() - 2..].join(".");
+    let suffix_labels = if MULTI_PART_SUFFIXES.contains(&last_two.as_str()) { 2 } else { 1 };
+    labels[labels.len().saturating_sub(suffix_labels + 1)..].join(".")
+}
+
+/// Whether the last label of `domain` is a delegated top-level domain.
+pub(crate) fn has_known_tld(domain: &str) -> bool {
+    domain
+        .trim_end_matches('.')
+        .rsplit('.')
+        .next()
+        .is_some_and(|tld| KNOWN_TLDS.contains(tld.to_lowercase().as_str()))
+}
+
 #[pyfunction]
 pub fn normalize_name(name: &str) -> String {
     if name.is_empty() {
@@ -258,6 +296,35 @@ pub fn normalize_name(name: &str) -> String {
     normalized.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
+/// Sorted character bigrams of a name after `normalize_name`, spaces
+/// dropped, for `bigram_similarity`.
+pub(crate) fn name_bigrams(name: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = normalize_name(name).chars().filter(|c| !c.is_whitespace()).collect();
+    let mut bigrams: Vec<(char, char)> = chars.windows(2).map(|w| (w[0], w[1])).collect();
+    bigrams.sort_unstable();
+    bigrams
+}
+
+/// Sørensen–Dice coefficient (0–1) of two sorted bigram lists.
+pub(crate) fn bigram_similarity(a: &[(char, char)], b: &[(char, char)]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
 #[pyfunction]
 pub fn clean_business_name(name: &str) -> String {
     if name.is_empty() {
@@ -423,3 +490,108 @@ pub fn filter_emails_for_domain(emails: Vec<String>, website_domain: &str) -> Ve
         .filter(|email| validate_email_domain(email, website_domain).0)
         .collect()
 }
+
+// ---------------------------------------------------------------------------
+// Language detection (character trigrams + script ranges)
+// ---------------------------------------------------------------------------
+
+// Most frequent trigrams per language, highest first (spaces mark word edges).
+static LANGUAGE_TRIGRAMS: &[(&str, &[&str])] = &[
+    ("en", &[" th", "the", "he ", "ing", "nd ", "and", " an", "ng ", " of", "of ", " to", "ion",
+        "ed ", "to ", "tio", "ent", "er ", " in", "is ", "at ", "for", " fo", "or ", "re ",
+        "hat", "on ", "ati", "you", " yo", "ou "]),
+    ("de", &["en ", "er ", "ch ", "der", "die", "ie ", "ich", "sch", "ein", "che", " di", " de",
+        "nd ", "und", "cht", " un", "gen", "ung", "te ", "ine", " ei", "es ", "den", "ten",
+        "ver", " ge", " ve", "ier", "n d", "ist"]),
+    ("fr", &["es ", " de", "de ", "le ", "ent", " le", "nt ", "la ", " la", "s d", "ion", "re ",
+        "e d", " et", "et ", "les", "on ", " pa", "tio", "que", "ns ", " qu", "our", "ue ",
+        "des", " po", " co", "e l", "ans", "ous"]),
+    ("es", &["de ", " de", "os ", " la", "la ", "el ", "es ", " el", "ión", "ent", "en ", " en",
+        "as ", "que", " qu", "ue ", "ado", "con", " co", "nte", "ar ", "del", "o d", " lo",
+        "los", "s d", "a d", "ón ", "ra ", "ció"]),
+    ("it", &["di ", " di", "la ", "re ", " la", "to ", "che", " co", "ent", "one", "ell", "del",
+        "zio", "ion", "ne ", "le ", " de", " il", "il ", " ch", "per", " pe", "nto", "lla",
+        "o d", "e d", "ata", " in", "tà ", "ato"]),
+    ("pt", &["de ", " de", "os ", "ão ", " qu", "que", "do ", " do", "ent", "ção", "da ", " da",
+        "es ", " co", "ar ", "ue ", "em ", " em", "com", "ões", "a d", "o d", "as ", " se",
+        "nte", "ra ", " pa", "ara", "se ", "não"]),
+    ("nl", &["en ", "de ", " de", "an ", "et ", "het", " he", "van", " va", "n d", "er ", "ijk",
+        " ee", "een", "oor", "ing", " in", "ver", "aar", "ie ", " en", "nd ", "in ", "te ",
+        "eer", "den", "ten", " op", "op ", "ij "]),
+];
+
+/// Languages identified by writing system alone: (code, char ranges).
+static SCRIPT_LANGUAGES: &[(&str, &[(u32, u32)])] = &[
+    ("ja", &[(0x3040, 0x30FF)]),
+    ("ko", &[(0xAC00, 0xD7AF), (0x1100, 0x11FF)]),
+    ("zh", &[(0x4E00, 0x9FFF)]),
+    ("ru", &[(0x0400, 0x04FF)]),
+    ("ar", &[(0x0600, 0x06FF)]),
+    ("el", &[(0x0370, 0x03FF)]),
+    ("he", &[(0x0590, 0x05FF)]),
+    ("th", &[(0x0E00, 0x0E7F)]),
+];
+
+const MIN_DETECTION_LETTERS: usize = 40;
+
+/// Guess the language of a block of text.
+///
+/// Non-Latin scripts are identified by character ranges (kana wins over
+/// shared CJK ideographs); Latin-script text is scored against per-language
+/// trigram profiles. Returns (ISO 639-1 code, confidence 0–1), or None when
+/// there is too little text to judge.
+pub(crate) fn detect_language(text: &str) -> Option<(&'static str, f64)> {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() < MIN_DETECTION_LETTERS {
+        return None;
+    }
+
+    for (lang, ranges) in SCRIPT_LANGUAGES {
+        let in_script = letters
+            .iter()
+            .filter(|c| ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&(**c as u32))))
+            .count();
+        // Japanese text mixes kana with kanji, so a smaller kana share suffices
+        let share = in_script as f64 / letters.len() as f64;
+        let threshold = if *lang == "ja" { 0.1 } else { 0.3 };
+        if share >= threshold {
+            return Some((lang, share.min(1.0)));
+        }
+    }
+
+    let normalized: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphabetic() { c } else { ' ' })
+        .collect();
+    let padded = format!(" {} ", normalized.split_whitespace().collect::<Vec<_>>().join(" "));
+    let chars: Vec<char> = padded.chars().collect();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for window in chars.windows(3) {
+        *counts.entry(window.iter().collect()).or_insert(0) += 1;
+    }
+
+    let mut scores: Vec<(&'static str, f64)> = LANGUAGE_TRIGRAMS
+        .iter()
+        .map(|(lang, trigrams)| {
+            let score = trigrams
+                .iter()
+                .enumerate()
+                .map(|(rank, tri)| {
+                    let weight = (trigrams.len() - rank) as f64;
+                    counts.get(*tri).copied().unwrap_or(0) as f64 * weight
+                })
+                .sum::<f64>();
+            (*lang, score)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let (best_lang, best) = scores[0];
+    if best <= 0.0 {
+        return None;
+    }
+    let runner_up = scores.get(1).map(|s| s.1).unwrap_or(0.0);
+    Some((best_lang, ((best - runner_up) / best).clamp(0.0, 1.0)))
+}
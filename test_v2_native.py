@@ -2,9 +2,15 @@
 
 import csv
 import io
+import json
 import math
+import os
 import hashlib
+import random
 import time
+from dataclasses import dataclass, field
+from datetime import datetime
+from typing import Optional
 
 from _leadswarm_native import (
     serialize_prospects_csv,
@@ -13,8 +19,55 @@ from _leadswarm_native import (
     haversine_distance,
     batch_haversine,
     extract_html_metadata,
+    extract_html_metadata_batch,
+    add_social_domains,
+    get_social_domains,
     normalize_domain,
     extract_emails,
+    extract_phones,
+    detect_error_page,
+    analyze_tech_stack,
+    analyze_responsive,
+    detect_wordpress_details,
+    detect_hosting_hints,
+    seo_audit,
+    content_metrics,
+    detect_forms,
+    detect_booking_system,
+    detect_booking_vendor,
+    detect_tracking,
+    detect_mixed_content,
+    detect_copyright_year,
+    detect_site_builder,
+    detect_page_builder,
+    calculate_fit_score,
+    calculate_opportunity_score,
+    explain_fit_score,
+    explain_opportunity_score,
+    score_prospects_batch,
+    rank_prospects,
+    grade_prospects,
+    register_scoring_profile,
+    get_default_fit_weights,
+    get_default_opportunity_weights,
+    set_priority_formula,
+    get_priority_formula,
+    calculate_priority,
+    generate_opportunity_notes,
+    top_prospects,
+    score_deltas,
+    compare_weightings,
+    reputation_signal,
+    load_scoring_config,
+    dump_scoring_config,
+    compile_rules,
+    segment_prospects,
+    merge_duplicate_prospects,
+    prospect_content_hash,
+    score_prospects_incremental,
+    calculate_confidence,
+    score_statistics,
+    flag_outliers,
 )
 
 
@@ -173,10 +226,1428 @@ test("Facebook in links", any("facebook.com" in l for l in meta["social_links"])
 test("Instagram in links", any("instagram.com" in l for l in meta["social_links"]))
 test("LinkedIn in links", any("linkedin.com" in l for l in meta["social_links"]))
 
+aliased = extract_html_metadata(
+    '<a href="https://twitter.com/testplumber">T</a><a href="https://x.com/TestPlumber/">X</a>'
+    '<a href="https://www.dropbox.com/s/abc">Dropbox</a>'
+)
+test("twitter.com and x.com dedupe to one link", aliased["social_links"] == ["https://x.com/testplumber"])
+
+add_social_domains(["https://www.yelp.com.au/"])
+test("Added social domain listed", "yelp.com.au" in get_social_domains())
+yelp = extract_html_metadata('<a href="https://www.yelp.com.au/biz/test-plumber">Yelp</a>')
+test("Added social domain matched", len(yelp["social_links"]) == 1)
+
+batch_meta = extract_html_metadata_batch([("good", html), ("huge", "x" * (51 * 1024 * 1024))])
+test("Batch keyed by page key", set(batch_meta) == {"good", "huge"})
+test("Batch matches single call", batch_meta["good"] == meta)
+test("Oversized page gets error marker", "error" in batch_meta["huge"])
+
+svg_sprite = extract_html_metadata(
+    '<html><body><svg><symbol id="menu"><title>Menu icon</title></symbol></svg>'
+    '<title>Joe\'s Plumbing</title></body></html>'
+)
+test("SVG <title> ignored before the real title", svg_sprite["title"] == "Joe's Plumbing")
+svg_head = extract_html_metadata(
+    '<html><head><title>Real Title</title></head>'
+    '<body><svg><title>Logo</title></svg></body></html>'
+)
+test("Head title wins over a body SVG title", svg_head["title"] == "Real Title")
+body_title = extract_html_metadata("<html><body><p>Hi</p><title>Body Title</title></body></html>")
+test("Title only in the body is found", body_title["title"] == "Body Title")
+
 empty_meta = extract_html_metadata("")
 test("Empty HTML title is None", empty_meta["title"] is None)
 test("Empty HTML social_links is []", empty_meta["social_links"] == [])
 
+print("\n[extract_emails fixtures]")
+fixtures_dir = os.path.join(os.path.dirname(os.path.abspath(__file__)), "tests", "fixtures")
+with open(os.path.join(fixtures_dir, "email_extraction.json")) as f:
+    email_cases = json.load(f)["cases"]
+for case in email_cases:
+    test(f"Emails: {case['case']}", extract_emails(case["html"]) == case["expected"])
+
+print("\n[extract_phones fixtures]")
+with open(os.path.join(fixtures_dir, "phone_extraction.json")) as f:
+    phone_cases = json.load(f)["cases"]
+found_by_format = {}
+for case in phone_cases:
+    got, want = set(extract_phones(case["html"])), set(case["phones"])
+    hits, found, labelled = found_by_format.get(case["format"], (0, 0, 0))
+    found_by_format[case["format"]] = (hits + len(got & want), found + len(got), labelled + len(want))
+true_hits = sum(hits for hits, _, _ in found_by_format.values())
+phone_precision = true_hits / sum(found for _, found, _ in found_by_format.values())
+phone_recall = true_hits / sum(labelled for _, _, labelled in found_by_format.values())
+print(f"  {len(phone_cases)} pages: precision {phone_precision:.2f}, recall {phone_recall:.2f}")
+for phone_format in ["spaces", "dots", "brackets", "+61"]:
+    hits, _, labelled = found_by_format[phone_format]
+    test(f"Phone recall for {phone_format} formats", hits == labelled)
+test("Phone precision at least 0.95", phone_precision >= 0.95)
+test("No phones from ABNs, prices or embedded digits", found_by_format["none"][1] == 0)
+ranked_case = next(c for c in phone_cases if c["case"] == "tel link ranks first")
+test("tel: links rank first", extract_phones(ranked_case["html"]) == ranked_case["phones"])
+
+print("\n[detect_error_page fixtures]")
+def fixture_html(name):
+    with open(os.path.join(fixtures_dir, name), encoding="utf-8") as f:
+        return f.read()
+challenge_html = fixture_html("cloudflare_challenge.html")
+mention_html = fixture_html("cloudflare_mention.html")
+test("Captured Cloudflare challenge detected", detect_error_page(challenge_html) == "challenge")
+challenge_report = analyze_tech_stack(challenge_html)
+test("Challenge page signals suppressed",
+     challenge_report["page_status"] == "challenge" and challenge_report["cms"] is None)
+test("Page mentioning Cloudflare not flagged", detect_error_page(mention_html) is None)
+test("Page mentioning Cloudflare analysed as usual", analyze_tech_stack(mention_html)["page_status"] == "ok")
+
+print("\n[analyze_responsive fixtures]")
+bootstrap2 = analyze_responsive(fixture_html("bootstrap2_fixed_960.html"))
+test("Bootstrap 2 fixed-960px page passed the old check", bootstrap2["is_responsive"])
+test("Bootstrap 2 fixed-960px page scores low",
+     bootstrap2["has_fixed_width_layout"] and bootstrap2["responsive_score"] <= 30)
+fluid = analyze_responsive(
+    '<html><head><meta name="viewport" content="width=device-width, initial-scale=1">'
+    '<style>.container { max-width: 1140px; } @media (max-width: 768px) { .nav { display: none; } }</style>'
+    '</head><body><div class="container"><img src="a.jpg" srcset="a-480.jpg 480w, a-960.jpg 960w"></div></body></html>'
+)
+test("Fluid layout scores high", not fluid["has_fixed_width_layout"] and fluid["responsive_score"] == 100)
+
+print("\n[detect_wordpress_details fixtures]")
+elementor_site = detect_wordpress_details(fixture_html("wordpress_elementor.html"))
+test("Elementor site theme slug", elementor_site["is_wordpress"] and elementor_site["theme"] == "hello-elementor")
+test("Elementor site plugins in first-seen order",
+     elementor_site["plugins"] == ["contact-form-7", "elementor", "elementor-pro", "header-footer-elementor"])
+test("Elementor site uses a page builder, not a default theme",
+     elementor_site["has_page_builder"] and not elementor_site["uses_default_theme"])
+
+print("\n[detect_hosting_hints provider table]")
+# One row per HOSTING_SIGNATURES row in html.rs: (provider, asset host suffix, kind)
+hosting_rows = [
+    ("WP Engine", "wpengine.com", "host"),
+    ("WP Engine", "wpenginepowered.com", "host"),
+    ("Kinsta", "kinsta.cloud", "host"),
+    ("Shopify", "cdn.shopify.com", "host"),
+    ("Netlify", "netlify.app", "host"),
+    ("Vercel", "vercel.app", "host"),
+    ("Squarespace", "squarespace-cdn.com", "host"),
+    ("Wix", "wixstatic.com", "host"),
+    ("Amazon CloudFront", "cloudfront.net", "cdn"),
+    ("BunnyCDN", "b-cdn.net", "cdn"),
+]
+for provider, suffix, kind in hosting_rows:
+    for host in [suffix, "static." + suffix]:
+        hints = detect_hosting_hints(
+            '<link rel="canonical" href="https://www.example-site.com.au/">'
+            f'<script src="https://{host}/assets/app.js"></script>'
+        )
+        detected = hints["hosting_hint"] == provider if kind == "host" else provider in hints["cdn_providers"]
+        test(f"Hosting: {provider} from {host}", detected)
+lookalike = detect_hosting_hints('<script src="https://notwpengine.com/app.js"></script>')
+test("Hosting: lookalike host not matched", lookalike["hosting_hint"] is None and lookalike["cdn_providers"] == [])
+
+print("\n[size guards]")
+over_cap = "<p>" + "x" * (50 * 1024 * 1024)
+for name, fn in [("seo_audit", seo_audit), ("content_metrics", content_metrics), ("detect_forms", detect_forms)]:
+    try:
+        fn(over_cap)
+        test(f"{name} rejects input over 50MB", False)
+    except ValueError:
+        test(f"{name} rejects input over 50MB", True)
+del over_cap
+two_paragraphs = "<body><p>" + "word " * 50 + "</p><p>" + "more " * 50 + "</p></body>"
+test("content_metrics reads the whole page by default", content_metrics(two_paragraphs)["word_count"] == 100)
+test("content_metrics stops at max_bytes", content_metrics(two_paragraphs, max_bytes=300)["word_count"] == 50)
+
+print("\n[detect_booking_system]")
+generic_booking = '<a href="/book-online">Book now</a>'
+test("Generic booking link detected", detect_booking_system(generic_booking))
+test("Generic booking link has no vendor", detect_booking_vendor(generic_booking) is None)
+fresha = '<script src="https://widget.fresha.com/widget.js"></script>'
+test("Vendor widget detected", detect_booking_system(fresha))
+test("Vendor reported when generic markers are also present",
+     detect_booking_vendor(generic_booking + fresha)["vendor"] == "Fresha")
+test("Vendor link reported as link",
+     detect_booking_vendor('<a href="https://calendly.com/joe/30min">Book</a>')
+     == {"vendor": "Calendly", "integration": "link"})
+test("Vendor script reported as widget",
+     detect_booking_vendor(
+         '<script src="https://assets.calendly.com/assets/external/widget.js"></script>'
+     ) == {"vendor": "Calendly", "integration": "widget"})
+
+print("\n[detect_tracking]")
+ga_and_pixel = detect_tracking(
+    '<script async src="https://www.googletagmanager.com/gtag/js?id=G-ABC123"></script>'
+    '<script>fbq("init", "123")</script>'
+)
+test("Original tracking keys unchanged",
+     (ga_and_pixel["google_analytics"], ga_and_pixel["facebook_pixel"], ga_and_pixel["google_ads"])
+     == (True, True, False))
+test("New tracking keys reported", {
+    "has_hotjar", "has_clarity", "has_linkedin_insight",
+    "has_tiktok_pixel", "has_pinterest_tag", "has_snap_pixel",
+} <= set(ga_and_pixel))
+test("New tracking keys default to False",
+     not any(v for k, v in ga_and_pixel.items() if k.startswith("has_")))
+test("Hotjar detected",
+     detect_tracking('<script src="https://static.hotjar.com/c/hotjar-123.js"></script>')["has_hotjar"])
+
+print("\n[detect_forms]")
+search_only = detect_forms('<form role="search"><input type="search" name="q"></form>')
+test("Search-only form is not a contact form",
+     (search_only["has_contact_form"], search_only["form_count"]) == (False, 0))
+newsletter = detect_forms(
+    '<form><input type="email" name="email"><button>Subscribe</button></form>'
+)
+test("Newsletter form is not a contact form",
+     not newsletter["has_contact_form"] and newsletter["newsletter_form"])
+contact = detect_forms(
+    '<form><input name="name"><input type="email" name="email"><textarea name="message">'
+    '</textarea></form>'
+)
+test("Form with a message box is a contact form", contact["has_contact_form"])
+
+print("\n[detect_mixed_content]")
+protocol_relative = detect_mixed_content('<img src="//cdn.example.com/logo.png">', "https://joes.com.au")
+test("Protocol-relative URLs are not mixed content",
+     (protocol_relative["blockable_count"], protocol_relative["upgradeable_count"]) == (0, 0))
+mixed = detect_mixed_content(
+    '<img src="http://joes.com.au/a.png"><iframe src="http://maps.example.com"></iframe>'
+    '<a href="http://old.example.com">Old site</a>',
+    "https://joes.com.au",
+)
+test("Passive and active mixed content split",
+     (mixed["upgradeable_examples"], mixed["blockable_examples"])
+     == (["http://joes.com.au/a.png"], ["http://maps.example.com"]))
+test("Plain http page not checked",
+     detect_mixed_content('<img src="http://joes.com.au/a.png">', "http://joes.com.au")["checked"]
+     is False)
+
+print("\n[detect_copyright_year]")
+footer_year = detect_copyright_year(
+    "<main><p>Established 1998</p></main><footer>© 2019 Joe's Plumbing</footer>", 2026
+)
+test("Footer-only copyright year found",
+     footer_year == {"year": 2019, "years_stale": 7, "source": "footer"})
+test("Footer beats a body year", detect_copyright_year(
+    "<main><p>Photo © 2024</p></main><footer>© 2018 Joe's Plumbing</footer>", 2026
+)["year"] == 2018)
+test("Year range uses its end year",
+     detect_copyright_year("<footer>Copyright © 2015–2023 Joe's</footer>", 2026)["year"] == 2023)
+test("Falls back to the whole document without a footer",
+     detect_copyright_year("<body><p>Copyright © 2016 Joe's</p></body>", 2026)["source"]
+     == "document")
+
+print("\n[detect_site_builder / detect_page_builder]")
+wix_elementor = analyze_tech_stack(
+    '<img src="https://static.wixstatic.com/media/logo.png"><div class="elementor-section"></div>'
+)
+test("Site builder from asset host is definite",
+     (wix_elementor["site_builder"], wix_elementor["site_builder_confidence"])
+     == ("Wix", "definite"))
+test("Page builder from class names is probable",
+     (wix_elementor["page_builder"], wix_elementor["page_builder_confidence"])
+     == ("Elementor", "probable"))
+elementor_plugin = analyze_tech_stack(
+    '<link rel="stylesheet" href="/wp-content/plugins/elementor/assets/css/frontend.min.css">'
+)
+test("Page builder from plugin path is definite",
+     elementor_plugin["page_builder_confidence"] == "definite")
+test("GoDaddy builder from wsimg.com",
+     detect_site_builder('<script src="https://img1.wsimg.com/x.js"></script>')
+     == "GoDaddy Website Builder")
+test("Beaver Builder from class names",
+     detect_page_builder('<div class="fl-builder-content"></div>') == "Beaver Builder")
+test("Builder named in body text not matched",
+     detect_site_builder("<p>We love wix.com tips</p>") is None)
+
+# --- Score breakdowns ---
+print("\n[explain_fit_score / explain_opportunity_score]")
+
+twitter = extract_html_metadata(
+    '<meta name="twitter:card" content="summary_large_image">'
+    '<meta property="twitter:site" content="@JoesPlumbing">'
+    '<meta name="twitter:title" content="">'
+    '<meta property="twitter:description" content="Desc">'
+    '<meta name="twitter:image" content="https://x/i.png">'
+)
+test("Twitter card dict", twitter["twitter_card"] == {
+    "card": "summary_large_image", "title": None, "description": "Desc",
+    "image": "https://x/i.png", "site": "@JoesPlumbing",
+})
+test("twitter:site handle strips @", twitter["twitter_handle"] == "JoesPlumbing")
+test("Empty twitter:title content is None", twitter["twitter_card"]["title"] is None)
+
+icons = extract_html_metadata(
+    '<link rel="icon" href="/favicon-32.png" sizes="32x32">'
+    '<link rel="apple-touch-icon" href="/apple.png" sizes="180x180">'
+    '<link rel="shortcut icon" href="data:image/png;base64,AAAA">',
+    base_url="https://joes.com.au/about",
+)
+icon_urls = [icon["url"] for icon in icons["favicons"]]
+test("Relative favicons resolved against base_url", icon_urls[:2] == [
+    "https://joes.com.au/favicon-32.png", "https://joes.com.au/apple.png",
+])
+test("Data-URI favicon flagged", [icon["is_data_uri"] for icon in icons["favicons"]] == [
+    False, False, True,
+])
+test("best_favicon picks the largest icon", icons["best_favicon"] == "https://joes.com.au/apple.png")
+no_icons = "<html><head><title>Joe's</title></head></html>"
+test("best_favicon falls back to /favicon.ico with base_url",
+     extract_html_metadata(no_icons, base_url="https://joes.com.au/about")["best_favicon"]
+     == "https://joes.com.au/favicon.ico")
+test("No /favicon.ico fallback without base_url",
+     extract_html_metadata(no_icons)["best_favicon"] is None)
+
+generators = extract_html_metadata(
+    '<meta name="generator" content="WordPress 6.4.2">'
+    '<meta name="generator" content="Elementor 3.18.0; features: e_dom_optimization">'
+    '<meta name="keywords" content="plumber, brisbane , , hot water">'
+)
+test("First generator kept as generator", generators["generator"] == "WordPress 6.4.2")
+test("Every generator listed", generators["generators"] == [
+    "WordPress 6.4.2", "Elementor 3.18.0; features: e_dom_optimization",
+])
+test("Generator parsed into name and version",
+     generators["generator_parsed"] == {"name": "WordPress", "version": "6.4.2"})
+test("Keywords split, trimmed and blanks dropped",
+     generators["meta_keywords"] == ["plumber", "brisbane", "hot water"])
+
+normalized = extract_html_metadata(
+    '<a href="https://www.facebook.com/JoesPlumbing/?ref=page_internal">Facebook</a>'
+    '<a href="https://m.facebook.com/joesplumbing">Mobile</a>'
+    '<a href="https://facebook.com/joesplumbing/">Slash</a>'
+    '<a href="https://www.facebook.com/sharer.php?u=https://joes.com.au">Share</a>'
+    '<a href="https://twitter.com/intent/tweet?text=hi">Tweet</a>'
+    '<a href="https://instagram.com/joes_plumbing/?utm_source=ig&hl=en">Instagram</a>'
+)
+test("?ref=, www., m. and trailing slash variants collapse to one link",
+     normalized["social_links"] == [
+         "https://facebook.com/JoesPlumbing", "https://instagram.com/joes_plumbing",
+     ])
+test("Share intents excluded",
+     not any("sharer" in l or "intent" in l for l in normalized["social_links"]))
+
+platforms = extract_html_metadata(
+    '<a href="https://notfacebook.com.au/joes">Lookalike</a>'
+    '<a href="https://wa.me/61412345678">WhatsApp</a>'
+    '<a href="https://t.me/joesplumbing">Telegram</a>'
+    '<a href="https://www.facebook.com/joesplumbing">Facebook</a>'
+)
+test("Lookalike domain is not Facebook",
+     not any("notfacebook" in l for l in platforms["social_links"]))
+test("WhatsApp and Telegram are their own platforms", platforms["social_profiles"] == {
+    "whatsapp": ["https://wa.me/61412345678"],
+    "telegram": ["https://t.me/joesplumbing"],
+    "facebook": ["https://facebook.com/joesplumbing"],
+})
+
+time_tag = '<time datetime="2021-01-02">2 Jan</time>'
+og_date = extract_html_metadata(
+    '<meta property="article:published_time" content="2020-05-06T10:00:00Z">' + time_tag
+)
+test("OpenGraph date beats <time>", (og_date["published_at"], og_date["date_source"])
+     == ("2020-05-06T10:00:00Z", "open_graph"))
+ld_date = extract_html_metadata(
+    '<script type="application/ld+json">{"@type": "Article", "datePublished": "2022-03-04",'
+    ' "dateModified": "2023-06-07"}</script>' + time_tag
+)
+test("JSON-LD dates beat <time>",
+     (ld_date["published_at"], ld_date["modified_at"], ld_date["date_source"])
+     == ("2022-03-04", "2023-06-07", "json_ld"))
+test("<time> used when nothing stronger",
+     extract_html_metadata(time_tag)["date_source"] == "time")
+vague_date = extract_html_metadata('<meta name="date" content="sometime last spring">')
+test("Unparseable date kept under date_raw",
+     (vague_date["published_at"], vague_date["date_raw"]) == (None, "sometime last spring"))
+bad_og_date = extract_html_metadata(
+    '<meta property="article:published_time" content="not a date">' + time_tag
+)
+test("Unparseable strongest date kept raw when <time> parses",
+     (bad_og_date["published_at"], bad_og_date["date_raw"]) == ("2021-01-02", "not a date"))
+
+en_au = {"tag": "en-AU", "language": "en", "region": "AU"}
+test("lang attribute normalized",
+     extract_html_metadata('<html lang="en-au"><head></head></html>')["lang"] == en_au)
+locale_only = extract_html_metadata(
+    '<html dir="rtl"><head><meta property="og:locale" content="en_AU"></head></html>'
+)
+test("lang falls back to og:locale", locale_only["lang"] == en_au)
+test("dir attribute read", locale_only["dir"] == "rtl")
+bad_lang = extract_html_metadata('<html lang="english!!"></html>')
+test("Malformed lang kept under lang_raw",
+     (bad_lang["lang"], bad_lang["lang_raw"]) == (None, "english!!"))
+
+test("pwa sub-dict present with empty values", extract_html_metadata("<p>Hi</p>")["pwa"] == {
+    "manifest_url": None, "apple_mobile_web_app_capable": False, "service_worker_hint": False,
+})
+pwa = extract_html_metadata(
+    '<link rel="manifest" href="/site.webmanifest">'
+    '<meta name="apple-mobile-web-app-capable" content="yes">'
+    '<script>navigator.serviceWorker.register("/sw.js")</script>',
+    base_url="https://joes.com.au/about",
+)["pwa"]
+test("pwa manifest resolved and hints set", pwa == {
+    "manifest_url": "https://joes.com.au/site.webmanifest",
+    "apple_mobile_web_app_capable": True,
+    "service_worker_hint": True,
+})
+
+viewports = extract_html_metadata(
+    '<meta name="viewport" content="width=1024">'
+    '<meta name="viewport" content="width=device-width, initial-scale=1, maximum-scale=1">'
+)["viewport"]
+test("Last viewport wins",
+     viewports["content"] == "width=device-width, initial-scale=1, maximum-scale=1")
+test("Viewport parsed", (viewports["width"], viewports["maximum_scale"], viewports["blocks_zoom"])
+     == ("device-width", 1.0, True))
+test("Repeated viewport flagged", viewports["multiple_declared"] is True)
+single_viewport = extract_html_metadata('<meta name="viewport" content="width=device-width">')
+test("Single viewport not flagged", single_viewport["viewport"]["multiple_declared"] is False)
+
+article_links = "".join(
+    f'<a href="https://facebook.com/page{i}/">Shared post</a>' for i in range(5)
+)
+capped = extract_html_metadata(
+    f"<main><article>{article_links}</article></main>"
+    '<footer><a href="https://facebook.com/joesplumbing">Facebook</a>'
+    '<a href="https://instagram.com/joes">Instagram</a></footer>'
+)
+test("Facebook capped at 3 profiles", len(capped["social_profiles"]["facebook"]) == 3)
+test("Footer profile survives the cap",
+     "https://facebook.com/joesplumbing" in capped["social_profiles"]["facebook"])
+test("social_links capped the same way as social_profiles",
+     sorted(capped["social_links"])
+     == sorted(url for urls in capped["social_profiles"].values() for url in urls))
+test("social_links_truncated counts dropped links", capped["social_links_truncated"] == 3)
+capped_two = extract_html_metadata(article_links, max_per_platform=2)
+test("max_per_platform respected",
+     capped_two["social_links"] == ["https://facebook.com/page0", "https://facebook.com/page1"])
+test("Truncation counted with max_per_platform", capped_two["social_links_truncated"] == 3)
+
+
+def random_prospect(rng):
+    maybe = lambda value: value if rng.random() < 0.7 else None
+    prospect = {
+        "website": maybe(f"https://site{rng.randint(0, 999)}.com"),
+        "phone": maybe("0412345678"),
+        "emails": ["info@example.com"] * rng.randint(0, 3),
+        "found_in_maps": rng.random() < 0.5,
+        "maps_position": maybe(rng.randint(1, 20)),
+        "rating": maybe(round(rng.uniform(1, 5), 1)),
+        "review_count": maybe(rng.randint(0, 300)),
+        "found_in_ads": rng.random() < 0.3,
+        "found_in_organic": rng.random() < 0.5,
+        "organic_position": maybe(rng.randint(1, 30)),
+    }
+    if rng.random() < 0.8:
+        prospect["signals"] = {
+            "has_google_analytics": rng.choice([True, False, None]),
+            "has_facebook_pixel": rng.choice([True, False, None]),
+            "has_booking_system": rng.choice([True, False, None]),
+            "emails": ["info@example.com"] * rng.randint(0, 2),
+            "cms": rng.choice(["Wix", "WordPress", None]),
+            "load_time_ms": maybe(rng.randint(200, 6000)),
+            "has_legacy_jquery": rng.choice([True, False, None]),
+            "is_placeholder": rng.random() < 0.05,
+            "legacy_score": maybe(rng.randint(0, 100)),
+            "is_hiring": rng.choice([True, None]),
+            "ab_testing_tools": rng.choice([[], ["VWO"], ["VWO", "Optimizely", "Google Optimize"]]),
+            "digital_maturity": maybe(rng.randint(0, 100)),
+            "estimated_location_count": rng.choice([None, 1, 6]),
+        }
+    return prospect
+
+
+rng = random.Random(177)
+random_prospects = [random_prospect(rng) for _ in range(1000)]
+fit_mismatches = 0
+opportunity_mismatches = 0
+for p in random_prospects:
+    fit_explained = explain_fit_score(p)
+    opportunity_explained = explain_opportunity_score(p)
+    if (round(sum(c["points"] for c in fit_explained["components"])) != fit_explained["total"]
+            or fit_explained["total"] != calculate_fit_score(p)):
+        fit_mismatches += 1
+    if (round(sum(c["points"] for c in opportunity_explained["components"])) != opportunity_explained["total"]
+            or opportunity_explained["total"] != calculate_opportunity_score(p)):
+        opportunity_mismatches += 1
+test("Fit breakdowns sum to the score (1000 prospects)", fit_mismatches == 0)
+test("Opportunity breakdowns sum to the score (1000 prospects)", opportunity_mismatches == 0)
+
+batch_scores = score_prospects_batch(random_prospects)
+test("Batch matches per-item scoring (1000 prospects)", batch_scores == [
+    (calculate_fit_score(p), calculate_opportunity_score(p)) for p in random_prospects
+])
+
+explained_batch = score_prospects_batch(random_prospects[:5], explain=True)
+test("Batch explain returns scores alongside breakdowns",
+     explained_batch["scores"] == score_prospects_batch(random_prospects[:5])
+     and explained_batch["explanations"][0]["fit"] == explain_fit_score(random_prospects[0]))
+
+print("\n[rank_prospects]")
+test("Percentile spans 0-100", rank_prospects([10.0, 30.0, 20.0]) == [0.0, 100.0, 50.0])
+test("Ties share their average percentile", rank_prospects([5.0, 5.0, 9.0, 1.0]) == [50.0, 50.0, 100.0, 0.0])
+test("Batch of one ranks 50.0", rank_prospects([42.0]) == [50.0])
+zscores = rank_prospects([1.0, 2.0, 3.0], method="zscore")
+test("Z-score centres on the mean", zscores[1] == 0.0 and abs(zscores[0] + zscores[2]) < 1e-9)
+test("Z-score of identical scores is 0", rank_prospects([7.0, 7.0], method="zscore") == [0.0, 0.0])
+try:
+    rank_prospects([1.0], method="median")
+    test("Unknown rank method raises ValueError", False)
+except ValueError:
+    test("Unknown rank method raises ValueError", True)
+
+ranked_batch = score_prospects_batch(random_prospects[:50], rank_method="percentile")
+test("Batch percentiles match rank_prospects",
+     ranked_batch["fit_percentile"] == rank_prospects([float(f) for f, _ in ranked_batch["scores"]])
+     and ranked_batch["priority_percentile"] == rank_prospects(ranked_batch["priority"]))
+test("Batch priority weights fit 0.4 and opportunity 0.6",
+     all(abs(pr - (f * 0.4 + o * 0.6)) < 1e-9
+         for pr, (f, o) in zip(ranked_batch["priority"], ranked_batch["scores"])))
+test("Batch z-score columns", "opportunity_zscore" in score_prospects_batch(
+    random_prospects[:5], rank_method="zscore"))
+
+print("\n[grade_prospects]")
+test("Quartile grades within the batch",
+     grade_prospects([10.0, 20.0, 30.0, 40.0, 50.0]) == ["D", "C", "B", "A", "A"])
+test("Fixed thresholds, boundary takes the higher grade",
+     grade_prospects([80.0, 79.9, 60.0, 10.0], [40.0, 80.0, 60.0]) == ["A", "B", "B", "D"])
+test("Empty batch grades to empty", grade_prospects([]) == [])
+try:
+    grade_prospects([1.0], [])
+    test("Empty thresholds raise ValueError", False)
+except ValueError:
+    test("Empty thresholds raise ValueError", True)
+graded_batch = score_prospects_batch(random_prospects[:20], grade=True)
+test("Batch grades follow priority", graded_batch["grade"] == grade_prospects(graded_batch["priority"]))
+test("Batch grade thresholds", set(score_prospects_batch(
+    random_prospects[:20], grade_thresholds=[0.0])["grade"]) == {"A"})
+graded_csv = serialize_prospects_csv([dict(prospects[0], grade="A")])
+test("CSV grade column when graded",
+     graded_csv.splitlines()[0].endswith(",grade") and graded_csv.splitlines()[1].endswith(",A"))
+test("CSV has no grade column otherwise", "grade" not in serialize_prospects_csv(prospects).splitlines()[0])
+
+print("\n[fit weight validation]")
+weighted_site = {"website": "https://x.example", "phone": "02 9999 1234", "found_in_maps": True}
+default_fit_weights = get_default_fit_weights()
+def fit_weights_error(weights):
+    try:
+        calculate_fit_score(weighted_site, weights)
+    except ValueError as e:
+        return str(e)
+    return None
+test("Negative fit weight raises", "non-negative" in (fit_weights_error({"phone": -5}) or ""))
+test("All-zero fit weights raise", fit_weights_error({name: 0 for name in default_fit_weights}) is not None)
+unknown_weight_error = fit_weights_error({"fax": 5}) or ""
+test("Unknown fit weight lists the valid names",
+     "fax" in unknown_weight_error and all(name in unknown_weight_error for name in default_fit_weights))
+try:
+    score_prospects_batch([weighted_site], weights={"phone": -5})
+    test("Batch rejects a negative fit weight", False)
+except ValueError:
+    test("Batch rejects a negative fit weight", True)
+phone_only = explain_fit_score(weighted_site, {"phone": 25})
+default_points = {c["name"]: c["points"] for c in explain_fit_score(weighted_site)["components"]}
+test("Missing fit weights fall back to the defaults",
+     all(c["points"] == (25 if c["name"] == "phone" else default_points[c["name"]]) for c in phone_only["components"])
+     and calculate_fit_score(weighted_site, {}) == calculate_fit_score(weighted_site))
+
+print("\n[scoring profiles]")
+no_booking_site = {
+    "website": "https://clinic.example", "phone": "0400000000", "found_in_maps": True,
+    "signals": {"has_google_analytics": True, "has_facebook_pixel": False,
+                "has_booking_system": False, "emails": ["a@clinic.example"]},
+}
+test("Medical ignores the missing-booking signal",
+     calculate_opportunity_score(no_booking_site, profile="medical")
+     < calculate_opportunity_score(no_booking_site, profile="default"))
+test("Trades weight phone and maps presence up",
+     calculate_fit_score(no_booking_site, profile="trades")
+     > calculate_fit_score(no_booking_site))
+no_ordering_site = dict(no_booking_site, signals=dict(no_booking_site["signals"], has_online_ordering=False))
+test("Hospitality rewards missing online ordering",
+     calculate_opportunity_score(no_ordering_site, profile="hospitality")
+     > calculate_opportunity_score(no_ordering_site, profile="trades"))
+try:
+    calculate_fit_score(no_booking_site, profile="aviation")
+    test("Unknown profile lists registered ones", False)
+except ValueError as e:
+    test("Unknown profile lists registered ones", "hospitality" in str(e) and "trades" in str(e))
+register_scoring_profile("booking_heavy", '{"extends": "trades", "opportunity": {"no_booking": 30}}')
+test("Custom profile extends a built-in",
+     calculate_opportunity_score(no_booking_site, profile="booking_heavy")
+     == calculate_opportunity_score(no_booking_site, profile="trades") + 25)
+test("Batch accepts a profile",
+     score_prospects_batch([no_booking_site], profile="medical")
+     == [(calculate_fit_score(no_booking_site, profile="medical"),
+          calculate_opportunity_score(no_booking_site, profile="medical"))])
+for bad in ['{"fit": {"websites": 1}}', '{"opportunity": {"no_booking": 1.5}}', '[]', '{"extends": "nope"}']:
+    try:
+        register_scoring_profile("bad", bad)
+        test(f"Invalid profile rejected: {bad}", False)
+    except ValueError:
+        test(f"Invalid profile rejected: {bad}", True)
+try:
+    register_scoring_profile("default", "{}")
+    test("Built-in profiles can't be replaced", False)
+except ValueError:
+    test("Built-in profiles can't be replaced", True)
+
+print("\n[freshness decay]")
+fresh_now = 1_700_000_000.0  # 2023-11-14T22:13:20Z
+fresh_base = random_prospects[:3]
+aged = [
+    dict(fresh_base[0], scraped_at="2023-11-14T22:13:20"),
+    dict(fresh_base[1], scraped_at="2023-10-15T22:13:20+00:00"),
+    dict(fresh_base[2], scraped_at="2023-09-16 08:13:20+10:00"),
+    dict(fresh_base[0], scraped_at="last Tuesday"),
+    dict(fresh_base[1]),
+]
+decayed = score_prospects_batch(aged, freshness_half_life_days=30, freshness_now=fresh_now)
+test("Decay at 0, 1x and 2x the half-life",
+     [round(m, 9) for m in decayed["freshness"][:3]] == [1.0, 0.5, 0.25])
+test("Decayed priority is raw priority times the multiplier",
+     all(abs(p - r * m) < 1e-9 for p, r, m in
+         zip(decayed["priority"], decayed["priority_raw"], decayed["freshness"])))
+test("Unparseable or missing scraped_at is flagged, not decayed",
+     decayed["freshness_unknown"] == [False, False, False, True, True]
+     and decayed["priority"][3:] == decayed["priority_raw"][3:])
+test("Future scrapes are not boosted", score_prospects_batch(
+    [dict(fresh_base[0], scraped_at="2030-01-01")], freshness_half_life_days=30,
+    freshness_now=fresh_now)["freshness"] == [1.0])
+try:
+    score_prospects_batch(aged, freshness_half_life_days=0)
+    test("Non-positive half-life raises ValueError", False)
+except ValueError:
+    test("Non-positive half-life raises ValueError", True)
+
+print("\n[strict scoring inputs]")
+reviewed = {"website": "https://a.example", "rating": 4.5, "review_count": 42}
+string_count = dict(reviewed, review_count="42")
+test("Lenient mode reads numeric strings",
+     calculate_fit_score(string_count) == calculate_fit_score(reviewed))
+test("Strict mode accepts absent and None fields",
+     calculate_fit_score({"website": "https://a.example", "review_count": None}, strict=True) == 15)
+try:
+    calculate_fit_score(string_count, strict=True)
+    test("Strict mode rejects a string review_count", False)
+except ValueError as e:
+    test("Strict mode rejects a string review_count", "review_count" in str(e) and "str" in str(e))
+try:
+    score_prospects_batch([reviewed, {"signals": {"load_time_ms": [1]}}], strict=True)
+    test("Strict batch names the index, key and type", False)
+except ValueError as e:
+    test("Strict batch names the index, key and type",
+         "prospect 1" in str(e) and "signals.load_time_ms" in str(e) and "list" in str(e))
+try:
+    calculate_opportunity_score({"website": "https://a.example", "signals": "n/a"}, strict=True)
+    test("Strict opportunity rejects non-dict signals", False)
+except ValueError:
+    test("Strict opportunity rejects non-dict signals", True)
+warned = score_prospects_batch(
+    [string_count, dict(string_count, rating="4.5"), dict(reviewed, found_in_maps="yes")],
+    report_warnings=True)
+test("Batch warnings count coercions and ignored values",
+     warned["warnings"] == {"coerced": {"rating": 1, "review_count": 2},
+                            "ignored": {"found_in_maps": 1}})
+
+print("\n[tech-stack opportunity signals]")
+modern_signals = {"has_google_analytics": True, "has_facebook_pixel": False,
+                  "has_booking_system": True, "emails": ["a@b.example"]}
+modern_site = {"website": "https://modern.example", "found_in_organic": True, "organic_position": 1,
+               "signals": modern_signals}
+def opportunity_with(**signals):
+    return calculate_opportunity_score(dict(modern_site, signals=dict(modern_signals, **signals)))
+base_opportunity = calculate_opportunity_score(modern_site)
+default_opportunity = get_default_opportunity_weights()
+test("New components listed with default weights",
+     all(name in default_opportunity for name in
+         ["not_responsive", "legacy_framework", "outdated_cms", "thin_content", "stale_copyright"]))
+test("Non-responsive adds points", opportunity_with(has_responsive=False) > base_opportunity)
+test("Legacy framework adds points", opportunity_with(has_legacy_framework=True) > base_opportunity)
+test("Outdated CMS from generator", opportunity_with(generator="WordPress 4.9.8") > base_opportunity
+     and opportunity_with(generator="WordPress 6.5") == base_opportunity)
+test("Outdated CMS from cms_version", opportunity_with(cms="Joomla", cms_version="3.10") > base_opportunity)
+test("Thin content from word_count", opportunity_with(word_count=40) > opportunity_with(word_count=900))
+test("Stale copyright", opportunity_with(copyright_years_stale=12) > opportunity_with(copyright_years_stale=0))
+test("Bare copyright year doesn't depend on the clock", opportunity_with(copyright_year=2012) == base_opportunity)
+tech_stack_signals = {"tracking": {"google_analytics": False, "facebook_pixel": False},
+                      "has_booking": False, "emails": []}
+test("analyze_tech_stack keys stand in for tracking and booking",
+     calculate_opportunity_score(dict(modern_site, signals=tech_stack_signals))
+     == calculate_opportunity_score(dict(modern_site, signals={
+         "has_google_analytics": False, "has_facebook_pixel": False,
+         "has_booking_system": False, "emails": []})))
+explained_stack = explain_opportunity_score(dict(modern_site, signals=dict(modern_signals, has_responsive=False)))
+test("Explain names the new components",
+     any(c["name"] == "not_responsive" and c["points"] == default_opportunity["not_responsive"]
+         for c in explained_stack["components"]))
+test("No signals scores as before", calculate_opportunity_score({"website": "https://x.example"}) == 50)
+
+print("\n[priority formula]")
+test("Default formula is 0.4 fit + 0.6 opportunity",
+     get_priority_formula() == {"kind": "weighted_sum", "params": {"fit": 0.4, "opportunity": 0.6}}
+     and calculate_priority(70, 40) == 70 * 0.4 + 40 * 0.6)
+test("Weighted sum with custom weights",
+     calculate_priority(70, 40, "weighted_sum", {"fit": 0.7, "opportunity": 0.3}) == 70 * 0.7 + 40 * 0.3)
+test("Geometric mean", calculate_priority(64, 16, "geometric_mean") == 32.0)
+test("Min-gated passes opportunity above the fit threshold",
+     calculate_priority(60, 80, "min_gated", {"threshold": 60}) == 80
+     and calculate_priority(59, 80, "min_gated", {"threshold": 60}) == 0)
+for kind, params in [("median", None), ("geometric_mean", {"fit": 1}), ("min_gated", None),
+                     ("weighted_sum", {"fit": -1})]:
+    try:
+        calculate_priority(50, 50, kind, params)
+        test(f"Invalid formula rejected: {kind} {params}", False)
+    except ValueError:
+        test(f"Invalid formula rejected: {kind} {params}", True)
+set_priority_formula("min_gated", {"threshold": 50})
+gated = score_prospects_batch(random_prospects[:30], rank_method="percentile")
+test("Batch applies and reports the configured formula",
+     gated["priority_formula"] == {"kind": "min_gated", "params": {"threshold": 50.0}}
+     and gated["priority"] == [float(o) if f >= 50 else 0.0 for f, o in gated["scores"]])
+set_priority_formula("weighted_sum")
+test("Resetting restores the default", calculate_priority(70, 40) == 70 * 0.4 + 40 * 0.6)
+
+print("\n[attribute-style prospects]")
+@dataclass
+class SignalsObj:
+    cms: Optional[str] = None
+    has_google_analytics: Optional[bool] = None
+    has_facebook_pixel: Optional[bool] = None
+    has_booking_system: Optional[bool] = None
+    emails: list = field(default_factory=list)
+    load_time_ms: Optional[int] = None
+    reachable: bool = True
+
+@dataclass
+class ProspectObj:
+    name: str
+    website: Optional[str] = None
+    phone: Optional[str] = None
+    emails: list = field(default_factory=list)
+    found_in_maps: bool = False
+    maps_position: Optional[int] = None
+    rating: Optional[float] = None
+    review_count: Optional[int] = None
+    found_in_ads: bool = False
+    found_in_organic: bool = False
+    organic_position: Optional[int] = None
+    fit_score: int = 0
+    opportunity_score: int = 0
+    priority_score: float = 0.0
+    scraped_at: Optional[datetime] = None
+    signals: Optional[SignalsObj] = None
+
+obj_prospect = ProspectObj(
+    name="Obj Plumbing", website="https://obj.example", phone="0400111222", emails=["a@obj.example"],
+    found_in_maps=True, maps_position=3, rating=4.6, review_count=31, fit_score=70,
+    opportunity_score=55, priority_score=61.0, scraped_at=datetime(2024, 5, 1, 9, 30),
+    signals=SignalsObj(cms="Wix", has_google_analytics=False, has_facebook_pixel=True,
+                       has_booking_system=False, load_time_ms=4200),
+)
+obj_as_dict = {k: v for k, v in vars(obj_prospect).items() if k != "signals"}
+obj_as_dict["signals"] = dict(vars(obj_prospect.signals))
+obj_as_dict["scraped_at"] = "2024-05-01T09:30:00"
+test("Batch scores objects like dicts",
+     score_prospects_batch([obj_prospect]) == score_prospects_batch([obj_as_dict]))
+test("Mixed dict/object batch",
+     score_prospects_batch([obj_as_dict, obj_prospect, random_prospects[0]])
+     == score_prospects_batch([obj_as_dict, obj_as_dict, random_prospects[0]]))
+test("CSV from objects matches dicts",
+     serialize_prospects_csv([obj_prospect, obj_as_dict]) == serialize_prospects_csv([obj_as_dict, obj_as_dict]))
+test("JSON from objects matches dicts (datetime as ISO)",
+     serialize_prospects_json([obj_prospect], False) == serialize_prospects_json([obj_as_dict], False)
+     and '"2024-05-01T09:30:00"' in serialize_prospects_json([obj_prospect], False))
+decayed_obj = score_prospects_batch([obj_prospect], freshness_half_life_days=30,
+                                    freshness_now=datetime(2024, 5, 31, 9, 30).timestamp())
+test("Object scraped_at datetimes feed freshness decay", decayed_obj["freshness_unknown"] == [False])
+
+print("\n[opportunity notes]")
+test("No website note", generate_opportunity_notes({"name": "x"}) == "No website found - needs web presence")
+noted = {"website": "https://n.example", "found_in_maps": True, "maps_position": 3,
+         "signals": {"has_google_analytics": False, "has_facebook_pixel": True,
+                     "cms": "Wix", "load_time_ms": 4200, "emails": ["a@n.example"]}}
+test("Notes follow breakdown order",
+     generate_opportunity_notes(noted) == "no Google Analytics; using Wix (limited platform); "
+     "slow site (4200ms load time); #3 in local pack (not #1); not ranking in organic search")
+quiet = {"website": "https://q.example", "found_in_organic": True, "organic_position": 1,
+         "signals": {"emails": ["a@q.example"]}}
+test("No triggering signals gives an empty string", generate_opportunity_notes(quiet) == "")
+test("Templates can be overridden or dropped",
+     generate_opportunity_notes(noted, {"weak_cms": "on {input}", "poor_maps": "", "not_in_organic": "unranked"})
+     == "no Google Analytics; on Wix; slow site (4200ms load time); unranked")
+try:
+    generate_opportunity_notes(noted, {"bogus": "x"})
+    test("Unknown template key raises ValueError", False)
+except ValueError:
+    test("Unknown template key raises ValueError", True)
+noted_batch = score_prospects_batch([noted, quiet, obj_prospect], with_notes=True)
+test("Batch notes match generate_opportunity_notes",
+     noted_batch["notes"] == [generate_opportunity_notes(p) for p in [noted, quiet, obj_prospect]])
+
+print("\n[top_prospects]")
+ranked_pool = [
+    {"name": "Bravo", "priority_score": 80.0, "fit_score": 60, "review_count": 10},
+    {"name": "Alpha", "priority_score": 80.0, "fit_score": 60, "review_count": 10},
+    {"name": "Delta", "priority_score": 90.0, "fit_score": 10},
+    {"name": "Echo", "priority_score": 80.0, "fit_score": 70, "review_count": 1},
+    {"name": "Foxtrot", "priority_score": 80.0, "fit_score": 60, "review_count": 50},
+    {"name": "Alpha", "priority_score": 80.0, "fit_score": 60, "review_count": 10},
+    {"name": "Golf"},
+]
+test("Ties break on fit, reviews, name, then index",
+     top_prospects(ranked_pool, 6) == [2, 3, 4, 1, 5, 0])
+test("n beyond the list returns every index ranked", top_prospects(ranked_pool, 100) == [2, 3, 4, 1, 5, 0, 6])
+test("n of zero returns nothing", top_prospects(ranked_pool, 0) == [])
+test("Rank by another field", top_prospects(ranked_pool, 1, by="fit_score") == [3])
+shuffled_rng = random.Random(188)
+big_pool = [{"name": f"p{shuffled_rng.randint(0, 50)}", "priority_score": float(shuffled_rng.randint(0, 20)),
+             "fit_score": shuffled_rng.randint(0, 3), "review_count": shuffled_rng.randint(0, 3)}
+            for _ in range(2000)]
+expected_top = sorted(range(len(big_pool)), key=lambda i: (
+    -big_pool[i]["priority_score"], -big_pool[i]["fit_score"], -big_pool[i]["review_count"],
+    big_pool[i]["name"], i))[:500]
+test("Partial selection matches a full sort (2000 prospects)", top_prospects(big_pool, 500) == expected_top)
+
+print("\n[score_deltas]")
+last_week = [
+    {"name": "Joe's Plumbing", "website": "https://www.joesplumbing.com.au", "found_in_ads": True,
+     "rating": 4.5, "review_count": 40, "fit_score": 60, "opportunity_score": 20, "priority_score": 36.0,
+     "signals": {"reachable": True, "has_google_analytics": True}},
+    {"name": "Gone Electrical", "domain": "gone.com.au", "fit_score": 30, "opportunity_score": 50,
+     "priority_score": 42.0},
+    {"name": "Steady Roofing", "website": "steadyroofing.com.au", "rating": 4.0, "review_count": 12},
+]
+this_week = [
+    {"name": "Joe's Plumbing", "website": "http://joesplumbing.com.au/", "found_in_ads": False,
+     "rating": 4.5, "review_count": 41, "fit_score": 55, "opportunity_score": 70, "priority_score": 64.0,
+     "signals": {"reachable": False, "has_google_analytics": True}},
+    {"name": "Steady Roofing", "website": "https://steadyroofing.com.au", "rating": 4.0, "review_count": 12},
+    {"name": "New Cafe", "domain": "newcafe.com.au", "fit_score": 40, "opportunity_score": 40},
+]
+deltas = score_deltas(last_week, this_week)
+by_name = {d["name"]: d for d in deltas}
+test("One entry per prospect, current first then dropped",
+     [d["name"] for d in deltas] == ["Joe's Plumbing", "Steady Roofing", "New Cafe", "Gone Electrical"])
+test("Statuses", [d["delta"]["status"] for d in deltas] == ["matched", "matched", "new", "dropped"])
+joe = by_name["Joe's Plumbing"]["delta"]
+test("Joined on normalized domain", joe["key"] == "joesplumbing.com.au")
+test("Deltas from stored scores", (joe["fit"], joe["opportunity"], joe["priority"]) == (-5, 50, 28.0))
+test("Previous scores reported", joe["previous"] == {"fit": 60, "opportunity": 20, "priority": 36.0})
+test("Changed fields include ads and reachability",
+     joe["changed_fields"] == ["website", "found_in_ads", "review_count", "signals.reachable"])
+steady = by_name["Steady Roofing"]
+test("Unscored prospects are scored",
+     steady["fit_score"] == calculate_fit_score(this_week[1]) and steady["delta"]["fit"] == 0)
+test("New prospect has no deltas",
+     by_name["New Cafe"]["delta"]["fit"] is None and by_name["New Cafe"]["priority_score"] == 40.0)
+test("Dropped prospect keeps its previous scores", by_name["Gone Electrical"]["fit_score"] == 30)
+rescored = score_deltas(last_week, this_week, rescore=True)
+test("rescore ignores stored scores", rescored[0]["fit_score"] == calculate_fit_score(this_week[0]))
+by_phone = score_deltas([{"name": "A", "phone": "+61 7 3000 1234"}], [{"name": "A", "phone": "(07) 3000 1234"}],
+                        key="phone")
+test("Join on normalized phone", by_phone[0]["delta"]["status"] == "matched")
+exported = json.loads(serialize_prospects_json(deltas, False))
+test("Deltas export through serialize_prospects_json",
+     exported[0]["scores"]["opportunity"] == 70 and exported[0]["delta"]["opportunity"] == 50
+     and exported[3]["delta"]["status"] == "dropped" and exported[2]["delta"]["previous"] is None)
+
+print("\n[compare_weightings]")
+same = compare_weightings(random_prospects, {"ads_presence": 2.0}, {"ads_presence": 2.0})
+test("Identical weightings correlate perfectly", same["spearman"] == 1.0)
+test("Identical weightings have no movers or grade changes",
+     same["movers"] == [] and same["grade_changes"] == 0 and same["count"] == len(random_prospects))
+test("None compares the profile with itself", compare_weightings(prospects)["movers"] == [])
+heavy_reviews = {"ads_presence": 0.0, "review_count": 3.0}
+shifted = compare_weightings(random_prospects, None, heavy_reviews)
+test("Different weightings move prospects", 0 < len(shifted["movers"]) <= 50)
+test("Correlation drops below 1", -1.0 <= shifted["spearman"] < 1.0)
+movements = [abs(m["movement"]) for m in shifted["movers"]]
+test("Largest movements first", movements == sorted(movements, reverse=True))
+mover = shifted["movers"][0]
+test("Movers carry both sides' scores",
+     mover["scores_a"] == score_prospects_batch(random_prospects)[mover["index"]]
+     and mover["scores_b"] == score_prospects_batch(random_prospects, weights=heavy_reviews)[mover["index"]])
+test("Movement is rank_a - rank_b", mover["movement"] == mover["rank_a"] - mover["rank_b"])
+test("top limits the movers", len(compare_weightings(random_prospects, None, heavy_reviews, top=5)["movers"]) == 5)
+test("Profiles compare by name", compare_weightings(random_prospects, "default", "trades")["spearman"] < 1.0)
+
+print("\n[directory-listing websites]")
+owned_site = {"website": "https://joesplumbing.com.au", "phone": "07 3000 1234", "found_in_maps": True}
+yelp_site = dict(owned_site, website="https://www.yelp.com.au/biz/joes-plumbing-brisbane")
+no_site = dict(owned_site, website=None)
+test("Directory listing scores below an owned site",
+     calculate_fit_score(yelp_site) == calculate_fit_score(owned_site) - 15 - 10)
+yelp_explained = {c["name"]: c for c in explain_fit_score(yelp_site)["components"]}
+test("No website points for a directory listing", yelp_explained["website"]["points"] == 0)
+test("Penalty named in the breakdown",
+     yelp_explained["directory_listing"]["points"] == -10
+     and yelp_explained["directory_listing"]["input"] == "yelp.com.au")
+test("Penalty is a fit weight", get_default_fit_weights()["directory_listing"] == 10)
+test("Penalty is configurable",
+     calculate_fit_score(yelp_site, {"directory_listing": 0}) == calculate_fit_score(owned_site) - 15)
+test("No website is unaffected",
+     "directory_listing" not in [c["name"] for c in explain_fit_score(no_site)["components"]]
+     and calculate_fit_score(no_site) == calculate_fit_score(owned_site) - 15)
+test("Batch applies the penalty",
+     score_prospects_batch([yelp_site, owned_site])[0][0] == calculate_fit_score(yelp_site))
+
+print("\n[reputation_signal]")
+def reputation(rating, count, **kw):
+    return reputation_signal(rating, count, **kw)["classification"]
+test("Many reviews at a poor rating is low_rating", reputation(3.1, 200) == "low_rating")
+test("Few reviews at a good rating is low_volume", reputation(5.0, 8) == "low_volume")
+test("Enough well-rated reviews is strong", reputation(4.6, 120) == "strong")
+test("No rating or reviews is no_presence",
+     reputation(None, None) == "no_presence" and reputation(None, 0) == "no_presence")
+test("Exactly 4.0 stars is not low_rating", reputation(4.0, 50) == "strong")
+test("Just under 4.0 stars is low_rating", reputation(3.99, 50) == "low_rating")
+test("Exactly the review threshold is not low_volume", reputation(4.5, 10) == "strong")
+test("One under the review threshold is low_volume", reputation(4.5, 9) == "low_volume")
+test("Poor rating wins over low volume", reputation(2.0, 3) == "low_rating")
+test("Thresholds are configurable",
+     reputation(4.2, 40, good_rating=4.5) == "low_rating" and reputation(4.6, 40, min_reviews=50) == "low_volume")
+test("Profile thresholds apply", reputation(4.5, 30, profile="hospitality") == "low_volume")
+test("Result echoes the thresholds used",
+     reputation_signal(4.5, 30) == {"classification": "strong", "rating": 4.5, "review_count": 30,
+                                    "good_rating": 4.0, "min_reviews": 10.0})
+reviewed_site = {"website": "https://joes.example", "found_in_organic": True, "organic_position": 1,
+                 "signals": {"has_google_analytics": True, "has_facebook_pixel": False, "emails": ["a@b.c"]}}
+def reputation_points(rating, count):
+    explained = explain_opportunity_score(dict(reviewed_site, rating=rating, review_count=count))
+    return {c["name"]: (c["points"], c["input"]) for c in explained["components"]
+            if c["name"] in ("low_volume", "low_rating")}
+test("low_rating adds opportunity points",
+     reputation_points(3.1, 200) == {"low_volume": (0, "low_rating"), "low_rating": (10, "low_rating")})
+test("low_volume adds opportunity points",
+     reputation_points(5.0, 8) == {"low_volume": (10, "low_volume"), "low_rating": (0, "low_volume")})
+test("Boundary values add nothing", reputation_points(4.0, 10) == {"low_volume": (0, "strong"),
+                                                                   "low_rating": (0, "strong")})
+test("Reputation weights are opportunity weights",
+     get_default_opportunity_weights()["low_volume"] == 10 and get_default_opportunity_weights()["low_rating"] == 10)
+rated = [dict(reviewed_site, name="Joe", rating=3.1, review_count=200,
+              reputation=reputation(3.1, 200))]
+rated_csv = list(csv.reader(io.StringIO(serialize_prospects_csv(rated))))
+test("CSV reputation column when classified",
+     rated_csv[0][-1] == "reputation" and rated_csv[1][-1] == "low_rating")
+test("No reputation column otherwise",
+     "reputation" not in serialize_prospects_csv([reviewed_site]).splitlines()[0])
+test("JSON reputation under google_business",
+     json.loads(serialize_prospects_json(rated, False))[0]["google_business"]["reputation"] == "low_rating")
+
+print("\n[unreachable_policy]")
+live_site = {"website": "https://live.example", "phone": "07 3000 0000", "found_in_maps": True,
+             "rating": 4.5, "review_count": 30,
+             "signals": {"reachable": True, "has_google_analytics": True, "has_facebook_pixel": True}}
+down_site = dict(live_site, website="https://down.example", signals={**live_site["signals"], "reachable": False})
+unchecked_site = dict(live_site, signals={k: v for k, v in live_site["signals"].items() if k != "reachable"})
+none_site = dict(live_site, signals={**live_site["signals"], "reachable": None})
+string_site = dict(live_site, signals={**live_site["signals"], "reachable": "false"})
+pool = [live_site, down_site, unchecked_site, none_site, string_site]
+plain = score_prospects_batch(pool)
+test("Without a policy reachable is ignored", plain[1] == plain[0])
+penalized = score_prospects_batch(pool, unreachable_policy="penalize_fit")
+test("penalize_fit zeroes fit only for reachable=False",
+     penalized[1] == (0, plain[1][1]) and penalized[0] == plain[0])
+test("Missing, None and non-bool reachable are not penalized", penalized[2:] == plain[2:])
+boosted = score_prospects_batch(pool, unreachable_policy="boost_opportunity")
+test("boost_opportunity raises opportunity to the no-website level",
+     boosted[1] == (plain[1][0], 80) and boosted[2:] == plain[2:])
+excluded = score_prospects_batch(pool, unreachable_policy="exclude")
+test("exclude keeps the slot with a -1 sentinel",
+     len(excluded) == len(pool) and excluded[1] == (-1, -1) and excluded[0] == plain[0])
+test("exclude leaves missing reachable alone", all(s != (-1, -1) for s in excluded[2:]))
+excluded_dict = score_prospects_batch(pool, unreachable_policy="exclude", rank_method="percentile", grade=True)
+test("Excluded indices listed", excluded_dict["excluded"] == [1])
+test("Excluded priority is -1 and unranked",
+     excluded_dict["priority"][1] == -1.0 and excluded_dict["priority_percentile"][1] is None
+     and excluded_dict["grade"][1] is None)
+included = [p for i, p in enumerate(pool) if i != 1]
+test("Ranks cover the remaining prospects",
+     [r for r in excluded_dict["priority_percentile"] if r is not None]
+     == score_prospects_batch(included, rank_method="percentile")["priority_percentile"])
+explained_down = score_prospects_batch([down_site], unreachable_policy="penalize_fit", explain=True)
+down_fit = explained_down["explanations"][0]["fit"]
+test("Penalty shows as an unreachable component",
+     down_fit["components"][-1]["name"] == "unreachable"
+     and round(sum(c["points"] for c in down_fit["components"])) == down_fit["total"] == 0)
+try:
+    score_prospects_batch(pool, unreachable_policy="drop")
+    test("Unknown policy raises ValueError", False)
+except ValueError:
+    test("Unknown policy raises ValueError", True)
+
+print("\n[caps, clamp and curves]")
+golden_path = os.path.join(os.path.dirname(os.path.abspath(__file__)), "tests", "fixtures", "scoring_golden.json")
+with open(golden_path) as f:
+    golden = json.load(f)
+for golden_profile in ["default", "trades", "hospitality", "medical", "retail"]:
+    scored = [list(s) for s in score_prospects_batch(golden["prospects"], profile=golden_profile)]
+    test(f"Golden scores reproduced ({golden_profile}, {len(scored)} prospects)", scored == golden[golden_profile])
+register_scoring_profile("unshaped", '{"caps": {}, "curves": {}}')
+test("Empty caps and curves change nothing",
+     [list(s) for s in score_prospects_batch(golden["prospects"], profile="unshaped")] == golden["default"])
+reviewed = {"website": "https://x.example", "rating": 4.5}
+def review_points(profile, count):
+    explained = explain_fit_score(dict(reviewed, review_count=count), profile=profile)
+    return next(c["points"] for c in explained["components"] if c["name"] == "review_count")
+register_scoring_profile("log_reviews", '{"curves": {"review_count": {"curve": "log", "base": 101}}}')
+test("Log curve keeps differentiating past the threshold",
+     review_points("log_reviews", 10) < review_points("log_reviews", 50) < review_points("log_reviews", 100))
+test("Log curve saturates at base - 1",
+     review_points("log_reviews", 100) == 10 and review_points("log_reviews", 5000) == 10)
+test("Log curve value", math.isclose(review_points("log_reviews", 10), 10 * math.log(11) / math.log(101)))
+register_scoring_profile("linear_reviews", '{"curves": {"review_count": {"curve": "linear_capped", "cap": 200}}}')
+test("Linear curve", review_points("linear_reviews", 50) == 2.5 and review_points("linear_reviews", 400) == 10)
+register_scoring_profile("step_reviews",
+                         '{"curves": {"review_count": {"curve": "step", "breakpoints": [[100, 1], [20, 0.5]]}}}')
+test("Step curve", [review_points("step_reviews", n) for n in (19, 20, 99, 100)] == [0, 5, 5, 10])
+test("Missing value earns nothing on a curve", review_points("log_reviews", None) == 0)
+register_scoring_profile("step_back", '{"extends": "step_reviews", "curves": {"review_count": null}}')
+test("null curve restores the threshold", review_points("step_back", 10) == review_points("default", 10) == 10)
+register_scoring_profile("capped_tools", '{"caps": {"ab_testing": 20, "phone": 5}}')
+tools = {"website": "https://x.example", "phone": "1", "found_in_maps": True, "rating": 4.5, "review_count": 50,
+         "signals": {"ab_testing_tools": ["VWO", "Optimizely", "AB Tasty", "Convert"]}}
+test("Cap raises a deduction's limit",
+     {c["name"]: c["points"] for c in explain_fit_score(tools, profile="capped_tools")["components"]}["ab_testing"] == -20)
+test("Cap limits a component",
+     {c["name"]: c["cap"] for c in explain_fit_score(tools, profile="capped_tools")["components"]}["phone"] == 5
+     and calculate_fit_score(tools, profile="capped_tools") == calculate_fit_score(tools) - 10 - 10)
+register_scoring_profile("narrow", '{"clamp": [10, 60]}')
+test("Clamp range bounds both scores",
+     calculate_opportunity_score({"name": "no site"}, profile="narrow") == 60
+     and calculate_fit_score({}, profile="narrow") == 10)
+clamped = explain_opportunity_score({"name": "no site"}, profile="narrow")
+test("Clamp shows in the breakdown",
+     clamped["components"][-1]["name"] == "clamp" and clamped["components"][-1]["cap"] == 60)
+for bad in ['{"clamp": [50, 40]}', '{"clamp": [0, 120]}', '{"caps": {"bogus": 5}}', '{"caps": {"phone": -1}}',
+            '{"curves": {"phone": {"curve": "log", "base": 10}}}', '{"curves": {"review_count": {"curve": "log"}}}',
+            '{"curves": {"review_count": {"curve": "log", "base": 1}}}',
+            '{"curves": {"review_count": {"curve": "step", "breakpoints": [[10, 2]]}}}',
+            '{"curves": {"review_count": {"curve": "cubic"}}}']:
+    try:
+        register_scoring_profile("bad_shape", bad)
+        test(f"Invalid shape {bad} raises ValueError", False)
+    except ValueError:
+        test(f"Invalid shape {bad} raises ValueError", True)
+
+print("\n[load_scoring_config]")
+config_json = """{
+  "profile": "trades",
+  "fit": {"phone": 25},
+  "opportunity": {"no_booking": 0},
+  "caps": {"ab_testing": 15},
+  "clamp": [5, 95],
+  "curves": {"review_count": {"curve": "log", "base": 101}},
+  "priority": {"kind": "min_gated", "params": {"threshold": 40}}
+}"""
+config_toml = """
+profile = "trades"
+clamp = [5, 95]
+
+[fit]
+phone = 25
+
+[opportunity]
+no_booking = 0
+
+[caps]
+ab_testing = 15
+
+[curves.review_count]
+curve = "log"
+base = 101
+
+[priority]
+kind = "min_gated"
+params = { threshold = 40 }
+"""
+from_json = load_scoring_config(config_json, "json")
+from_toml = load_scoring_config(config_toml, "toml")
+test("JSON and TOML load the same config", dump_scoring_config(from_json) == dump_scoring_config(from_toml))
+test("Config reports its base profile", from_json.profile == "trades" and from_json.ignored_keys == [])
+dumped = dump_scoring_config(from_json)
+test("Dump round-trips (JSON)", dump_scoring_config(load_scoring_config(dumped, "json")) == dumped)
+dumped_toml = dump_scoring_config(from_json, "toml")
+test("Dump round-trips (TOML)",
+     dump_scoring_config(load_scoring_config(dumped_toml, "toml"), "toml") == dumped_toml
+     and dump_scoring_config(load_scoring_config(dumped_toml, "toml")) == dumped)
+test("Dump spells out every weight",
+     json.loads(dumped)["fit"]["phone"] == 25 and json.loads(dumped)["fit"]["maps_presence"] == 20
+     and json.loads(dumped)["priority"] == {"kind": "min_gated", "params": {"threshold": 40.0}})
+register_scoring_profile("config_equivalent", json.dumps({k: v for k, v in json.loads(config_json).items()
+                                                          if k not in ("profile", "priority")}
+                                                         | {"extends": "trades"}))
+batch_prospects = [{"website": "https://a.example", "phone": "1", "rating": 4.6, "review_count": 80},
+                   {"name": "no site", "found_in_maps": True},
+                   {"website": "https://b.example", "signals": {"has_google_analytics": True}}]
+via_config = score_prospects_batch(batch_prospects, grade=True, config=from_json)
+set_priority_formula("min_gated", {"threshold": 40})
+via_profile = score_prospects_batch(batch_prospects, grade=True, profile="config_equivalent")
+set_priority_formula("weighted_sum")
+test("Batch with config matches the same profile and formula", via_config == via_profile)
+test("Config's formula doesn't touch the global one",
+     get_priority_formula()["kind"] == "weighted_sum" and via_config["priority_formula"]["kind"] == "min_gated")
+plain = load_scoring_config("{}", "json")
+test("Config without priority uses the formula in effect",
+     score_prospects_batch(batch_prospects, grade=True, config=plain)
+     == score_prospects_batch(batch_prospects, grade=True))
+test("Weights still apply on top of a config",
+     score_prospects_batch(batch_prospects, weights={"phone": 0}, config=from_json)
+     == score_prospects_batch(batch_prospects, weights={"phone": 0}, profile="config_equivalent"))
+def config_error(text, fmt="json", **kw):
+    try:
+        load_scoring_config(text, fmt, **kw)
+    except ValueError as e:
+        return str(e)
+    return None
+test("JSON syntax error names the line", "line 3" in (config_error('{\n  "fit": {\n    "phone": }\n}') or ""))
+test("TOML syntax error names the line", "line 2" in (config_error('[fit]\nphone = = 3\n', "toml") or ""))
+test("Schema error names the field", "fit.phone" in (config_error('{"fit": {"phone": "lots"}}') or ""))
+test("Unknown key errors by default", "fit.fone" in (config_error('{"fit": {"fone": 5}}') or ""))
+test("Unknown nested key errors", "curves.review_count.bse" in
+     (config_error('{"curves": {"review_count": {"curve": "log", "base": 101, "bse": 3}}}') or ""))
+test("Unknown priority param errors",
+     "priority.params.fit" in (config_error('{"priority": {"kind": "min_gated", "params": {"fit": 1}}}') or ""))
+lenient = load_scoring_config('{"version": 2, "fit": {"fone": 5, "phone": 20}}', "json", allow_unknown=True)
+test("allow_unknown skips and lists unknown keys",
+     sorted(lenient.ignored_keys) == ["fit.fone", "version"] and json.loads(dump_scoring_config(lenient))["fit"]["phone"] == 20)
+test("Unknown format errors", config_error("{}", "yaml") is not None)
+test("Unknown base profile errors", "no_such" in (config_error('{"profile": "no_such"}') or ""))
+try:
+    score_prospects_batch(batch_prospects, profile="trades", config=from_json)
+    test("profile and config together raise ValueError", False)
+except ValueError:
+    test("profile and config together raise ValueError", True)
+
+print("\n[compile_rules]")
+rule_set = compile_rules([
+    'dentist_booking: category contains "dentist" and has_booking_system == false => opportunity +10',
+    'rating < 3.5 or review_count missing => fit -5',
+    'not (signals.reachable != false) => exclude',
+])
+test("RuleSet names rules, unnamed ones by index",
+     rule_set.names == ["dentist_booking", "rule_1", "rule_2"] and len(rule_set) == 3)
+ruled = [
+    {"website": "https://smile.example", "category": "Family Dentist", "rating": 4.6, "review_count": 40,
+     "signals": {"has_booking_system": False}},
+    {"website": "https://smile2.example", "category": "Dentist", "rating": 4.6, "review_count": 40,
+     "signals": {"has_booking_system": True}},
+    {"website": "https://plumb.example", "category": "Plumber", "rating": 3.0, "review_count": 40},
+    {"website": "https://down.example", "category": "Dentist", "signals": {"reachable": False}},
+]
+base_scores = score_prospects_batch(ruled)
+rule_scores = score_prospects_batch(ruled, rules=rule_set)
+test("Matching rule adjusts opportunity", rule_scores[0] == (base_scores[0][0], base_scores[0][1] + 10))
+test("Non-matching prospect unchanged", rule_scores[1] == base_scores[1])
+test("or matches either side", rule_scores[2] == (base_scores[2][0] - 5, base_scores[2][1]))
+test("exclude rule scores -1", rule_scores[3] == (-1, -1))
+explained_rules = score_prospects_batch(ruled, explain=True, rules=rule_set)
+test("Explain lists matched rules",
+     [e["rules"] for e in explained_rules["explanations"]] == [["dentist_booking"], [], ["rule_1"],
+                                                                ["rule_1", "rule_2"]])
+rule_components = [c for c in explained_rules["explanations"][0]["opportunity"]["components"] if c["name"] == "rule"]
+test("Rule shows as a breakdown component",
+     rule_components == [{"name": "rule", "input": "dentist_booking", "points": 10.0, "cap": None}])
+test("Excluded by rule listed", explained_rules["excluded"] == [3])
+test("Breakdowns still sum to the scores",
+     all(round(sum(c["points"] for c in e["fit"]["components"])) == s[0]
+         for e, s in zip(explained_rules["explanations"][:3], explained_rules["scores"][:3])))
+capped_rule = compile_rules(['category contains "dentist" => opportunity +100'])
+test("Rule adjustments are clamped",
+     score_prospects_batch([ruled[0]], rules=capped_rule)[0][1] == 100)
+class RuledProspect:
+    website = "https://obj.example"
+    category = "Dentist"
+    signals = {"has_booking_system": False}
+test("Objects are read for rule fields",
+     score_prospects_batch([RuledProspect()], rules=rule_set)[0][1]
+     == score_prospects_batch([RuledProspect()])[0][1] + 10)
+test("Text compares ignoring case and lists by element",
+     score_prospects_batch([{"signals": {"ab_testing_tools": ["VWO"]}}],
+                           rules=compile_rules(['ab_testing_tools contains "vwo" => fit +7']))[0][0] == 7)
+def rule_error(rules):
+    try:
+        compile_rules(rules)
+    except ValueError as e:
+        return str(e)
+    return None
+test("Compile error names rule index and column",
+     rule_error(["rating > 4 => fit +5", "rating >> 4 => fit +5"]) == "rule 1 at column 9: expected a number, quoted string, true or false")
+test("Missing adjustment is an error", "rule 0 at column 11" in (rule_error(["rating > 4"]) or ""))
+test("Unterminated string is an error", "column 19" in (rule_error(['category contains "dent => exclude']) or ""))
+test("Out-of-range points are an error", rule_error(["rating > 4 => fit +500"]) is not None)
+test("Unknown adjustment is an error", rule_error(["rating > 4 => priority +5"]) is not None)
+
+print("\n[segment_prospects]")
+tiered = [
+    {"phone": "555 0100", "priority_score": 75.0},
+    {"emails": ["a@x.example"], "priority_score": 60.0},
+    {"phone": "555 0101", "priority_score": 59.9},
+    {"phone": "555 0102", "priority_score": 12.0},
+    {"priority_score": 90.0},
+    {"phone": "  ", "signals": {"emails": ["b@x.example"]}, "priority_score": 45.0},
+]
+segmented = segment_prospects(tiered)
+test("Tiers by priority, a met threshold taking the higher tier",
+     segmented["tiers"] == {"call_now": [0, 1], "email_sequence": [2, 5], "nurture": [3], "unreachable": [4]})
+test("Counts per tier and total",
+     segmented["counts"] == {"call_now": 2, "email_sequence": 2, "nurture": 1, "unreachable": 1, "total": 6})
+test("Default thresholds reported", segmented["thresholds"] == {"call_now": 60.0, "email_sequence": 40.0})
+test("require_contact=False scores everyone",
+     segment_prospects(tiered, require_contact=False)["tiers"]["call_now"] == [0, 1, 4])
+test("Custom thresholds",
+     segment_prospects(tiered, {"call_now": 80, "email_sequence": 10})["tiers"]
+     == {"call_now": [], "email_sequence": [0, 1, 2, 3, 5], "nurture": [], "unreachable": [4]})
+unscored = {"website": "https://x.example", "phone": "1", "found_in_maps": True, "rating": 4.5, "review_count": 50}
+expected_priority = calculate_priority(calculate_fit_score(unscored), calculate_opportunity_score(unscored))
+expected_tier = "call_now" if expected_priority >= 60 else "email_sequence" if expected_priority >= 40 else "nurture"
+test("Missing priority is computed", segment_prospects([unscored])["tiers"][expected_tier] == [0])
+test("rescore ignores stored priority",
+     segment_prospects([dict(unscored, priority_score=99.0)], rescore=True)["tiers"][expected_tier] == [0])
+written = [dict(p) for p in tiered]
+segment_prospects(written, write_tier=True)
+test("write_tier sets tier", [p["tier"] for p in written]
+     == ["call_now", "call_now", "email_sequence", "nurture", "unreachable", "email_sequence"])
+test("Tier exported as a CSV column",
+     serialize_prospects_csv(written).splitlines()[0].endswith(",tier")
+     and serialize_prospects_csv(written).splitlines()[1].endswith(",call_now")
+     and "tier" not in serialize_prospects_csv(tiered).splitlines()[0])
+tier_and_reputation = serialize_prospects_csv([dict(written[0], reputation="low_rating")]).splitlines()[0].split(",")
+test("Tier column goes after reputation", tier_and_reputation[-2:] == ["reputation", "tier"])
+test("Tier exported in JSON scores", json.loads(serialize_prospects_json(written, False))[0]["scores"]["tier"] == "call_now")
+class TierProspect:
+    phone = "555"
+    priority_score = 50.0
+tier_object = TierProspect()
+segment_prospects([tier_object], write_tier=True)
+test("write_tier sets an attribute on objects", tier_object.tier == "email_sequence")
+for bad_thresholds in [{"hot": 80}, {"call_now": 30, "email_sequence": 50}]:
+    try:
+        segment_prospects(tiered, bad_thresholds)
+        test(f"Invalid thresholds {bad_thresholds} raise ValueError", False)
+    except ValueError:
+        test(f"Invalid thresholds {bad_thresholds} raise ValueError", True)
+
+print("\n[merge_duplicate_prospects]")
+dupes = [
+    {"name": "Joe's Plumbing", "website": "https://www.joesplumbing.com.au/", "emails": ["joe@joesplumbing.com.au"],
+     "found_in_ads": True, "ad_position": 2, "rating": 4.2, "review_count": 30, "source": "ads",
+     "scraped_at": "2024-05-31T23:30:00"},
+    {"name": "Smith Dental", "phone": "(02) 9999 1234", "source": "maps"},
+    {"name": "Joes Plumbing Pty Ltd", "phone": "0400 111 222", "address": "1 Pipe St, Sydney NSW 2000",
+     "found_in_maps": True, "maps_position": 4, "rating": 4.5, "review_count": 12, "source": "maps",
+     "emails": ["JOE@joesplumbing.com.au", "office@joesplumbing.com.au"], "scraped_at": "2024-06-01T09:00:00+10:00"},
+    {"name": "Different Co", "website": "https://joesplumbing.com.au/contact", "found_in_organic": True,
+     "organic_position": 7, "source": "organic"},
+    {"name": "Smith Dental Clinic", "phone": "+61 2 9999 1234", "source": "maps"},
+    {"name": "Yelp page", "website": "https://www.yelp.com.au/biz/a"},
+    {"name": "Other Yelp page", "website": "https://www.yelp.com.au/biz/b"},
+]
+merged, groups = merge_duplicate_prospects(dupes)
+test("Groups by domain, phone and fuzzy name", groups == [[0, 2, 3], [1, 4], [5], [6]])
+joes = merged[0]
+test("Emails unioned case-insensitively",
+     sorted(e.lower() for e in joes["emails"]) == ["joe@joesplumbing.com.au", "office@joesplumbing.com.au"])
+test("found_in_* unioned with best positions",
+     joes["found_in_ads"] and joes["found_in_maps"] and joes["found_in_organic"]
+     and (joes["ad_position"], joes["maps_position"], joes["organic_position"]) == (2, 4, 7))
+test("Fullest address and website kept",
+     joes["address"] == "1 Pipe St, Sydney NSW 2000" and joes["website"] == "https://joesplumbing.com.au/contact")
+test("Max rating and review count", joes["rating"] == 4.5 and joes["review_count"] == 30)
+test("Distinct sources joined", joes["source"] == "ads, maps, organic")
+test("Most recent scraped_at kept, offsets applied", joes["scraped_at"] == "2024-05-31T23:30:00")
+test("Phone numbers match across formats", merged[1]["source"] == "maps" and groups[1] == [1, 4])
+test("Directory listings don't join on domain", groups[2:] == [[5], [6]])
+import itertools
+orders = [merge_duplicate_prospects([dupes[i] for i in order])[0] for order in itertools.permutations([0, 2, 3])]
+test("Merge is independent of order within a group", all(m == orders[0] for m in orders))
+test("Strict threshold leaves near names apart",
+     merge_duplicate_prospects([{"name": "Smith Dental"}, {"name": "Smith Dental Clinic"}], name_threshold=1.0)[1]
+     == [[0], [1]])
+test("Identical names merge",
+     merge_duplicate_prospects([{"name": "Acme Pty Ltd"}, {"name": "ACME"}], name_threshold=1.0)[1] == [[0, 1]])
+test("Names don't join different domains",
+     merge_duplicate_prospects([{"name": "Acme", "website": "acme.com"}, {"name": "Acme", "website": "acme.net"}])[1]
+     == [[0], [1]])
+test("A domainless record doesn't bridge two domains",
+     merge_duplicate_prospects([{"name": "Joes Plumbing", "website": "https://joesplumbing.com.au"},
+                                {"name": "Joes Plumbing", "website": None},
+                                {"name": "Joes Plumbing", "website": "https://joes-plumbing-sydney.com.au"}])[1]
+     == [[0, 1], [2]])
+rng = random.Random(7)
+bulk = [{"name": "".join(rng.choice("abcdefghijklmnopqrstuvwxyz") for _ in range(12))} for _ in range(3000)]
+bulk.append({"name": bulk[10]["name"].upper()})
+bulk.append({"name": bulk[2000]["name"] + "s"})
+test("Large maps-only batch finds its near names",
+     [g for g in merge_duplicate_prospects(bulk, name_threshold=0.9)[1] if len(g) > 1] == [[10, 3000], [2000, 3001]])
+test("Empty input", merge_duplicate_prospects([]) == ([], []))
+try:
+    merge_duplicate_prospects(dupes, name_threshold=0)
+    test("Invalid name_threshold raises ValueError", False)
+except ValueError:
+    test("Invalid name_threshold raises ValueError", True)
+
+print("\n[score_prospects_batch as_numpy]")
+numpy_prospects = [{"website": f"https://n{i}.example", "phone": "1" if i % 2 else None, "rating": 3.5 + (i % 3) * 0.5,
+                    "review_count": i * 3, "found_in_maps": i % 4 == 0,
+                    "signals": {"has_google_analytics": i % 5 == 0, "reachable": i % 7 != 3}} for i in range(40)]
+try:
+    import numpy as np
+except ImportError:
+    np = None
+if np is None:
+    try:
+        score_prospects_batch(numpy_prospects, as_numpy=True)
+        test("as_numpy without NumPy raises ImportError", False)
+    except ImportError as e:
+        test("as_numpy without NumPy raises ImportError", "NumPy" in str(e))
+else:
+    fit_np, opp_np, priority_np = score_prospects_batch(numpy_prospects, as_numpy=True)
+    listed = score_prospects_batch(numpy_prospects)
+    test("as_numpy dtypes", (fit_np.dtype, opp_np.dtype, priority_np.dtype) == (np.int64, np.int64, np.float64))
+    test("as_numpy scores match the list path",
+         fit_np.tolist() == [f for f, _ in listed] and opp_np.tolist() == [o for _, o in listed])
+    test("as_numpy priority matches the dict path",
+         priority_np.tolist() == score_prospects_batch(numpy_prospects, explain=True)["priority"])
+    excluded_np = score_prospects_batch(numpy_prospects, unreachable_policy="exclude", as_numpy=True)
+    test("as_numpy keeps exclusion sentinels",
+         excluded_np[0][3] == -1 and excluded_np[2][3] == -1.0
+         and excluded_np[2].tolist() == score_prospects_batch(numpy_prospects, unreachable_policy="exclude",
+                                                              explain=True)["priority"])
+    empty_np = score_prospects_batch([], as_numpy=True)
+    test("as_numpy on an empty batch", [len(a) for a in empty_np] == [0, 0, 0])
+try:
+    score_prospects_batch(numpy_prospects, as_numpy=True, explain=True)
+    test("as_numpy with dict options raises ValueError", False)
+except ValueError:
+    test("as_numpy with dict options raises ValueError", True)
+
+print("\n[score_prospects_incremental]")
+nightly = [{"name": f"Biz {i}", "website": f"https://inc{i}.example", "phone": "1" if i % 2 else None,
+            "rating": 4.0 + (i % 2) * 0.5, "review_count": i * 5, "opportunity_notes": "old notes",
+            "signals": {"has_google_analytics": i % 3 == 0, "load_time_ms": 1000 + i * 400}} for i in range(12)]
+first_run = score_prospects_incremental(nightly)
+test("First run scores everything", first_run["misses"] == 12 and first_run["hits"] == 0)
+test("Incremental scores match the batch", first_run["scores"] == score_prospects_batch(nightly))
+test("Incremental priority matches the batch",
+     first_run["priority"] == score_prospects_batch(nightly, explain=True)["priority"])
+test("Hashes match prospect_content_hash", first_run["hashes"] == [prospect_content_hash(p) for p in nightly])
+cosmetic = [dict(p, opportunity_notes="rewritten notes", name=p["name"].upper()) for p in nightly]
+test("Non-scoring fields don't change the hash", prospect_content_hash(cosmetic[0]) == prospect_content_hash(nightly[0]))
+second_run = score_prospects_incremental(cosmetic, first_run["cache"])
+test("Cosmetic change is a cache hit", second_run["hits"] == 12 and second_run["misses"] == 0
+     and second_run["scores"] == first_run["scores"])
+changed_night = [dict(p) for p in nightly]
+changed_night[4] = dict(changed_night[4], found_in_maps=True)
+changed_night.append({"website": "https://new.example"})
+third_run = score_prospects_incremental(changed_night, second_run["cache"])
+test("Changed and new prospects are rescored", third_run["hits"] == 11 and third_run["misses"] == 2
+     and third_run["scores"] == score_prospects_batch(changed_night))
+test("Cache covers the current batch", sorted(third_run["cache"]) == sorted(set(third_run["hashes"])))
+test("Numeric strings hash like numbers",
+     prospect_content_hash({"rating": "4.5", "review_count": "12"}) == prospect_content_hash({"rating": 4.5, "review_count": 12}))
+test("Scoring fields change the hash",
+     prospect_content_hash({"rating": 4.5}) != prospect_content_hash({"rating": 4.0}))
+stale_cache = {first_run["hashes"][0]: (1, 2, 3.0)}
+test("Cached values are reused as given", score_prospects_incremental(nightly[:1], stale_cache)["scores"] == [(1, 2)])
+trades_run = score_prospects_incremental(nightly, first_run["cache"], profile="trades")
+test("Another profile misses the cache", trades_run["misses"] == len(nightly)
+     and trades_run["scores"] == score_prospects_batch(nightly, profile="trades"))
+test("Hashes cover the profile", trades_run["hashes"] == [prospect_content_hash(p, "trades") for p in nightly])
+set_priority_formula("geometric_mean")
+formula_run = score_prospects_incremental(nightly, first_run["cache"])
+set_priority_formula("weighted_sum")
+test("Another priority formula misses the cache", formula_run["misses"] == len(nightly))
+
+print("\n[calculate_confidence]")
+full = {"website": "https://full.example", "phone": "0400 000 000", "address": "1 Main St",
+        "emails": ["a@full.example", "b@full.example"], "rating": 4.5, "review_count": 30,
+        "signals": {"cms": "WordPress", "emails": ["c@full.example", "A@full.example"]}}
+test("Fully covered prospect scores 100", calculate_confidence(full) == 100)
+test("Empty prospect scores 0", calculate_confidence({}) == 0)
+test("Blank strings don't count", calculate_confidence({"website": " ", "phone": ""}) == 0)
+test("Website alone earns its weight", calculate_confidence({"website": "https://a.example"}) == 20)
+test("Emails earn a third per distinct address",
+     calculate_confidence({"emails": ["x@a.example", "X@a.example"]}) == 5)
+test("Custom weights rescale to 0-100",
+     calculate_confidence({"phone": "1"}, {"website": 0, "emails": 0, "rating": 0, "review_count": 0,
+                                           "signals": 0, "address": 0}) == 100)
+test("Confidence ignores score values",
+     calculate_confidence(dict(full, fit_score=0, opportunity_score=0)) == calculate_confidence(full))
+try:
+    calculate_confidence(full, {"fax": 5})
+    test("Unknown confidence field raises", False)
+except ValueError as e:
+    test("Unknown confidence field raises", "valid fields" in str(e))
+conf_batch = score_prospects_batch([full, {"website": "https://a.example"}], with_confidence=True)
+test("Batch returns confidence per prospect", conf_batch["confidence"] == [100, 20])
+conf_explain = score_prospects_batch([full], explain=True)
+test("Explain includes the confidence breakdown",
+     conf_explain["explanations"][0]["confidence"]["total"] == 100
+     and "confidence" not in conf_explain)
+test("Confidence weights imply with_confidence",
+     score_prospects_batch([{"phone": "1"}], confidence_weights={"phone": 85})["confidence"] == [50])
+try:
+    score_prospects_batch([full], with_confidence=True, as_numpy=True)
+    test("with_confidence rejects as_numpy", False)
+except ValueError:
+    test("with_confidence rejects as_numpy", True)
+confident = [dict(full, confidence=100), {"name": "Sparse"}]
+conf_csv = serialize_prospects_csv(confident).splitlines()
+test("CSV adds a confidence column", conf_csv[0].endswith(",confidence") and conf_csv[1].endswith(",100")
+     and conf_csv[2].endswith(","))
+confidence_last = serialize_prospects_csv([dict(full, confidence=100, reputation="low_rating", tier="nurture")])
+test("Confidence column goes last", confidence_last.splitlines()[0].split(",")[-3:] == ["reputation", "tier", "confidence"])
+test("CSV omits confidence when unset", "confidence" not in serialize_prospects_csv([full]))
+conf_json = json.loads(serialize_prospects_json(confident, False))
+test("JSON scores carry confidence when set",
+     conf_json[0]["scores"]["confidence"] == 100 and "confidence" not in conf_json[1]["scores"])
+
+print("\n[score_statistics]")
+dist = score_statistics([10, 20, 30, 40, 50, 60, 70, 80, 90, 100], bins=3)
+test("Summary values", dist["count"] == 10 and dist["mean"] == 55.0 and dist["median"] == 55.0
+     and dist["min"] == 10 and dist["max"] == 100 and abs(dist["std"] - 28.7228) < 1e-3)
+test("Deciles interpolate like NumPy", len(dist["deciles"]) == 9 and dist["deciles"][0] == 19.0
+     and dist["deciles"][-1] == 91.0)
+test("Histogram edges and counts", dist["histogram"]["edges"] == [10.0, 40.0, 70.0, 100.0]
+     and dist["histogram"]["counts"] == [3, 3, 4])
+test("Default bins is 10", len(score_statistics([1, 2, 3])["histogram"]["counts"]) == 10)
+single = score_statistics([42.0], bins=2)
+test("Single value gets a unit-wide range",
+     single["histogram"]["edges"] == [41.5, 42.0, 42.5] and single["histogram"]["counts"] == [0, 1])
+empty_stats = score_statistics([])
+test("Empty input is an empty summary", empty_stats["count"] == 0 and empty_stats["mean"] is None
+     and empty_stats["deciles"] == [] and empty_stats["histogram"] == {"edges": [], "counts": []})
+for bad_args in (([1.0], 0), ([float("nan")], 10)):
+    try:
+        score_statistics(*bad_args)
+        test(f"Rejects {bad_args}", False)
+    except ValueError:
+        test(f"Rejects {bad_args}", True)
+
+print("\n[flag_outliers]")
+spread = [50, 52, 48, 51, 49, 50, 95, 3]
+test("IQR flags both tails", flag_outliers(spread) == [6, 7])
+test("Larger k flags fewer", flag_outliers(spread, "iqr", 20.0) == [])
+test("Z-score flags beyond k sigma", flag_outliers(spread, "zscore", 1.5) == [6, 7])
+test("Z-score default is three sigma", flag_outliers(spread, "zscore") == [])
+test("Equal scores flag nothing", flag_outliers([5, 5, 5], "zscore", 0.0) == [])
+test("Empty scores flag nothing", flag_outliers([]) == [])
+try:
+    flag_outliers(spread, "mad")
+    test("Unknown outlier method raises", False)
+except ValueError as e:
+    test("Unknown outlier method raises", "iqr" in str(e))
+stats_batch = score_prospects_batch(prospects, with_stats=True)
+test("Batch stats match score_statistics",
+     stats_batch["stats"]["fit"] == score_statistics([f for f, _ in stats_batch["scores"]])
+     and stats_batch["stats"]["priority"] == score_statistics(stats_batch["priority"]))
+excluded_stats = score_prospects_batch(
+    [{"website": "https://a.example", "signals": {"reachable": False}}, {"website": "https://b.example"}],
+    unreachable_policy="exclude", with_stats=True)
+test("Batch stats skip excluded prospects", excluded_stats["stats"]["fit"]["count"] == 1)
+
 # --- V1 sanity check ---
 print("\n[v1 sanity check]")
 test("normalize_domain works", normalize_domain("https://www.example.com/page") == "example.com")
@@ -229,6 +1700,12 @@ for _ in range(100):
     extract_html_metadata(big_html)
 rust_meta = time.perf_counter() - start
 
+batch_pages = [(str(i), big_html) for i in range(100)]
+start = time.perf_counter()
+extract_html_metadata_batch(batch_pages)
+rust_batch = time.perf_counter() - start
+print(f"HTML metadata batch (x100):     Batch {rust_batch*1000:.1f}ms  Loop {rust_meta*1000:.1f}ms  Speedup: {rust_meta/rust_batch:.1f}x")
+
 from bs4 import BeautifulSoup
 start = time.perf_counter()
 for _ in range(100):